@@ -14,9 +14,21 @@ pub fn device_macro_derive(input: TokenStream) -> TokenStream {
 
     let gen = quote! {
         impl Device for #name {
-            fn get_attribute(&self, name: &str) -> Attribute {
+            fn get_attribute(&self, name: &str) -> Ev3Result<Attribute> {
                 self.driver.get_attribute(name)
             }
+
+            fn is_connected(&self) -> bool {
+                self.driver.is_connected()
+            }
+
+            fn reconnect(&self) -> Ev3Result<()> {
+                self.driver.reconnect()
+            }
+
+            fn clear_cache(&self) {
+                self.driver.clear_cache()
+            }
         }
     };
     gen.into()
@@ -27,8 +39,34 @@ pub fn sensor_macro_derive(input: TokenStream) -> TokenStream {
     let ast: syn::DeriveInput = syn::parse(input).unwrap();
 
     let name = &ast.ident;
-    let gen = quote! {
-        impl Sensor for #name {}
+
+    // Sensors with mode dependent getters store the flag of the automatic mode switch in this field.
+    let has_auto_mode_switch = match &ast.data {
+        syn::Data::Struct(data) => data.fields.iter().any(|field| {
+            field
+                .ident
+                .as_ref()
+                .is_some_and(|ident| ident == "auto_mode_switch")
+        }),
+        _ => false,
+    };
+
+    let gen = if has_auto_mode_switch {
+        quote! {
+            impl Sensor for #name {
+                fn get_auto_mode_switch(&self) -> bool {
+                    self.auto_mode_switch
+                }
+
+                fn set_auto_mode_switch(&mut self, enabled: bool) {
+                    self.auto_mode_switch = enabled;
+                }
+            }
+        }
+    } else {
+        quote! {
+            impl Sensor for #name {}
+        }
     };
     gen.into()
 }
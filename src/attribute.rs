@@ -1,28 +1,60 @@
 //! A wrapper to a attribute file commonly in the `/sys/class/` directory.
 use std::error::Error;
+use std::fmt;
 use std::fs::{self, File, OpenOptions};
-use std::io::{Read, Seek, SeekFrom, Write};
-use std::os::unix::fs::PermissionsExt;
+use std::io;
+use std::os::unix::fs::{FileExt, PermissionsExt};
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::{Path, PathBuf};
+use std::str;
 use std::string::String;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
 
+use crate::backend::AttributeSource;
 use crate::driver::DRIVER_PATH;
 use crate::utils::OrErr;
-use crate::{Ev3Error, Ev3Result};
+use crate::{wait, Ev3Error, Ev3Result};
+
+/// Initial size of the read buffer. Most attribute values are much shorter.
+const INITIAL_BUFFER_SIZE: usize = 64;
 
 /// A wrapper to a attribute file in the `/sys/class/` directory.
-#[derive(Debug, Clone)]
+///
+/// The file is opened once and accessed with positioned reads and writes (`pread`/`pwrite`),
+/// so no seek is necessary. Reads reuse a buffer that is shared by all clones of the attribute.
+///
+/// Attributes of other backends, e.g. a remote brick, are not backed by a file but by an `AttributeSource`.
+#[derive(Clone)]
 pub struct Attribute {
+    storage: Arc<RwLock<Storage>>,
+    buffer: Arc<Mutex<Vec<u8>>>,
+    reopen: Option<Reopen>,
+}
+
+/// Opens an attribute again after its device was re-plugged. Set by reconnectable drivers.
+pub(crate) type Reopen = Arc<dyn Fn() -> Ev3Result<Attribute> + Send + Sync>;
+
+/// Storage of the attribute value. It is shared by all clones of the attribute
+/// and replaced if the attribute is reopened after its device was re-plugged.
+#[derive(Clone)]
+struct Storage {
     file_path: PathBuf,
-    file: Arc<Mutex<File>>,
+    handle: Handle,
+}
+
+/// Handle of the attribute value.
+#[derive(Clone)]
+enum Handle {
+    File(Arc<File>),
+    Source(Arc<dyn AttributeSource>),
 }
 
 impl Attribute {
     /// Create a new `Attribute` instance for the given path.
     pub fn from_path(path: &Path) -> Ev3Result<Attribute> {
-        let stat = fs::metadata(path)?;
+        let stat = fs::metadata(path).map_err(Ev3Error::io(path))?;
 
         let mode = stat.permissions().mode();
 
@@ -33,12 +65,56 @@ impl Attribute {
         let file = OpenOptions::new()
             .read(readable)
             .write(writeable)
-            .open(path)?;
+            .open(path)
+            .map_err(Ev3Error::io(path))?;
 
-        Ok(Attribute {
-            file_path: PathBuf::from(path),
-            file: Arc::new(Mutex::new(file)),
-        })
+        Ok(Attribute::new(
+            path,
+            Handle::File(Arc::new(file)),
+            vec![0; INITIAL_BUFFER_SIZE],
+        ))
+    }
+
+    /// Create a new `Attribute` instance that is not backed by a file but by the given `source`.
+    /// The `path` is only used for error messages and `get_file_path()`.
+    pub fn from_source(path: &Path, source: Arc<dyn AttributeSource>) -> Attribute {
+        Attribute::new(path, Handle::Source(source), Vec::new())
+    }
+
+    fn new(path: &Path, handle: Handle, buffer: Vec<u8>) -> Attribute {
+        Attribute {
+            storage: Arc::new(RwLock::new(Storage {
+                file_path: PathBuf::from(path),
+                handle,
+            })),
+            buffer: Arc::new(Mutex::new(buffer)),
+            reopen: None,
+        }
+    }
+
+    /// Sets the function that opens the attribute again if an access fails because the device was re-plugged.
+    pub(crate) fn with_reopen(mut self, reopen: Reopen) -> Attribute {
+        self.reopen = Some(reopen);
+        self
+    }
+
+    /// Runs `op` on the storage and runs it once more on the reopened attribute if it failed with `ENODEV` or `ENOENT`,
+    /// i.e. the device was unplugged. The reopened storage replaces the stale one for all clones of this attribute.
+    /// Returns the original error if the attribute cannot be reopened.
+    fn retry_reopened<R>(&self, mut op: impl FnMut(&Storage) -> Ev3Result<R>) -> Ev3Result<R> {
+        let result = op(&self.storage.read().unwrap());
+        match result {
+            Err(e) if is_stale(&e) => match self.reopen.as_ref().and_then(|reopen| reopen().ok()) {
+                Some(attribute) => {
+                    let storage = attribute.storage.read().unwrap().clone();
+                    let mut current = self.storage.write().unwrap();
+                    *current = storage;
+                    op(&current)
+                }
+                None => Err(e),
+            },
+            result => result,
+        }
     }
 
     /// Create a new `Attribute` instance that wrap's
@@ -94,10 +170,12 @@ impl Attribute {
         discriminator_path: &str,
         discriminator_value: &str,
     ) -> Ev3Result<Attribute> {
-        let paths = fs::read_dir(driver_path)?;
+        let paths = fs::read_dir(driver_path).map_err(Ev3Error::io(driver_path.as_ref()))?;
 
         for path_result in paths {
-            let path_buf = path_result?.path();
+            let path_buf = path_result
+                .map_err(Ev3Error::io(driver_path.as_ref()))?
+                .path();
             let current_path = path_buf.to_str().or_err()?;
 
             let discriminator_attribute = Attribute::from_path(&PathBuf::from(format!(
@@ -115,47 +193,68 @@ impl Attribute {
             msg: format!(
                 "Attribute `{attribute_path}` at driver path `{driver_path}` could not be found!"
             ),
+            path: Some(PathBuf::from(driver_path)),
         })
     }
 
-    /// Returns the current value of the wrapped file.
-    fn get_str(&self) -> Ev3Result<String> {
-        let mut value = String::new();
-        let mut file = self.file.lock().unwrap();
-        file.seek(SeekFrom::Start(0))?;
-        file.read_to_string(&mut value)?;
-        Ok(value.trim_end().to_owned())
+    /// Reads the complete content of the wrapped file into the shared buffer and passes it to `f`.
+    fn read_with<R>(&self, f: impl FnOnce(&[u8]) -> Ev3Result<R>) -> Ev3Result<R> {
+        #[cfg(feature = "tracing")]
+        let start = Instant::now();
+
+        let mut buffer = self.buffer.lock().unwrap();
+        let len = self.retry_reopened(|storage| storage.read_into(&mut buffer));
+
+        #[cfg(feature = "tracing")]
+        trace_io(
+            "read",
+            &self.get_file_path(),
+            start,
+            len.as_ref().map(|len| &buffer[..*len]),
+        );
+
+        f(&buffer[..len?])
+    }
+
+    /// Reads the wrapped file as trimmed utf8 string slice and passes it to `f`.
+    fn read_str_with<R>(&self, f: impl FnOnce(&str) -> Ev3Result<R>) -> Ev3Result<R> {
+        self.read_with(|data| match str::from_utf8(data) {
+            Ok(value) => f(value.trim_end()),
+            Err(err) => Err(Ev3Error::Parse {
+                path: self.get_file_path(),
+                value: String::from_utf8_lossy(data).into_owned(),
+                msg: format!("{err}"),
+            }),
+        })
     }
 
     /// Sets the value of the wrapped file.
-    /// Returns a `Ev3Result::InternalError` if the file is not writable.
+    /// Returns a `Ev3Result::Io` error if the file is not writable.
     fn set_str(&self, value: &str) -> Ev3Result<()> {
-        let mut file = self.file.lock().unwrap();
-        file.seek(SeekFrom::Start(0))?;
-        file.write_all(value.as_bytes())?;
-        Ok(())
+        self.write_at(0, value.as_bytes())
     }
 
     /// Returns the current value of the wrapped file.
     /// The value is parsed to the type `T`.
-    /// Returns a `Ev3Result::InternalError` if the current value is not parsable to type `T`.
+    /// Returns a `Ev3Result::Parse` error if the current value is not parsable to type `T`.
     pub fn get<T>(&self) -> Ev3Result<T>
     where
         T: std::str::FromStr,
         <T as std::str::FromStr>::Err: Error,
     {
-        let value = self.get_str()?;
-        match value.parse::<T>() {
+        self.read_str_with(|value| match value.parse::<T>() {
             Ok(value) => Ok(value),
-            Err(err) => Err(Ev3Error::InternalError {
+            Err(err) => Err(Ev3Error::Parse {
+                path: self.get_file_path(),
+                value: value.to_owned(),
                 msg: format!("{err}"),
             }),
-        }
+        })
     }
 
     /// Sets the value of the wrapped file.
     /// The value is parsed from the type `T`.
-    /// Returns a `Ev3Result::InternalError` if the file is not writable.
+    /// Returns a `Ev3Result::Io` error if the file is not writable.
     pub fn set<T>(&self, value: T) -> Ev3Result<()>
     where
         T: std::string::ToString,
@@ -166,7 +265,7 @@ impl Attribute {
     #[inline]
     /// Sets the value of the wrapped file.
     /// This function skips the string parsing of the `self.set<T>()` function.
-    /// Returns a `Ev3Result::InternalError` if the file is not writable.
+    /// Returns a `Ev3Result::Io` error if the file is not writable.
     pub fn set_str_slice(&self, value: &str) -> Ev3Result<()> {
         self.set_str(value)
     }
@@ -174,31 +273,374 @@ impl Attribute {
     /// Returns a string vector representation of the wrapped file.
     /// The file value is splitted at whitespace's.
     pub fn get_vec(&self) -> Ev3Result<Vec<String>> {
-        let value = self.get_str()?;
-        let vec = value
-            .split_whitespace()
-            .map(|word| word.to_owned())
-            .collect();
-        Ok(vec)
+        self.read_str_with(|value| {
+            Ok(value
+                .split_whitespace()
+                .map(|word| word.to_owned())
+                .collect())
+        })
     }
 
     /// Returns a C pointer to the wrapped file.
+    /// Returns `-1` if the attribute is not backed by a file.
     pub fn get_raw_fd(&self) -> RawFd {
-        self.file.lock().unwrap().as_raw_fd()
+        match &self.storage.read().unwrap().handle {
+            Handle::File(file) => file.as_raw_fd(),
+            Handle::Source(_) => -1,
+        }
     }
 
     /// Returns the path to the wrapped file.
     pub fn get_file_path(&self) -> PathBuf {
-        self.file_path.clone()
+        self.storage.read().unwrap().file_path.clone()
     }
 
     /// Read and return the raw bytes of this attribute
     pub fn get_raw_data(&self) -> Ev3Result<Vec<u8>> {
-        let mut data = Vec::new();
+        self.read_with(|data| Ok(data.to_vec()))
+    }
+
+    /// Read the raw bytes of this attribute into `buf` without allocating.
+    /// Returns the number of bytes read. If `buf` is too small, the data is truncated.
+    pub fn get_raw_data_into(&self, buf: &mut [u8]) -> Ev3Result<usize> {
+        self.read_at(0, buf)
+    }
+
+    /// Read raw bytes starting at `offset` into `buf`. Returns the number of bytes read.
+    /// Used for binary attributes like `direct`, where the offset selects the register.
+    pub fn read_at(&self, offset: u64, buf: &mut [u8]) -> Ev3Result<usize> {
+        #[cfg(feature = "tracing")]
+        let start = Instant::now();
+
+        let len = self.retry_reopened(|storage| match &storage.handle {
+            Handle::File(file) => file
+                .read_at(buf, offset)
+                .map_err(Ev3Error::io(&storage.file_path)),
+            Handle::Source(source) => source.read().map(|data| {
+                let data = data.get(offset as usize..).unwrap_or_default();
+                let len = data.len().min(buf.len());
+                buf[..len].copy_from_slice(&data[..len]);
+                len
+            }),
+        });
+
+        #[cfg(feature = "tracing")]
+        trace_io(
+            "read",
+            &self.get_file_path(),
+            start,
+            len.as_ref().map(|len| &buf[..*len]),
+        );
+
+        len
+    }
+
+    /// Write raw bytes starting at `offset`.
+    /// Used for binary attributes like `direct`, where the offset selects the register.
+    /// Attributes that are not backed by a file only support the offset `0`.
+    pub fn write_at(&self, offset: u64, data: &[u8]) -> Ev3Result<()> {
+        #[cfg(feature = "tracing")]
+        let start = Instant::now();
+
+        let result = self.retry_reopened(|storage| match &storage.handle {
+            Handle::File(file) => file
+                .write_all_at(data, offset)
+                .map_err(Ev3Error::io(&storage.file_path)),
+            Handle::Source(source) if offset == 0 => source.write(data),
+            Handle::Source(_) => Err(Ev3Error::InternalError {
+                msg: format!(
+                    "Writing at offset {offset} is not supported by {}",
+                    storage.file_path.display()
+                ),
+                path: Some(storage.file_path.clone()),
+            }),
+        });
+
+        #[cfg(feature = "tracing")]
+        trace_io(
+            "write",
+            &self.get_file_path(),
+            start,
+            result.as_ref().map(|_| data),
+        );
+
+        result
+    }
+
+    /// Returns an iterator over the changes of this attribute.
+    ///
+    /// Only works for attributes that signal changes with `poll(2)`, like the motor `state` or the port `status`.
+    /// Attributes that are not backed by a file are polled for changes instead.
+    /// Each item is the new value after a change. The iterator ends if the `timeout` passes without a change
+    /// or after the first error. If the `timeout` is `None` it will wait an infinite time.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ev3dev_lang_rust::Device;
+    /// use ev3dev_lang_rust::motors::LargeMotor;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let motor = LargeMotor::find()?;
+    ///
+    /// for state in motor.get_read_only_attribute("state")?.watch(None) {
+    ///     println!("New motor state: {}", state?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch(&self, timeout: Option<Duration>) -> AttributeWatch {
+        AttributeWatch {
+            attribute: self.clone(),
+            timeout,
+            armed: None,
+            done: false,
+        }
+    }
+
+    /// Polls the attribute until its value differs from `last`.
+    /// Returns `None` if the `timeout` passes without a change.
+    fn poll_change(&self, last: &str, timeout: Option<Duration>) -> Option<Ev3Result<String>> {
+        let start = Instant::now();
+        loop {
+            match self.get::<String>() {
+                Ok(value) if value == last => {}
+                value => return Some(value),
+            }
+            if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+                return None;
+            }
+            thread::sleep(wait::POLL_INTERVAL);
+        }
+    }
+}
+
+impl Storage {
+    /// Reads the complete content of the wrapped file into `buffer` and returns its length.
+    fn read_into(&self, buffer: &mut Vec<u8>) -> Ev3Result<usize> {
+        let file = match &self.handle {
+            Handle::File(file) => file,
+            Handle::Source(source) => {
+                let data = source.read()?;
+                buffer.clear();
+                buffer.extend_from_slice(&data);
+                return Ok(data.len());
+            }
+        };
+
+        let mut len = 0;
+        loop {
+            let n = file
+                .read_at(&mut buffer[len..], len as u64)
+                .map_err(Ev3Error::io(&self.file_path))?;
+            len += n;
+
+            // sysfs returns the whole value with the first read if the buffer is large enough.
+            if len < buffer.len() {
+                return Ok(len);
+            }
+            let new_len = buffer.len() * 2;
+            buffer.resize(new_len, 0);
+        }
+    }
+}
+
+/// Checks if `error` means that the device of the attribute was unplugged.
+fn is_stale(error: &Ev3Error) -> bool {
+    match error {
+        Ev3Error::Io { source, .. } => {
+            source.kind() == io::ErrorKind::NotFound || source.raw_os_error() == Some(libc::ENODEV)
+        }
+        _ => false,
+    }
+}
+
+/// Iterator over the changes of an attribute. Created by `Attribute::watch()`.
+#[derive(Debug, Clone)]
+pub struct AttributeWatch {
+    attribute: Attribute,
+    timeout: Option<Duration>,
+    /// Value of the last read, `None` until the watch is armed.
+    armed: Option<String>,
+    done: bool,
+}
+
+impl Iterator for AttributeWatch {
+    type Item = Ev3Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // A change is only signaled if the attribute was read since the last change.
+        let last = match self.armed.take() {
+            Some(last) => last,
+            None => match self.attribute.get::<String>() {
+                Ok(value) => value,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            },
+        };
+
+        let fd = self.attribute.get_raw_fd();
+        let value = if fd >= 0 {
+            if wait::wait_file_changes(fd, self.timeout) {
+                Some(self.attribute.get::<String>())
+            } else {
+                None
+            }
+        } else {
+            self.attribute.poll_change(&last, self.timeout)
+        };
+
+        match &value {
+            Some(Ok(value)) => self.armed = Some(value.clone()),
+            _ => self.done = true,
+        }
+        value
+    }
+}
+
+/// Emits a `tracing` event for an attribute access that started at `start`.
+/// Successful accesses are `trace` events with the value, failed accesses are `debug` events with the error.
+#[cfg(feature = "tracing")]
+fn trace_io(operation: &str, path: &Path, start: Instant, result: Result<&[u8], &Ev3Error>) {
+    let elapsed_us = start.elapsed().as_micros() as u64;
+    match result {
+        Ok(data) => tracing::trace!(
+            operation,
+            path = %path.display(),
+            value = %String::from_utf8_lossy(data).trim_end(),
+            elapsed_us,
+            "attribute {operation}"
+        ),
+        Err(error) => tracing::debug!(
+            operation,
+            path = %path.display(),
+            %error,
+            elapsed_us,
+            "attribute {operation} failed"
+        ),
+    }
+}
+
+impl fmt::Debug for Attribute {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Attribute")
+            .field("file_path", &self.get_file_path())
+            .finish()
+    }
+}
+
+/// Read-only view of an `Attribute`, e.g. for `value0` or `state`.
+///
+/// Writing to a read-only attribute is a compile error:
+///
+/// ```compile_fail
+/// use ev3dev_lang_rust::ReadOnlyAttribute;
+///
+/// fn reset(value0: &ReadOnlyAttribute) {
+///     value0.set(0).unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReadOnlyAttribute(Attribute);
+
+impl ReadOnlyAttribute {
+    /// Returns the current value of the wrapped file.
+    /// The value is parsed to the type `T`.
+    /// Returns a `Ev3Result::Parse` error if the current value is not parsable to type `T`.
+    pub fn get<T>(&self) -> Ev3Result<T>
+    where
+        T: std::str::FromStr,
+        <T as std::str::FromStr>::Err: Error,
+    {
+        self.0.get()
+    }
+
+    /// Returns a string vector representation of the wrapped file.
+    /// The file value is splitted at whitespace's.
+    pub fn get_vec(&self) -> Ev3Result<Vec<String>> {
+        self.0.get_vec()
+    }
+
+    /// Read and return the raw bytes of this attribute
+    pub fn get_raw_data(&self) -> Ev3Result<Vec<u8>> {
+        self.0.get_raw_data()
+    }
+
+    /// Read the raw bytes of this attribute into `buf` without allocating.
+    /// Returns the number of bytes read. If `buf` is too small, the data is truncated.
+    pub fn get_raw_data_into(&self, buf: &mut [u8]) -> Ev3Result<usize> {
+        self.0.get_raw_data_into(buf)
+    }
+
+    /// Returns a C pointer to the wrapped file.
+    pub fn get_raw_fd(&self) -> RawFd {
+        self.0.get_raw_fd()
+    }
+
+    /// Returns an iterator over the changes of this attribute. See `Attribute::watch()`.
+    pub fn watch(&self, timeout: Option<Duration>) -> AttributeWatch {
+        self.0.watch(timeout)
+    }
+
+    /// Returns the path to the wrapped file.
+    pub fn get_file_path(&self) -> PathBuf {
+        self.0.get_file_path()
+    }
+}
+
+impl From<Attribute> for ReadOnlyAttribute {
+    fn from(attribute: Attribute) -> Self {
+        ReadOnlyAttribute(attribute)
+    }
+}
+
+/// Write-only view of an `Attribute`, e.g. for `command`.
+/// Attributes that can be read and written use `Attribute` itself.
+///
+/// Reading a write-only attribute is a compile error:
+///
+/// ```compile_fail
+/// use ev3dev_lang_rust::WritableAttribute;
+///
+/// fn last_command(command: &WritableAttribute) -> String {
+///     command.get().unwrap()
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct WritableAttribute(Attribute);
+
+impl WritableAttribute {
+    /// Sets the value of the wrapped file.
+    /// The value is parsed from the type `T`.
+    /// Returns a `Ev3Result::Io` error if the file is not writable.
+    pub fn set<T>(&self, value: T) -> Ev3Result<()>
+    where
+        T: std::string::ToString,
+    {
+        self.0.set(value)
+    }
+
+    /// Sets the value of the wrapped file.
+    /// This function skips the string parsing of the `self.set<T>()` function.
+    /// Returns a `Ev3Result::Io` error if the file is not writable.
+    pub fn set_str_slice(&self, value: &str) -> Ev3Result<()> {
+        self.0.set_str_slice(value)
+    }
+
+    /// Returns the path to the wrapped file.
+    pub fn get_file_path(&self) -> PathBuf {
+        self.0.get_file_path()
+    }
+}
 
-        let mut file = self.file.lock().unwrap();
-        file.seek(SeekFrom::Start(0))?;
-        file.read_to_end(&mut data)?;
-        Ok(data)
+impl From<Attribute> for WritableAttribute {
+    fn from(attribute: Attribute) -> Self {
+        WritableAttribute(attribute)
     }
 }
@@ -0,0 +1,93 @@
+//! Backends that provide the devices and attributes of a `Driver`.
+//!
+//! By default all devices are read from `/sys/class/` on the brick itself (`Sysfs`).
+//! Other backends, e.g. a remote brick, provide the same devices to the same device types,
+//! so the control code does not depend on where the devices are.
+
+use std::fmt::Debug;
+use std::path::Path;
+use std::sync::Arc;
+
+use crate::driver::{read_class_dir, DRIVER_PATH};
+use crate::utils::OrErr;
+use crate::{Attribute, Ev3Error, Ev3Result};
+
+/// Provides the devices of a `Driver` and opens their attributes.
+pub trait Backend: Debug + Send + Sync {
+    /// Returns the names of all devices of the class `class_name`, e.g. `motor0`.
+    fn find_names(&self, class_name: &str) -> Ev3Result<Vec<String>>;
+
+    /// Opens the attribute `attribute_name` of the device `class_name/name`.
+    fn open_attribute(
+        &self,
+        class_name: &str,
+        name: &str,
+        attribute_name: &str,
+    ) -> Ev3Result<Attribute>;
+
+    /// Checks if the device `class_name/name` exists.
+    fn is_connected(&self, class_name: &str, name: &str) -> bool {
+        self.find_names(class_name)
+            .is_ok_and(|names| names.iter().any(|n| n == name))
+    }
+}
+
+impl<B: Backend + ?Sized> Backend for Arc<B> {
+    fn find_names(&self, class_name: &str) -> Ev3Result<Vec<String>> {
+        (**self).find_names(class_name)
+    }
+
+    fn open_attribute(
+        &self,
+        class_name: &str,
+        name: &str,
+        attribute_name: &str,
+    ) -> Ev3Result<Attribute> {
+        (**self).open_attribute(class_name, name, attribute_name)
+    }
+
+    fn is_connected(&self, class_name: &str, name: &str) -> bool {
+        (**self).is_connected(class_name, name)
+    }
+}
+
+/// The devices in `/sys/class/` of this brick. This is the default backend.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sysfs;
+
+impl Backend for Sysfs {
+    fn find_names(&self, class_name: &str) -> Ev3Result<Vec<String>> {
+        let mut names = Vec::new();
+        let class_path = Path::new(DRIVER_PATH).join(class_name);
+        for path in read_class_dir(class_name)? {
+            let file_name = path.map_err(Ev3Error::io(&class_path))?.file_name();
+            names.push(file_name.to_str().or_err()?.to_owned());
+        }
+
+        Ok(names)
+    }
+
+    fn open_attribute(
+        &self,
+        class_name: &str,
+        name: &str,
+        attribute_name: &str,
+    ) -> Ev3Result<Attribute> {
+        Attribute::from_sys_class(class_name, name, attribute_name)
+    }
+
+    fn is_connected(&self, class_name: &str, name: &str) -> bool {
+        Path::new(DRIVER_PATH).join(class_name).join(name).exists()
+    }
+}
+
+/// Storage of an attribute that is not a local file, e.g. an attribute of a remote brick.
+///
+/// Created by backends and wrapped with `Attribute::from_source()`.
+pub trait AttributeSource: Debug + Send + Sync {
+    /// Returns the complete value of the attribute.
+    fn read(&self) -> Ev3Result<Vec<u8>>;
+
+    /// Replaces the value of the attribute.
+    fn write(&self, data: &[u8]) -> Ev3Result<()>;
+}
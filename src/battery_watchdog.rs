@@ -0,0 +1,156 @@
+//! Background guard against running the brick on an empty battery.
+
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::motors::emergency_stop_all;
+use crate::task::CancelToken;
+use crate::PowerSupply;
+
+/// Options for a `BatteryWatchdog`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatteryWatchdogOptions {
+    /// The watchdog triggers if the voltage is below this value.
+    pub min_volts: f32,
+    /// Time between two voltage readings.
+    pub interval: Duration,
+    /// Number of consecutive readings below `min_volts` that trigger the watchdog.
+    /// Short voltage drops, e.g. while motors accelerate, are ignored.
+    pub readings: u32,
+    /// Stop all motors (`coast`) when the watchdog triggers.
+    pub stop_motors: bool,
+}
+
+impl Default for BatteryWatchdogOptions {
+    /// Trigger after 3 readings below 6.5 V with an interval of one second and stop all motors.
+    fn default() -> Self {
+        Self {
+            min_volts: 6.5,
+            interval: Duration::from_secs(1),
+            readings: 3,
+            stop_motors: true,
+        }
+    }
+}
+
+/// Polls the voltage of a power supply in a background thread and
+/// calls a handler once if the battery is low.
+///
+/// A brown-out in the middle of a run can corrupt the SD card,
+/// so the watchdog can also stop all motors and raise a flag the main program observes to end normally.
+/// The thread is stopped when the watchdog is dropped.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::control::Loop;
+/// use ev3dev_lang_rust::{BatteryWatchdog, BatteryWatchdogOptions, PowerSupply};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let options = BatteryWatchdogOptions {
+///     min_volts: 6.8,
+///     ..BatteryWatchdogOptions::default()
+/// };
+/// let watchdog = BatteryWatchdog::start(PowerSupply::new()?, options, |volts| {
+///     eprintln!("Battery low: {volts:.2} V");
+/// });
+///
+/// // The control loop ends after the watchdog stopped the motors.
+/// Loop::new(std::time::Duration::from_millis(20))
+///     .cancel_token(watchdog.token())
+///     .run(|_| {
+///         // Run the robot...
+///         Ok(())
+///     })?;
+///
+/// if watchdog.is_triggered() {
+///     std::process::exit(1);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct BatteryWatchdog {
+    triggered: CancelToken,
+    token: CancelToken,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl BatteryWatchdog {
+    /// Starts watching the `power_supply`.
+    ///
+    /// `on_low` is called with the last voltage reading when the watchdog triggers,
+    /// before the motors are stopped. Failed readings are ignored.
+    pub fn start(
+        power_supply: PowerSupply,
+        options: BatteryWatchdogOptions,
+        on_low: impl FnOnce(f32) + Send + 'static,
+    ) -> Self {
+        let triggered = CancelToken::new();
+        let token = CancelToken::new();
+
+        let thread_triggered = triggered.clone();
+        let thread_token = token.clone();
+        let handle = thread::spawn(move || {
+            let mut low_readings = 0;
+
+            while thread_token.sleep(options.interval) {
+                let volts = match power_supply.voltage_volts() {
+                    Ok(volts) => volts,
+                    Err(_) => continue,
+                };
+
+                if volts >= options.min_volts {
+                    low_readings = 0;
+                    continue;
+                }
+
+                low_readings += 1;
+                if low_readings >= options.readings {
+                    on_low(volts);
+
+                    if options.stop_motors {
+                        let _ = emergency_stop_all("coast");
+                    }
+                    thread_triggered.cancel();
+                    return;
+                }
+            }
+        });
+
+        BatteryWatchdog {
+            triggered,
+            token,
+            handle: Some(handle),
+        }
+    }
+
+    /// Checks if the watchdog has triggered, i.e. `on_low` was called and the motors were stopped.
+    /// A triggered watchdog stops polling.
+    pub fn is_triggered(&self) -> bool {
+        self.triggered.is_cancelled()
+    }
+
+    /// Returns a token that is cancelled when the watchdog triggers,
+    /// e.g. for `control::Loop::cancel_token()` or to wait for the trigger with `CancelToken::wait()`.
+    pub fn token(&self) -> CancelToken {
+        self.triggered.clone()
+    }
+
+    /// Stops the watchdog.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.token.cancel();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for BatteryWatchdog {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
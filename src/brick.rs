@@ -0,0 +1,220 @@
+//! System information of the brick, e.g. to report its health to a remote dashboard.
+//!
+//! # Example
+//! ```no_run
+//! use ev3dev_lang_rust::brick;
+//!
+//! # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+//! let os = brick::os_release()?;
+//! let load = brick::load_average()?;
+//! let memory = brick::memory()?;
+//!
+//! println!("{} ({}, kernel {})", brick::hostname()?, os.pretty_name, brick::kernel_version()?);
+//! println!("Load: {:.2}, free memory: {} kB", load.one, memory.available_kb);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::ffi::CStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{Ev3Error, Ev3Result};
+
+const OS_RELEASE_FILE: &str = "/etc/os-release";
+const LOADAVG_FILE: &str = "/proc/loadavg";
+const MEMINFO_FILE: &str = "/proc/meminfo";
+
+fn read_file(path: &str) -> Ev3Result<String> {
+    fs::read_to_string(path).map_err(Ev3Error::io(Path::new(path)))
+}
+
+fn parse_error(path: &str, value: &str, msg: &str) -> Ev3Error {
+    Ev3Error::Parse {
+        path: PathBuf::from(path),
+        value: value.to_owned(),
+        msg: msg.to_owned(),
+    }
+}
+
+/// Kernel and host information as returned by `uname`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Uname {
+    /// Operating system name, e.g. `Linux`.
+    pub sysname: String,
+    /// Hostname of the brick, e.g. `ev3dev`.
+    pub nodename: String,
+    /// Kernel release, e.g. `4.14.117-ev3dev-2.3.5-ev3`.
+    pub release: String,
+    /// Kernel build version.
+    pub version: String,
+    /// Hardware name, e.g. `armv5tejl`.
+    pub machine: String,
+}
+
+/// Returns the `uname` information of the running kernel.
+pub fn uname() -> Ev3Result<Uname> {
+    let mut info: libc::utsname = unsafe { std::mem::zeroed() };
+    if unsafe { libc::uname(&mut info) } != 0 {
+        return Err(Ev3Error::Io {
+            path: None,
+            source: io::Error::last_os_error(),
+        });
+    }
+
+    let field = |chars: &[libc::c_char]| {
+        unsafe { CStr::from_ptr(chars.as_ptr()) }
+            .to_string_lossy()
+            .into_owned()
+    };
+    Ok(Uname {
+        sysname: field(&info.sysname),
+        nodename: field(&info.nodename),
+        release: field(&info.release),
+        version: field(&info.version),
+        machine: field(&info.machine),
+    })
+}
+
+/// Returns the hostname of the brick.
+pub fn hostname() -> Ev3Result<String> {
+    Ok(uname()?.nodename)
+}
+
+/// Returns the kernel release, e.g. `4.14.117-ev3dev-2.3.5-ev3`.
+pub fn kernel_version() -> Ev3Result<String> {
+    Ok(uname()?.release)
+}
+
+/// Operating system identification from `/etc/os-release`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OsRelease {
+    /// `ID`, e.g. `debian`.
+    pub id: String,
+    /// `NAME`, e.g. `Debian GNU/Linux`.
+    pub name: String,
+    /// `VERSION_ID`, e.g. `10`.
+    pub version_id: String,
+    /// `PRETTY_NAME`, e.g. `ev3dev-stretch`.
+    pub pretty_name: String,
+}
+
+impl OsRelease {
+    /// Parses the content of an `os-release` file. Unknown keys are ignored.
+    pub fn parse(content: &str) -> Self {
+        let mut release = OsRelease::default();
+
+        for line in content.lines() {
+            if let Some((key, value)) = line.trim().split_once('=') {
+                let value = value.trim_matches(|c| c == '"' || c == '\'').to_owned();
+                match key {
+                    "ID" => release.id = value,
+                    "NAME" => release.name = value,
+                    "VERSION_ID" => release.version_id = value,
+                    "PRETTY_NAME" => release.pretty_name = value,
+                    _ => {}
+                }
+            }
+        }
+
+        release
+    }
+}
+
+/// Returns the operating system identification of the brick.
+pub fn os_release() -> Ev3Result<OsRelease> {
+    Ok(OsRelease::parse(&read_file(OS_RELEASE_FILE)?))
+}
+
+/// Average number of runnable processes as reported by `/proc/loadavg`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LoadAverage {
+    /// Average over the last minute.
+    pub one: f32,
+    /// Average over the last 5 minutes.
+    pub five: f32,
+    /// Average over the last 15 minutes.
+    pub fifteen: f32,
+}
+
+impl LoadAverage {
+    /// Parses the content of `/proc/loadavg`, e.g. `0.42 0.30 0.25 1/80 1234`.
+    pub fn parse(content: &str) -> Ev3Result<Self> {
+        let values = content
+            .split_whitespace()
+            .take(3)
+            .map(str::parse::<f32>)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| parse_error(LOADAVG_FILE, content.trim(), &err.to_string()))?;
+
+        match values[..] {
+            [one, five, fifteen] => Ok(LoadAverage { one, five, fifteen }),
+            _ => Err(parse_error(
+                LOADAVG_FILE,
+                content.trim(),
+                "Expected three load averages",
+            )),
+        }
+    }
+}
+
+/// Returns the current CPU load of the brick.
+pub fn load_average() -> Ev3Result<LoadAverage> {
+    LoadAverage::parse(&read_file(LOADAVG_FILE)?)
+}
+
+/// Memory usage as reported by `/proc/meminfo`, in kilobytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MemoryInfo {
+    /// Total usable memory.
+    pub total_kb: u64,
+    /// Unused memory.
+    pub free_kb: u64,
+    /// Memory available for new programs, including caches that can be reclaimed.
+    pub available_kb: u64,
+}
+
+impl MemoryInfo {
+    /// Parses the content of `/proc/meminfo`.
+    ///
+    /// Older kernels do not report `MemAvailable`, it falls back to `MemFree` then.
+    pub fn parse(content: &str) -> Ev3Result<Self> {
+        let value = |key: &str| -> Ev3Result<Option<u64>> {
+            let line = content
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .find(|(name, _)| *name == key);
+            match line {
+                Some((_, value)) => {
+                    let value = value.trim().trim_end_matches("kB").trim();
+                    value
+                        .parse()
+                        .map(Some)
+                        .map_err(|err: std::num::ParseIntError| {
+                            parse_error(MEMINFO_FILE, value, &err.to_string())
+                        })
+                }
+                None => Ok(None),
+            }
+        };
+        let required =
+            |key: &str| value(key)?.ok_or_else(|| parse_error(MEMINFO_FILE, key, "Missing key"));
+
+        let free_kb = required("MemFree")?;
+        Ok(MemoryInfo {
+            total_kb: required("MemTotal")?,
+            free_kb,
+            available_kb: value("MemAvailable")?.unwrap_or(free_kb),
+        })
+    }
+}
+
+/// Returns the current memory usage of the brick.
+pub fn memory() -> Ev3Result<MemoryInfo> {
+    MemoryInfo::parse(&read_file(MEMINFO_FILE)?)
+}
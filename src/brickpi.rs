@@ -1,11 +1,7 @@
 //! EV3 specific features
 
-use std::fs;
-use std::path::Path;
-
-use crate::driver::DRIVER_PATH;
-use crate::utils::OrErr;
-use crate::{Attribute, Ev3Result};
+use crate::led::{blink_lit, find_led, set_trigger_all};
+use crate::{Driver, Ev3Result, LedChannel, Trigger};
 
 /// Color type.
 pub type Color = u8;
@@ -13,8 +9,8 @@ pub type Color = u8;
 /// The led's on top of the EV3 brick.
 #[derive(Debug, Clone)]
 pub struct Led {
-    led1: Attribute,
-    led2: Attribute,
+    led1: LedChannel,
+    led2: LedChannel,
 }
 
 impl Led {
@@ -26,45 +22,58 @@ impl Led {
 
     /// Create a new instance of the `Led` struct.
     pub fn new() -> Ev3Result<Led> {
-        let mut led1_name = String::new();
-        let mut led2_name = String::new();
-
-        let paths = fs::read_dir(Path::new(DRIVER_PATH).join("leds"))?;
+        let names = Driver::find_names("leds")?;
 
-        for path in paths {
-            let file_name = path?.file_name();
-            let name = file_name.to_str().or_err()?.to_owned();
+        Ok(Led {
+            led1: find_led(&names, &[&["led1", "brick-status"], &["led1"]])?,
+            led2: find_led(&names, &[&["led2", "brick-status"], &["led2"]])?,
+        })
+    }
 
-            if name.contains("led1") {
-                led1_name = name;
-            } else if name.contains("led2") {
-                led2_name = name;
-            }
-        }
+    /// Returns all leds in `/sys/class/leds/`, including the leds of this struct.
+    pub fn list() -> Ev3Result<Vec<LedChannel>> {
+        LedChannel::list()
+    }
 
-        let led1 = Attribute::from_sys_class("leds", led1_name.as_str(), "brightness")?;
-        let led2 = Attribute::from_sys_class("leds", led2_name.as_str(), "brightness")?;
+    /// Returns led1.
+    pub fn led1(&self) -> &LedChannel {
+        &self.led1
+    }
 
-        Ok(Led { led1, led2 })
+    /// Returns led2.
+    pub fn led2(&self) -> &LedChannel {
+        &self.led2
     }
 
     /// Returns the current brightness value of led1.
     pub fn get_led1(&self) -> Ev3Result<Color> {
-        self.led1.get()
+        self.led1.get_brightness()
     }
 
     /// Sets the brightness value of led1.
     pub fn set_led1(&self, brightness: Color) -> Ev3Result<()> {
-        self.led1.set(brightness)
+        self.led1.set_brightness(brightness)
     }
 
     /// Returns the current brightness value of led2.
     pub fn get_led2(&self) -> Ev3Result<Color> {
-        self.led2.get()
+        self.led2.get_brightness()
     }
 
     /// Sets the brightness value of led2.
     pub fn set_led2(&self, brightness: Color) -> Ev3Result<()> {
-        self.led2.set(brightness)
+        self.led2.set_brightness(brightness)
+    }
+
+    /// Sets the kernel trigger of both leds.
+    ///
+    /// Use `Trigger::None` to stop blinking.
+    pub fn set_trigger(&self, trigger: &Trigger) -> Ev3Result<()> {
+        set_trigger_all(&[&self.led1, &self.led2], trigger)
+    }
+
+    /// Blinks the leds that are currently on. The blinking is done by the kernel.
+    pub fn set_blink(&self, on_ms: u32, off_ms: u32) -> Ev3Result<()> {
+        blink_lit(&[&self.led1, &self.led2], on_ms, off_ms)
     }
 }
@@ -1,11 +1,7 @@
 //! EV3 specific features
 
-use std::fs;
-use std::path::Path;
-
-use crate::driver::DRIVER_PATH;
-use crate::utils::OrErr;
-use crate::{Attribute, Ev3Result};
+use crate::led::{blink_lit, find_led, set_trigger_all};
+use crate::{Driver, Ev3Result, LedChannel, Trigger};
 
 /// Color type.
 pub type Color = u8;
@@ -13,7 +9,7 @@ pub type Color = u8;
 /// The led's on top of the EV3 brick.
 #[derive(Debug, Clone)]
 pub struct Led {
-    led: Attribute,
+    led: LedChannel,
 }
 
 impl Led {
@@ -25,31 +21,42 @@ impl Led {
 
     /// Create a new instance of the `Led` struct.
     pub fn new() -> Ev3Result<Led> {
-        let mut led_name = String::new();
-
-        let paths = fs::read_dir(Path::new(DRIVER_PATH).join("leds"))?;
-
-        for path in paths {
-            let file_name = path?.file_name();
-            let name = file_name.to_str().or_err()?.to_owned();
+        let names = Driver::find_names("leds")?;
 
-            if name.contains(":brick-status") && name.contains("led0:") {
-                led_name = name;
-            }
-        }
+        Ok(Led {
+            led: find_led(&names, &[&["led0", "brick-status"]])?,
+        })
+    }
 
-        let led = Attribute::from_sys_class("leds", led_name.as_str(), "brightness")?;
+    /// Returns all leds in `/sys/class/leds/`, including the led of this struct.
+    pub fn list() -> Ev3Result<Vec<LedChannel>> {
+        LedChannel::list()
+    }
 
-        Ok(Led { led })
+    /// Returns the amber led.
+    pub fn led(&self) -> &LedChannel {
+        &self.led
     }
 
     /// Returns the current brightness value of led.
     pub fn get_led(&self) -> Ev3Result<Color> {
-        self.led.get()
+        self.led.get_brightness()
     }
 
     /// Sets the brightness value of led.
     pub fn set_led(&self, brightness: Color) -> Ev3Result<()> {
-        self.led.set(brightness)
+        self.led.set_brightness(brightness)
+    }
+
+    /// Sets the kernel trigger of the led.
+    ///
+    /// Use `Trigger::None` to stop blinking.
+    pub fn set_trigger(&self, trigger: &Trigger) -> Ev3Result<()> {
+        set_trigger_all(&[&self.led], trigger)
+    }
+
+    /// Blinks the led if it is currently on. The blinking is done by the kernel.
+    pub fn set_blink(&self, on_ms: u32, off_ms: u32) -> Ev3Result<()> {
+        blink_lit(&[&self.led], on_ms, off_ms)
     }
 }
@@ -0,0 +1,294 @@
+//! Persistent calibration data, so robots don't need to be recalibrated on every boot.
+//!
+//! Calibrations are keyed by the driver and the address of the device, e.g.
+//! `lego-ev3-gyro@ev3-ports:in2`, so a sensor that is plugged into another port
+//! or replaced by another type of sensor is not matched with stale values.
+//!
+//! Calibration files are written as JSON (feature `json`). With the `config` feature, files with a `.toml`
+//! extension are written as TOML:
+//!
+//! ```toml
+//! version = 1
+//!
+//! [devices."lego-ev3-gyro@ev3-ports:in2"]
+//! gyro_bias = -0.25
+//!
+//! [devices."lego-ev3-color@ev3-ports:in3"]
+//! white_balance = [1.0, 0.92, 1.31]
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{Device, Ev3Error, Ev3Result};
+
+/// Calibration values of a single device. Unset values are `None`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct DeviceCalibration {
+    /// Drift of a gyro sensor at rest in degree per second.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub gyro_bias: Option<f64>,
+    /// Factors for the red, green and blue components of a color sensor,
+    /// so a white surface reads as equal components.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub white_balance: Option<[f64; 3]>,
+    /// Offset in centimeters that is added to the distance of an ultrasonic sensor.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub distance_offset: Option<f64>,
+    /// Ratio of the measured to the nominal wheel diameter of a drive motor.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub wheel_diameter_factor: Option<f64>,
+    /// Other calibration values, e.g. of custom sensors.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub values: BTreeMap<String, f64>,
+}
+
+impl DeviceCalibration {
+    /// Checks if no value is set.
+    pub fn is_empty(&self) -> bool {
+        *self == DeviceCalibration::default()
+    }
+}
+
+/// Calibration data of all devices of a robot, stored in a JSON or TOML file.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::calibration::Calibration;
+/// use ev3dev_lang_rust::sensors::GyroSensor;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let gyro = GyroSensor::find()?;
+/// let path = "/home/robot/calibration.json";
+///
+/// let mut calibration = Calibration::load_or_default(path)?;
+/// let bias = match calibration.device(&gyro)?.gyro_bias {
+///     Some(bias) => bias,
+///     None => {
+///         // Measure the drift of the sensor at rest...
+///         let bias = -0.25;
+///         calibration.device_mut(&gyro)?.gyro_bias = Some(bias);
+///         calibration.save(path)?;
+///         bias
+///     }
+/// };
+/// # Ok(())
+/// # }
+/// ```
+///
+/// With the `serde` feature it is serialized with the version of the file format,
+/// data with another version is rejected.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "CalibrationFile", try_from = "CalibrationFile")
+)]
+pub struct Calibration {
+    devices: BTreeMap<String, DeviceCalibration>,
+}
+
+/// Serialized form of a `Calibration`.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CalibrationFile {
+    version: u32,
+    #[serde(default)]
+    devices: BTreeMap<String, DeviceCalibration>,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<CalibrationFile> for Calibration {
+    type Error = String;
+
+    fn try_from(file: CalibrationFile) -> Result<Self, String> {
+        if file.version != Calibration::VERSION {
+            return Err(format!(
+                "unsupported version {}, expected {}",
+                file.version,
+                Calibration::VERSION
+            ));
+        }
+        Ok(Calibration {
+            devices: file.devices,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Calibration> for CalibrationFile {
+    fn from(calibration: Calibration) -> Self {
+        CalibrationFile {
+            version: Calibration::VERSION,
+            devices: calibration.devices,
+        }
+    }
+}
+
+impl Calibration {
+    /// Version of the file format. Files with a newer version are rejected.
+    pub const VERSION: u32 = 1;
+
+    /// Creates an empty calibration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the key of `device`, e.g. `lego-ev3-gyro@ev3-ports:in2`.
+    pub fn key(device: &dyn Device) -> Ev3Result<String> {
+        Ok(format!(
+            "{}@{}",
+            device.get_driver_name()?,
+            device.get_address()?
+        ))
+    }
+
+    /// Loads the calibration from the JSON or TOML file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> Ev3Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(Ev3Error::io(path))?;
+
+        if is_toml(path) {
+            Self::from_toml_str(&content)
+        } else {
+            Self::from_json_str(&content)
+        }
+        .map_err(|e| match e {
+            Ev3Error::InternalError { msg, .. } => Ev3Error::InternalError {
+                msg: format!("Invalid calibration file: {msg}"),
+                path: Some(path.to_path_buf()),
+            },
+            e => e,
+        })
+    }
+
+    /// Loads the calibration from the file at `path` or returns an empty calibration if the file does not exist.
+    pub fn load_or_default(path: impl AsRef<Path>) -> Ev3Result<Self> {
+        match Self::load(path) {
+            Err(Ev3Error::Io { source, .. }) if source.kind() == io::ErrorKind::NotFound => {
+                Ok(Self::new())
+            }
+            result => result,
+        }
+    }
+
+    /// Saves the calibration to the file at `path`, as TOML if the extension is `.toml` and as JSON otherwise.
+    ///
+    /// The file is replaced atomically, so a crash while saving does not destroy the previous calibration.
+    pub fn save(&self, path: impl AsRef<Path>) -> Ev3Result<()> {
+        let path = path.as_ref();
+        let content = if is_toml(path) {
+            self.to_toml_string()?
+        } else {
+            self.to_json_string()?
+        };
+
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        let tmp_path = Path::new(&tmp_path);
+
+        fs::write(tmp_path, content).map_err(Ev3Error::io(tmp_path))?;
+        fs::rename(tmp_path, path).map_err(Ev3Error::io(path))
+    }
+
+    /// Returns the calibration of `device` or an empty calibration if the device is not calibrated.
+    pub fn device(&self, device: &dyn Device) -> Ev3Result<DeviceCalibration> {
+        Ok(self.get(&Self::key(device)?).cloned().unwrap_or_default())
+    }
+
+    /// Returns the calibration of `device` for modification.
+    pub fn device_mut(&mut self, device: &dyn Device) -> Ev3Result<&mut DeviceCalibration> {
+        Ok(self.devices.entry(Self::key(device)?).or_default())
+    }
+
+    /// Returns the calibration with the given `key`. See `Calibration::key()`.
+    pub fn get(&self, key: &str) -> Option<&DeviceCalibration> {
+        self.devices.get(key)
+    }
+
+    /// Sets the calibration with the given `key`. See `Calibration::key()`.
+    pub fn insert(&mut self, key: &str, calibration: DeviceCalibration) {
+        self.devices.insert(key.to_owned(), calibration);
+    }
+
+    /// Removes the calibration with the given `key`.
+    pub fn remove(&mut self, key: &str) -> Option<DeviceCalibration> {
+        self.devices.remove(key)
+    }
+
+    /// Returns the keys of all calibrated devices.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.devices.keys().map(String::as_str)
+    }
+
+    /// Parses a calibration in JSON format.
+    #[cfg(feature = "json")]
+    pub fn from_json_str(content: &str) -> Ev3Result<Self> {
+        serde_json::from_str(content).map_err(|e| invalid(&e.to_string()))
+    }
+
+    /// Writes the calibration in JSON format.
+    #[cfg(feature = "json")]
+    pub fn to_json_string(&self) -> Ev3Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| invalid(&e.to_string()))
+    }
+
+    /// Parses a calibration in TOML format.
+    #[cfg(feature = "config")]
+    pub fn from_toml_str(content: &str) -> Ev3Result<Self> {
+        toml::from_str(content).map_err(|e| invalid(e.message()))
+    }
+
+    /// Writes the calibration in TOML format.
+    #[cfg(feature = "config")]
+    pub fn to_toml_string(&self) -> Ev3Result<String> {
+        toml::to_string(self).map_err(|e| invalid(&e.to_string()))
+    }
+
+    #[cfg(not(feature = "json"))]
+    fn from_json_str(_content: &str) -> Ev3Result<Self> {
+        Err(unsupported("JSON", "json"))
+    }
+
+    #[cfg(not(feature = "json"))]
+    fn to_json_string(&self) -> Ev3Result<String> {
+        Err(unsupported("JSON", "json"))
+    }
+
+    #[cfg(not(feature = "config"))]
+    fn from_toml_str(_content: &str) -> Ev3Result<Self> {
+        Err(unsupported("TOML", "config"))
+    }
+
+    #[cfg(not(feature = "config"))]
+    fn to_toml_string(&self) -> Ev3Result<String> {
+        Err(unsupported("TOML", "config"))
+    }
+}
+
+fn is_toml(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|extension| extension == "toml")
+}
+
+fn invalid(msg: &str) -> Ev3Error {
+    Ev3Error::InternalError {
+        msg: msg.to_owned(),
+        path: None,
+    }
+}
+
+#[cfg(not(all(feature = "json", feature = "config")))]
+fn unsupported(format: &str, feature: &str) -> Ev3Error {
+    invalid(&format!(
+        "{format} calibration files require the `{feature}` feature"
+    ))
+}
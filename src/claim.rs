@@ -0,0 +1,137 @@
+//! Optional in-process registry of device claims.
+//!
+//! Controllers can claim the devices they drive, either exclusively or shared. A second exclusive
+//! claim on the same device fails with `Ev3Error::AlreadyClaimed`. This catches bugs like
+//! "two behaviors driving one motor" at startup instead of on the field.
+//!
+//! Claims are released when the `DeviceClaim` is dropped. Devices that are never claimed are not affected.
+//!
+//! # Example
+//! ```no_run
+//! use ev3dev_lang_rust::claim::DeviceClaim;
+//! use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+//!
+//! # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+//! let motor = LargeMotor::get(MotorPort::OutA)?;
+//!
+//! let _drive = DeviceClaim::exclusive(&motor, "drive")?;
+//!
+//! // Fails, the motor is already claimed by "drive".
+//! assert!(DeviceClaim::exclusive(&motor, "line follower").is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::utils::OrErr;
+use crate::{Device, Ev3Error, Ev3Result};
+
+/// Kind of a device claim.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ClaimKind {
+    /// No other claim may exist for the device.
+    Exclusive,
+    /// Other shared claims may exist for the device.
+    Shared,
+}
+
+#[derive(Debug)]
+struct ClaimEntry {
+    id: usize,
+    path: PathBuf,
+    kind: ClaimKind,
+    owner: String,
+}
+
+static CLAIMS: Mutex<Vec<ClaimEntry>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A claim on a device. The claim is released when this value is dropped.
+#[derive(Debug)]
+pub struct DeviceClaim {
+    id: usize,
+    device: String,
+    kind: ClaimKind,
+    owner: String,
+}
+
+impl DeviceClaim {
+    /// Claim `device` exclusively for `owner`.
+    ///
+    /// Returns `Ev3Error::AlreadyClaimed` if any other claim exists for the device.
+    pub fn exclusive(device: &dyn Device, owner: &str) -> Ev3Result<DeviceClaim> {
+        DeviceClaim::claim(device, owner, ClaimKind::Exclusive)
+    }
+
+    /// Claim `device` shared for `owner`.
+    ///
+    /// Returns `Ev3Error::AlreadyClaimed` if an exclusive claim exists for the device.
+    pub fn shared(device: &dyn Device, owner: &str) -> Ev3Result<DeviceClaim> {
+        DeviceClaim::claim(device, owner, ClaimKind::Shared)
+    }
+
+    /// Claim `device` for `owner`.
+    pub fn claim(device: &dyn Device, owner: &str, kind: ClaimKind) -> Ev3Result<DeviceClaim> {
+        let address = device.get_read_only_attribute("address")?;
+        // The device directory in `/sys/class/` identifies the device across all instances.
+        let path = address.get_file_path().parent().or_err()?.to_path_buf();
+        let device_name = address
+            .get::<String>()
+            .unwrap_or_else(|_| path.to_string_lossy().into_owned());
+
+        let mut claims = CLAIMS.lock().unwrap();
+
+        let conflict = claims.iter().find(|entry| {
+            entry.path == path
+                && (kind == ClaimKind::Exclusive || entry.kind == ClaimKind::Exclusive)
+        });
+        if let Some(entry) = conflict {
+            return Err(Ev3Error::AlreadyClaimed {
+                device: device_name,
+                owner: entry.owner.clone(),
+                path: Some(path),
+            });
+        }
+
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        claims.push(ClaimEntry {
+            id,
+            path,
+            kind,
+            owner: owner.to_owned(),
+        });
+
+        Ok(DeviceClaim {
+            id,
+            device: device_name,
+            kind,
+            owner: owner.to_owned(),
+        })
+    }
+
+    /// Returns the address of the claimed device.
+    pub fn get_device(&self) -> &str {
+        &self.device
+    }
+
+    /// Returns the kind of this claim.
+    pub fn get_kind(&self) -> ClaimKind {
+        self.kind
+    }
+
+    /// Returns the owner of this claim.
+    pub fn get_owner(&self) -> &str {
+        &self.owner
+    }
+}
+
+impl Drop for DeviceClaim {
+    fn drop(&mut self) {
+        if let Ok(mut claims) = CLAIMS.lock() {
+            claims.retain(|entry| entry.id != self.id);
+        }
+    }
+}
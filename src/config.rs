@@ -0,0 +1,300 @@
+//! Robot configuration files that declare the devices of a robot by their role.
+//!
+//! ```toml
+//! drive_left = { type = "LargeMotor", port = "outB" }
+//! drive_right = { type = "LargeMotor", port = "outC" }
+//! line = { type = "ColorSensor", port = "in3" }
+//! gyro = { type = "GyroSensor" }
+//! ```
+//!
+//! Ports are parsed with `Port::parse()`, e.g. `outA`, `B`, `in3` or `in1:mux2`.
+//! Devices without a `port` are found by their type, so there must be exactly one of them.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::motors::{LargeMotor, LinearActuator, MediumMotor, MotorPort};
+use crate::sensors::{
+    AccelerometerSensor, ColorSensor, CompassSensor, GyroSensor, HiTechnicColorSensor, I2cSensor,
+    InfraredSensor, IrSeekerSensor, LightSensor, MuxPort, PixyCamera, SensorPort, SoundSensor,
+    TemperatureSensor, TouchSensor, UltrasonicSensor,
+};
+use crate::{Ev3Error, Ev3Result, Port};
+
+/// Declaration of a single device in a robot configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeviceConfig {
+    /// Type of the device, e.g. `LargeMotor` or `ColorSensor`.
+    #[serde(rename = "type")]
+    pub device_type: String,
+    /// Port of the device, e.g. `outB` or `in3`. (None if the device is found by its type)
+    #[serde(default)]
+    pub port: Option<String>,
+}
+
+type BoxedDevice = Box<dyn Any + Send>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PortKind {
+    Motor,
+    Sensor,
+}
+
+struct DeviceType {
+    name: &'static str,
+    port_kind: PortKind,
+    get: fn(&dyn Port) -> Ev3Result<BoxedDevice>,
+    find: fn() -> Ev3Result<BoxedDevice>,
+}
+
+macro_rules! device_types {
+    ($( $device:ident: $port_kind:ident ),* $(,)?) => {
+        const DEVICE_TYPES: &[DeviceType] = &[
+            $(
+                DeviceType {
+                    name: stringify!($device),
+                    port_kind: PortKind::$port_kind,
+                    get: |port| Ok(Box::new($device::get_by_port(port)?)),
+                    find: || Ok(Box::new($device::find()?)),
+                },
+            )*
+        ];
+    };
+}
+
+device_types!(
+    LargeMotor: Motor,
+    MediumMotor: Motor,
+    LinearActuator: Motor,
+    AccelerometerSensor: Sensor,
+    ColorSensor: Sensor,
+    CompassSensor: Sensor,
+    GyroSensor: Sensor,
+    HiTechnicColorSensor: Sensor,
+    I2cSensor: Sensor,
+    InfraredSensor: Sensor,
+    IrSeekerSensor: Sensor,
+    LightSensor: Sensor,
+    PixyCamera: Sensor,
+    SoundSensor: Sensor,
+    TemperatureSensor: Sensor,
+    TouchSensor: Sensor,
+    UltrasonicSensor: Sensor,
+);
+
+fn parse_port(port_kind: PortKind, port: &str) -> Option<Box<dyn Port>> {
+    match port_kind {
+        PortKind::Motor => MotorPort::parse(port)
+            .ok()
+            .map(|port| Box::new(port) as Box<dyn Port>),
+        PortKind::Sensor => SensorPort::parse(port)
+            .map(|port| Box::new(port) as Box<dyn Port>)
+            .or_else(|_| MuxPort::parse(port).map(|port| Box::new(port) as Box<dyn Port>))
+            .ok(),
+    }
+}
+
+/// Describes the valid ports of `port_kind` for error messages.
+fn expected_ports(port_kind: PortKind) -> &'static str {
+    match port_kind {
+        PortKind::Motor => "outA to outD",
+        PortKind::Sensor => "in1 to in4",
+    }
+}
+
+fn device_type(name: &str) -> Option<&'static DeviceType> {
+    DEVICE_TYPES
+        .iter()
+        .find(|device_type| device_type.name == name)
+}
+
+/// Checks that the type and port of the device `role` are known.
+fn validate(role: &str, config: &DeviceConfig) -> Result<(), String> {
+    let device_type = device_type(&config.device_type).ok_or_else(|| {
+        format!(
+            "Unknown device type '{}' of '{role}', expected one of {:?}",
+            config.device_type,
+            DEVICE_TYPES.iter().map(|t| t.name).collect::<Vec<_>>()
+        )
+    })?;
+
+    if let Some(port) = &config.port {
+        if parse_port(device_type.port_kind, port).is_none() {
+            return Err(format!(
+                "Invalid port '{port}' of '{role}', a {} expects {}",
+                config.device_type,
+                expected_ports(device_type.port_kind)
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Connects to the device declared by `config`.
+fn connect_device(config: &DeviceConfig) -> Ev3Result<BoxedDevice> {
+    let device_type = device_type(&config.device_type).ok_or_else(|| Ev3Error::InternalError {
+        msg: format!("Unknown device type '{}'", config.device_type),
+        path: None,
+    })?;
+
+    match &config.port {
+        Some(port) => {
+            let port =
+                parse_port(device_type.port_kind, port).ok_or_else(|| Ev3Error::InvalidPort {
+                    value: port.clone(),
+                    expected: expected_ports(device_type.port_kind).to_owned(),
+                    path: None,
+                })?;
+            (device_type.get)(port.as_ref())
+        }
+        None => (device_type.find)(),
+    }
+}
+
+/// Devices of a robot, declared by their role in a TOML file and connected at startup.
+///
+/// All devices are validated and connected by `from_toml()`, so a missing or wrongly plugged device
+/// is reported with its role before the robot starts moving.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::config::RobotConfig;
+/// use ev3dev_lang_rust::motors::LargeMotor;
+/// use ev3dev_lang_rust::sensors::ColorSensor;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// // robot.toml:
+/// // drive_left = { type = "LargeMotor", port = "outB" }
+/// // line = { type = "ColorSensor", port = "in3" }
+/// let robot = RobotConfig::from_toml("robot.toml")?;
+///
+/// let drive_left: LargeMotor = robot.get("drive_left")?;
+/// let line: ColorSensor = robot.get("line")?;
+///
+/// drive_left.run_forever()?;
+/// while line.get_color()? != 1 {}
+/// drive_left.stop()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct RobotConfig {
+    devices: BTreeMap<String, (DeviceConfig, BoxedDevice)>,
+}
+
+impl RobotConfig {
+    /// Reads the configuration file at `path` and connects all declared devices.
+    pub fn from_toml(path: impl AsRef<Path>) -> Ev3Result<Self> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(Ev3Error::io(path))?;
+
+        let declarations = Self::parse(&content).map_err(|e| match e {
+            Ev3Error::InternalError { msg, .. } => Ev3Error::InternalError {
+                msg: format!("Invalid robot config: {msg}"),
+                path: Some(path.to_path_buf()),
+            },
+            e => e,
+        })?;
+        Self::connect(declarations)
+    }
+
+    /// Parses the configuration `content` and connects all declared devices.
+    pub fn from_toml_str(content: &str) -> Ev3Result<Self> {
+        Self::connect(Self::parse(content)?)
+    }
+
+    /// Parses and validates the configuration `content` without connecting any device.
+    pub fn parse(content: &str) -> Ev3Result<BTreeMap<String, DeviceConfig>> {
+        let declarations: BTreeMap<String, DeviceConfig> =
+            toml::from_str(content).map_err(|e| Ev3Error::InternalError {
+                msg: match e.span() {
+                    Some(span) => format!(
+                        "line {}: {}",
+                        content[..span.start].matches('\n').count() + 1,
+                        e.message()
+                    ),
+                    None => e.message().to_owned(),
+                },
+                path: None,
+            })?;
+
+        let errors = declarations
+            .iter()
+            .filter_map(|(role, config)| validate(role, config).err())
+            .collect::<Vec<_>>();
+        if !errors.is_empty() {
+            return Err(Ev3Error::InternalError {
+                msg: errors.join("; "),
+                path: None,
+            });
+        }
+
+        Ok(declarations)
+    }
+
+    /// Connects all `declarations`. Fails with the roles of all devices that could not be connected.
+    pub fn connect(declarations: BTreeMap<String, DeviceConfig>) -> Ev3Result<Self> {
+        let mut devices = BTreeMap::new();
+        let mut errors = Vec::new();
+
+        for (role, config) in declarations {
+            match connect_device(&config) {
+                Ok(device) => {
+                    devices.insert(role, (config, device));
+                }
+                Err(e) => errors.push(format!("{role}: {e}")),
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(Ev3Error::InternalError {
+                msg: format!("Missing devices of the robot config: {}", errors.join("; ")),
+                path: None,
+            });
+        }
+
+        Ok(RobotConfig { devices })
+    }
+
+    /// Returns the device `role`. Fails if there is no such role or the device is not a `T`.
+    pub fn get<T: Any + Clone>(&self, role: &str) -> Ev3Result<T> {
+        let (config, device) = self
+            .devices
+            .get(role)
+            .ok_or_else(|| Ev3Error::InternalError {
+                msg: format!("No device '{role}' in the robot config"),
+                path: None,
+            })?;
+
+        device
+            .downcast_ref::<T>()
+            .cloned()
+            .ok_or_else(|| Ev3Error::InternalError {
+                msg: format!(
+                    "'{role}' is a {}, not a {}",
+                    config.device_type,
+                    std::any::type_name::<T>()
+                        .rsplit("::")
+                        .next()
+                        .unwrap_or_default()
+                ),
+                path: None,
+            })
+    }
+
+    /// Returns the declaration of the device `role`.
+    pub fn device_config(&self, role: &str) -> Option<&DeviceConfig> {
+        self.devices.get(role).map(|(config, _)| config)
+    }
+
+    /// Returns the roles of all devices.
+    pub fn roles(&self) -> impl Iterator<Item = &str> {
+        self.devices.keys().map(String::as_str)
+    }
+}
@@ -0,0 +1,213 @@
+//! Fixed-rate scheduler for control loops.
+
+use std::time::{Duration, Instant};
+
+use crate::task::CancelToken;
+use crate::{Ev3Error, Ev3Result};
+
+/// Timing statistics of a `Loop`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LoopStats {
+    /// Number of finished iterations.
+    pub iterations: u64,
+    /// Number of iterations that ended after the next tick, so the schedule was shifted.
+    pub overruns: u64,
+    /// Largest delay between the scheduled and the actual start of an iteration.
+    pub max_jitter: Duration,
+    /// Mean delay between the scheduled and the actual start of an iteration.
+    pub mean_jitter: Duration,
+    /// Longest time spent in the closure.
+    pub max_busy: Duration,
+}
+
+/// State of a running `Loop`, passed to the closure on every iteration.
+#[derive(Debug)]
+pub struct LoopContext {
+    start: Instant,
+    dt: Duration,
+    elapsed: Duration,
+    stats: LoopStats,
+    total_jitter: Duration,
+    stopped: bool,
+}
+
+impl LoopContext {
+    /// Returns the time since the start of the previous iteration (zero on the first iteration).
+    pub fn dt(&self) -> Duration {
+        self.dt
+    }
+
+    /// Returns `dt()` in seconds, e.g. for the integral and derivative of a PID controller.
+    pub fn dt_secs(&self) -> f32 {
+        self.dt.as_secs_f32()
+    }
+
+    /// Returns the time since the start of the loop.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the number of the current iteration, starting with 0.
+    pub fn iteration(&self) -> u64 {
+        self.stats.iterations
+    }
+
+    /// Returns the timing statistics of the previous iterations.
+    pub fn stats(&self) -> &LoopStats {
+        &self.stats
+    }
+
+    /// Ends the loop after the current iteration.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+    }
+}
+
+/// Runs a closure with a fixed rate.
+///
+/// Unlike a plain `thread::sleep()` in a loop, the time spent in the closure is compensated,
+/// so the rate does not drift. If an iteration takes longer than the period, the missed ticks
+/// are skipped and counted as overruns instead of running the closure several times in a burst.
+///
+/// The loop ends when the closure calls `LoopContext::stop()`, returns an error
+/// or the cancel token is cancelled.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::control::Loop;
+/// use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+/// use ev3dev_lang_rust::sensors::ColorSensor;
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let sensor = ColorSensor::find()?;
+/// let motor = LargeMotor::get(MotorPort::OutA)?;
+/// motor.run_direct()?;
+///
+/// let stats = Loop::run_at(50.0, |ctx| {
+///     let error = 50 - sensor.get_color()?;
+///     motor.set_duty_cycle_sp(error.clamp(-100, 100))?;
+///
+///     if ctx.elapsed() > Duration::from_secs(10) {
+///         ctx.stop();
+///     }
+///     Ok(())
+/// })?;
+///
+/// motor.stop()?;
+/// println!("{} iterations, {} overruns", stats.iterations, stats.overruns);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Loop {
+    period: Duration,
+    token: Option<CancelToken>,
+}
+
+impl Loop {
+    /// Creates a loop that starts an iteration every `period`.
+    pub fn new(period: Duration) -> Self {
+        Self {
+            period,
+            token: None,
+        }
+    }
+
+    /// Creates a loop that runs `frequency` times per second.
+    /// Returns an error if `frequency` is not a positive number.
+    pub fn with_frequency(frequency: f64) -> Ev3Result<Self> {
+        if !(frequency.is_finite() && frequency > 0.0) {
+            return Err(Ev3Error::InternalError {
+                msg: format!("Invalid loop frequency {frequency} Hz, expected a positive number"),
+                path: None,
+            });
+        }
+        Ok(Self::new(Duration::from_secs_f64(1.0 / frequency)))
+    }
+
+    /// Ends the loop as soon as `token` is cancelled, e.g. by an emergency stop.
+    pub fn cancel_token(mut self, token: CancelToken) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    /// Returns the period of the loop.
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Runs `body` `frequency` times per second until it stops. See `Loop::run()`.
+    pub fn run_at<F>(frequency: f64, body: F) -> Ev3Result<LoopStats>
+    where
+        F: FnMut(&mut LoopContext) -> Ev3Result<()>,
+    {
+        Self::with_frequency(frequency)?.run(body)
+    }
+
+    /// Runs `body` until it calls `LoopContext::stop()`, returns an error or the token is cancelled.
+    /// Returns the timing statistics of the loop or the error of the closure.
+    pub fn run<F>(&self, mut body: F) -> Ev3Result<LoopStats>
+    where
+        F: FnMut(&mut LoopContext) -> Ev3Result<()>,
+    {
+        let start = Instant::now();
+        let mut ctx = LoopContext {
+            start,
+            dt: Duration::ZERO,
+            elapsed: Duration::ZERO,
+            stats: LoopStats::default(),
+            total_jitter: Duration::ZERO,
+            stopped: false,
+        };
+
+        let mut scheduled = start;
+        let mut last_start = None;
+
+        loop {
+            if self.token.as_ref().is_some_and(CancelToken::is_cancelled) {
+                break;
+            }
+
+            let iteration_start = Instant::now();
+            ctx.dt = last_start.map_or(Duration::ZERO, |last| iteration_start - last);
+            ctx.elapsed = iteration_start - ctx.start;
+            last_start = Some(iteration_start);
+
+            let jitter = iteration_start.saturating_duration_since(scheduled);
+            ctx.total_jitter += jitter;
+            ctx.stats.max_jitter = ctx.stats.max_jitter.max(jitter);
+
+            body(&mut ctx)?;
+
+            let now = Instant::now();
+            ctx.stats.max_busy = ctx.stats.max_busy.max(now - iteration_start);
+            ctx.stats.iterations += 1;
+            ctx.stats.mean_jitter = ctx.total_jitter.div_f64(ctx.stats.iterations as f64);
+
+            if ctx.stopped {
+                break;
+            }
+
+            scheduled += self.period;
+            if now > scheduled {
+                // Start the next iteration right away and skip the missed ticks
+                // instead of catching up in a burst.
+                ctx.stats.overruns += 1;
+                scheduled = now;
+            }
+
+            let delay = scheduled.saturating_duration_since(now);
+            match &self.token {
+                Some(token) => {
+                    if !token.sleep(delay) {
+                        break;
+                    }
+                }
+                None => std::thread::sleep(delay),
+            }
+        }
+
+        Ok(ctx.stats)
+    }
+}
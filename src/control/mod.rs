@@ -0,0 +1,13 @@
+//! Building blocks for closed-loop control of the robot.
+
+mod control_loop;
+pub use self::control_loop::{Loop, LoopContext, LoopStats};
+
+mod pid;
+pub use self::pid::Pid;
+
+mod navigator;
+pub use self::navigator::{Navigator, NavigatorOptions};
+
+mod path;
+pub use self::path::{PathExecutor, PathProgress, Segment};
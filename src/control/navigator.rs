@@ -0,0 +1,264 @@
+//! Heading based navigation of a differential drive robot.
+
+use std::f32::consts::PI;
+use std::time::{Duration, Instant};
+
+use super::{Loop, LoopContext, Pid};
+use crate::motors::TachoMotor;
+use crate::sensors::{heading_difference, HeadingSensor};
+use crate::task::CancelToken;
+use crate::{Ev3Error, Ev3Result};
+
+/// Options for a `Navigator`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavigatorOptions {
+    /// Diameter of the wheels in millimeters.
+    pub wheel_diameter: f32,
+    /// Controller that converts the heading error in degree to a wheel speed difference in tacho counts per second.
+    pub heading_pid: Pid,
+    /// Maximal wheel speed while turning in tacho counts per second.
+    pub turn_speed: i32,
+    /// A turn is finished if the heading differs by at most this many degrees.
+    pub tolerance: i32,
+    /// Rate of the control loop in Hz.
+    pub rate: f64,
+    /// Maximal time of a single maneuver. (None to wait forever)
+    pub timeout: Option<Duration>,
+}
+
+impl Default for NavigatorOptions {
+    /// Options for the 56 mm wheels of the EV3 education set and LEGO large motors.
+    fn default() -> Self {
+        Self {
+            wheel_diameter: 56.0,
+            heading_pid: Pid::new(10.0, 0.0, 0.5),
+            turn_speed: 400,
+            tolerance: 1,
+            rate: 50.0,
+            timeout: Some(Duration::from_secs(10)),
+        }
+    }
+}
+
+/// Combines the two drive motors of a robot with a gyro or compass sensor
+/// to turn to absolute headings and drive straight along a heading.
+///
+/// Headings are measured in degree and increase clockwise, see `HeadingSensor`.
+/// The motors are stopped after every maneuver, also if it fails.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::control::{Navigator, NavigatorOptions};
+/// use ev3dev_lang_rust::motors::{MotorPort, TachoMotor};
+/// use ev3dev_lang_rust::sensors::GyroSensor;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let gyro = GyroSensor::find()?;
+/// gyro.set_mode_gyro_ang()?;
+///
+/// let navigator = Navigator::new(
+///     TachoMotor::get(MotorPort::OutB)?,
+///     TachoMotor::get(MotorPort::OutC)?,
+///     gyro,
+///     NavigatorOptions::default(),
+/// );
+///
+/// // Drive a square with 50 cm sides.
+/// for heading in [0, 90, 180, 270] {
+///     navigator.turn_to_heading(heading)?;
+///     navigator.drive_heading(heading, 500.0, 300)?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Navigator<H: HeadingSensor> {
+    left: TachoMotor,
+    right: TachoMotor,
+    sensor: H,
+    options: NavigatorOptions,
+    distance_origin: (i32, i32),
+}
+
+impl<H: HeadingSensor> Navigator<H> {
+    /// Creates a navigator for the `left` and `right` drive motors and the heading `sensor`.
+    pub fn new(left: TachoMotor, right: TachoMotor, sensor: H, options: NavigatorOptions) -> Self {
+        Self {
+            left,
+            right,
+            sensor,
+            options,
+            distance_origin: (0, 0),
+        }
+    }
+
+    /// Returns the heading sensor.
+    pub fn sensor(&self) -> &H {
+        &self.sensor
+    }
+
+    /// Returns the current heading in degree.
+    pub fn heading(&self) -> Ev3Result<i32> {
+        self.sensor.get_heading()
+    }
+
+    /// Returns the distance in millimeters the robot moved forward since the last `reset_distance()`.
+    pub fn distance(&self) -> Ev3Result<f32> {
+        let (left, right) = self.positions()?;
+        let counts = (left - self.distance_origin.0) + (right - self.distance_origin.1);
+        self.counts_to_mm(counts as f32 / 2.0)
+    }
+
+    /// Sets the current position as origin of `distance()`.
+    pub fn reset_distance(&mut self) -> Ev3Result<()> {
+        self.distance_origin = self.positions()?;
+        Ok(())
+    }
+
+    /// Turns on the spot until the robot points to `heading`.
+    ///
+    /// Returns `Ev3Error::Timeout` if the heading is not reached within the timeout of the options.
+    pub fn turn_to_heading(&self, heading: i32) -> Ev3Result<()> {
+        self.turn_to_heading_with(heading, None)
+    }
+
+    /// Turns to `heading` until the turn is finished or the `token` is cancelled.
+    pub(super) fn turn_to_heading_with(
+        &self,
+        heading: i32,
+        token: Option<&CancelToken>,
+    ) -> Ev3Result<()> {
+        let mut pid = self
+            .options
+            .heading_pid
+            .clone()
+            .with_limit(self.options.turn_speed as f32);
+        pid.reset();
+
+        self.run(token, |ctx| {
+            let error = heading_difference(heading, self.heading()?);
+            if error.abs() <= self.options.tolerance {
+                ctx.stop();
+                return Ok(());
+            }
+
+            let output = pid.update(error as f32, ctx.dt_secs()).round() as i32;
+            self.set_speeds(output, -output)
+        })
+    }
+
+    /// Drives `distance` millimeters (backwards for negative values) with `speed` tacho counts per second
+    /// and steers to keep the robot on `heading`.
+    ///
+    /// Returns `Ev3Error::Timeout` if the distance is not reached within the timeout of the options.
+    pub fn drive_heading(&self, heading: i32, distance: f32, speed: i32) -> Ev3Result<()> {
+        self.drive_arc_with(heading, distance, 0.0, speed, None)
+    }
+
+    /// Drives on an arc with the given `radius` in millimeters, that changes the heading by `angle` degree,
+    /// with `speed` tacho counts per second. Positive angles turn clockwise.
+    ///
+    /// The heading is measured by the sensor and corrected along the arc, so no track width is needed.
+    /// Returns `Ev3Error::Timeout` if the arc is not finished within the timeout of the options.
+    pub fn drive_arc(&self, radius: f32, angle: i32, speed: i32) -> Ev3Result<()> {
+        let distance = radius.abs() * (angle as f32).abs().to_radians();
+        self.drive_arc_with(self.heading()?, distance, angle as f32, speed, None)
+    }
+
+    /// Drives `distance` millimeters while the target heading changes linearly from `heading` by `angle` degree,
+    /// until the distance is reached or the `token` is cancelled.
+    pub(super) fn drive_arc_with(
+        &self,
+        heading: i32,
+        distance: f32,
+        angle: f32,
+        speed: i32,
+        token: Option<&CancelToken>,
+    ) -> Ev3Result<()> {
+        let target = self.mm_to_counts(distance.abs())?;
+        let base_speed = if distance < 0.0 {
+            -speed.abs()
+        } else {
+            speed.abs()
+        };
+        let (left_start, right_start) = self.positions()?;
+
+        let mut pid = self
+            .options
+            .heading_pid
+            .clone()
+            .with_limit(speed.abs() as f32);
+        pid.reset();
+
+        self.run(token, |ctx| {
+            let (left, right) = self.positions()?;
+            let travelled = ((left - left_start) + (right - right_start)).abs() as f32 / 2.0;
+            if travelled >= target {
+                ctx.stop();
+                return Ok(());
+            }
+
+            let target_heading = heading + (angle * travelled / target).round() as i32;
+            let error = heading_difference(target_heading, self.heading()?);
+            let correction = pid.update(error as f32, ctx.dt_secs()).round() as i32;
+            self.set_speeds(base_speed + correction, base_speed - correction)
+        })
+    }
+
+    /// Stops both motors.
+    pub fn stop(&self) -> Ev3Result<()> {
+        let left = self.left.stop();
+        self.right.stop()?;
+        left
+    }
+
+    /// Runs `body` in a control loop with the rate and timeout of the options and stops the motors afterwards.
+    /// The loop ends early if the `token` is cancelled.
+    fn run<F>(&self, token: Option<&CancelToken>, mut body: F) -> Ev3Result<()>
+    where
+        F: FnMut(&mut LoopContext) -> Ev3Result<()>,
+    {
+        let start = Instant::now();
+        let result = Loop::with_frequency(self.options.rate).and_then(|mut control_loop| {
+            if let Some(token) = token {
+                control_loop = control_loop.cancel_token(token.clone());
+            }
+            control_loop.run(|ctx| {
+                if let Some(timeout) = self.options.timeout {
+                    if start.elapsed() >= timeout {
+                        return Err(Ev3Error::Timeout {
+                            timeout,
+                            path: None,
+                        });
+                    }
+                }
+                body(ctx)
+            })
+        });
+
+        let stopped = self.stop();
+        result?;
+        stopped
+    }
+
+    fn set_speeds(&self, left: i32, right: i32) -> Ev3Result<()> {
+        self.left.set_speed_sp(left)?;
+        self.right.set_speed_sp(right)?;
+        self.left.run_forever()?;
+        self.right.run_forever()
+    }
+
+    fn positions(&self) -> Ev3Result<(i32, i32)> {
+        Ok((self.left.get_position()?, self.right.get_position()?))
+    }
+
+    fn counts_to_mm(&self, counts: f32) -> Ev3Result<f32> {
+        let count_per_rot = self.left.get_count_per_rot()? as f32;
+        Ok(counts / count_per_rot * PI * self.options.wheel_diameter)
+    }
+
+    fn mm_to_counts(&self, mm: f32) -> Ev3Result<f32> {
+        let count_per_rot = self.left.get_count_per_rot()? as f32;
+        Ok(mm / (PI * self.options.wheel_diameter) * count_per_rot)
+    }
+}
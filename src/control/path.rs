@@ -0,0 +1,188 @@
+//! Sequential execution of path segments with a `Navigator`.
+
+use super::Navigator;
+use crate::sensors::{heading_difference, HeadingSensor};
+use crate::task::CancelToken;
+use crate::Ev3Result;
+
+/// A single maneuver of a path. Distances are in millimeters, angles in degree and positive angles turn clockwise.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    /// Drive straight on the current heading (backwards for negative distances).
+    Straight {
+        /// Distance in millimeters
+        distance: f32,
+    },
+    /// Drive on an arc that changes the heading by `angle`.
+    Arc {
+        /// Radius of the arc in millimeters
+        radius: f32,
+        /// Change of the heading in degree
+        angle: i32,
+    },
+    /// Turn on the spot by `angle`.
+    Turn {
+        /// Change of the heading in degree
+        angle: i32,
+    },
+    /// Turn on the spot to an absolute heading of the sensor.
+    TurnTo {
+        /// Heading in degree
+        heading: i32,
+    },
+}
+
+/// Progress of a `PathExecutor`, reported before each segment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathProgress {
+    /// Index of the segment that is started.
+    pub index: usize,
+    /// Number of segments of the path.
+    pub total: usize,
+    /// The segment that is started.
+    pub segment: Segment,
+}
+
+/// Executes a list of segments one after another on a `Navigator`.
+///
+/// The heading of each segment is derived from the previous segments instead of the measured heading,
+/// so small errors at the end of a turn don't add up over the path.
+///
+/// The path can be cancelled from another thread, e.g. by a button handler, with the token returned by
+/// `cancel_token()`. A running segment is aborted and the motors are stopped.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::control::{Navigator, NavigatorOptions, PathExecutor, Segment};
+/// use ev3dev_lang_rust::motors::{MotorPort, TachoMotor};
+/// use ev3dev_lang_rust::sensors::GyroSensor;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let navigator = Navigator::new(
+///     TachoMotor::get(MotorPort::OutB)?,
+///     TachoMotor::get(MotorPort::OutC)?,
+///     GyroSensor::find()?,
+///     NavigatorOptions::default(),
+/// );
+///
+/// let path = PathExecutor::new(
+///     vec![
+///         Segment::Straight { distance: 400.0 },
+///         Segment::Arc { radius: 200.0, angle: 90 },
+///         Segment::Turn { angle: -45 },
+///         Segment::Straight { distance: -150.0 },
+///     ],
+///     300,
+/// );
+///
+/// let completed = path.execute(&navigator, |progress| {
+///     println!("Segment {}/{}: {:?}", progress.index + 1, progress.total, progress.segment);
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathExecutor {
+    segments: Vec<Segment>,
+    speed: i32,
+    token: CancelToken,
+}
+
+impl PathExecutor {
+    /// Creates an executor for the `segments`, that drives with `speed` tacho counts per second.
+    pub fn new(segments: Vec<Segment>, speed: i32) -> Self {
+        Self {
+            segments,
+            speed,
+            token: CancelToken::new(),
+        }
+    }
+
+    /// Creates an executor that drives to the `waypoints` `(x, y)` in millimeters, turning on the spot at each waypoint.
+    ///
+    /// The robot starts at `(0, 0)` and the x axis points along its initial heading, the y axis to its right.
+    pub fn from_waypoints(waypoints: &[(f32, f32)], speed: i32) -> Self {
+        let mut segments = Vec::new();
+        let mut position = (0.0f32, 0.0f32);
+        let mut heading = 0;
+
+        for &(x, y) in waypoints {
+            let (dx, dy) = (x - position.0, y - position.1);
+            let distance = dx.hypot(dy);
+            if distance < 1.0 {
+                continue;
+            }
+
+            let target = dy.atan2(dx).to_degrees().round() as i32;
+            let angle = heading_difference(target, heading);
+            if angle != 0 {
+                segments.push(Segment::Turn { angle });
+            }
+            segments.push(Segment::Straight { distance });
+
+            position = (x, y);
+            heading = target;
+        }
+
+        Self::new(segments, speed)
+    }
+
+    /// Returns the segments of the path.
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// Returns the token that cancels the execution. A cancelled executor does not start again.
+    pub fn cancel_token(&self) -> CancelToken {
+        self.token.clone()
+    }
+
+    /// Executes the segments and calls `on_progress` before each segment.
+    ///
+    /// Returns the number of completed segments, which is less than the number of segments
+    /// if the execution was cancelled, or the first error of a segment.
+    pub fn execute<H, F>(&self, navigator: &Navigator<H>, mut on_progress: F) -> Ev3Result<usize>
+    where
+        H: HeadingSensor,
+        F: FnMut(&PathProgress),
+    {
+        let token = Some(&self.token);
+        let mut heading = navigator.heading()?;
+
+        for (index, segment) in self.segments.iter().enumerate() {
+            if self.token.is_cancelled() {
+                return Ok(index);
+            }
+            on_progress(&PathProgress {
+                index,
+                total: self.segments.len(),
+                segment: *segment,
+            });
+
+            match *segment {
+                Segment::Straight { distance } => {
+                    navigator.drive_arc_with(heading, distance, 0.0, self.speed, token)?;
+                }
+                Segment::Arc { radius, angle } => {
+                    let distance = radius.abs() * (angle as f32).abs().to_radians();
+                    navigator.drive_arc_with(heading, distance, angle as f32, self.speed, token)?;
+                    heading += angle;
+                }
+                Segment::Turn { angle } => {
+                    heading += angle;
+                    navigator.turn_to_heading_with(heading, token)?;
+                }
+                Segment::TurnTo { heading: target } => {
+                    heading = target;
+                    navigator.turn_to_heading_with(heading, token)?;
+                }
+            }
+
+            if self.token.is_cancelled() {
+                return Ok(index);
+            }
+        }
+
+        Ok(self.segments.len())
+    }
+}
@@ -0,0 +1,85 @@
+//! PID controller.
+
+/// Discrete PID controller with output limit and anti-windup.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::control::{Loop, Pid};
+/// use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+/// use ev3dev_lang_rust::sensors::ColorSensor;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let sensor = ColorSensor::find()?;
+/// let motor = LargeMotor::get(MotorPort::OutA)?;
+/// let mut pid = Pid::new(1.5, 0.1, 0.05).with_limit(100.0);
+///
+/// motor.run_direct()?;
+/// Loop::run_at(50.0, |ctx| {
+///     let error = 50.0 - sensor.get_color()? as f32;
+///     motor.set_duty_cycle_sp(pid.update(error, ctx.dt_secs()) as i32)
+/// })?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pid {
+    /// Proportional gain.
+    pub kp: f32,
+    /// Integral gain.
+    pub ki: f32,
+    /// Derivative gain.
+    pub kd: f32,
+    limit: Option<f32>,
+    integral: f32,
+    last_error: Option<f32>,
+}
+
+impl Pid {
+    /// Creates a controller with the given gains and without output limit.
+    pub fn new(kp: f32, ki: f32, kd: f32) -> Self {
+        Self {
+            kp,
+            ki,
+            kd,
+            limit: None,
+            integral: 0.0,
+            last_error: None,
+        }
+    }
+
+    /// Limits the output to `-limit..=limit`.
+    /// The integral stops growing while the output is saturated.
+    pub fn with_limit(mut self, limit: f32) -> Self {
+        self.limit = Some(limit.abs());
+        self
+    }
+
+    /// Computes the output for the current `error` (setpoint minus measurement),
+    /// `dt` seconds after the previous update.
+    ///
+    /// The derivative is skipped on the first update and if `dt` is zero.
+    pub fn update(&mut self, error: f32, dt: f32) -> f32 {
+        let derivative = match self.last_error {
+            Some(last_error) if dt > 0.0 => (error - last_error) / dt,
+            _ => 0.0,
+        };
+        self.last_error = Some(error);
+
+        let integral = self.integral + error * dt.max(0.0);
+        let output = self.kp * error + self.ki * integral + self.kd * derivative;
+
+        match self.limit {
+            Some(limit) if output.abs() > limit => output.clamp(-limit, limit),
+            _ => {
+                self.integral = integral;
+                output
+            }
+        }
+    }
+
+    /// Clears the integral and the previous error, e.g. before a new setpoint.
+    pub fn reset(&mut self) {
+        self.integral = 0.0;
+        self.last_error = None;
+    }
+}
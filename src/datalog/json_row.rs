@@ -0,0 +1,144 @@
+//! Rows of JSON-lines logs: `{"time":0.010,"gyro":-3,"left":120}`.
+
+use crate::{Ev3Error, Ev3Result};
+
+#[cfg(feature = "json")]
+use std::fmt;
+
+#[cfg(feature = "json")]
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+#[cfg(feature = "json")]
+use serde::ser::{Serialize, Serializer};
+#[cfg(feature = "json")]
+use serde_json::value::RawValue;
+#[cfg(feature = "json")]
+use serde_json::Value;
+
+/// A field of a row that is written by a `Recorder`.
+#[cfg(feature = "json")]
+#[derive(Debug, serde::Serialize)]
+#[serde(untagged)]
+enum Field {
+    /// Numbers are written verbatim, e.g. `0.010`.
+    Number(Box<RawValue>),
+    String(String),
+    /// A failed reading.
+    Null,
+}
+
+#[cfg(feature = "json")]
+impl Field {
+    fn new(value: Option<String>) -> Self {
+        match value {
+            Some(value) if value.parse::<f64>().is_ok_and(f64::is_finite) => {
+                match RawValue::from_string(value.clone()) {
+                    Ok(number) => Field::Number(number),
+                    Err(_) => Field::String(value),
+                }
+            }
+            Some(value) => Field::String(value),
+            None => Field::Null,
+        }
+    }
+}
+
+/// A JSON object that keeps the order of its fields.
+#[cfg(feature = "json")]
+#[derive(Debug)]
+struct Row<V>(Vec<(String, V)>);
+
+#[cfg(feature = "json")]
+impl<V: Serialize> Serialize for Row<V> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(key, value)| (key, value)))
+    }
+}
+
+#[cfg(feature = "json")]
+impl<'de, V: Deserialize<'de>> Deserialize<'de> for Row<V> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RowVisitor<V>(std::marker::PhantomData<V>);
+
+        impl<'de, V: Deserialize<'de>> Visitor<'de> for RowVisitor<V> {
+            type Value = Row<V>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("an object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Row<V>, A::Error> {
+                let mut fields = Vec::new();
+                while let Some(field) = map.next_entry()? {
+                    fields.push(field);
+                }
+                Ok(Row(fields))
+            }
+        }
+
+        deserializer.deserialize_map(RowVisitor(std::marker::PhantomData))
+    }
+}
+
+/// Formats a row with the `time` in seconds and the `values` of the channels.
+/// Failed readings (`None`) are written as `null`.
+#[cfg(feature = "json")]
+pub(crate) fn format_row(time: &str, values: Vec<(String, Option<String>)>) -> Ev3Result<String> {
+    let mut fields = vec![("time".to_owned(), Field::new(Some(time.to_owned())))];
+    fields.extend(
+        values
+            .into_iter()
+            .map(|(name, value)| (name, Field::new(value))),
+    );
+    serde_json::to_string(&Row(fields)).map_err(|e| Ev3Error::InternalError {
+        msg: format!("Could not format a log row: {e}"),
+        path: None,
+    })
+}
+
+/// Parses the row in line `number` of a log into its fields in the order of the line.
+/// `null` values are `None`, booleans are converted to `1` and `0`.
+#[cfg(feature = "json")]
+pub(crate) fn parse_row(line: &str, number: usize) -> Ev3Result<Vec<(String, Option<String>)>> {
+    let Row(fields) =
+        serde_json::from_str::<Row<Value>>(line).map_err(|e| Ev3Error::InternalError {
+            msg: format!("Invalid line {number} of the log: {e}"),
+            path: None,
+        })?;
+
+    Ok(fields
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::Null => None,
+                Value::Bool(value) => Some(u8::from(value).to_string()),
+                Value::String(value) => Some(value),
+                value => Some(value.to_string()),
+            };
+            (key, value)
+        })
+        .collect())
+}
+
+/// Returns an error if JSON-lines logs are not supported, i.e. without the `json` feature.
+#[cfg(feature = "json")]
+pub(crate) fn check_supported() -> Ev3Result<()> {
+    Ok(())
+}
+
+#[cfg(not(feature = "json"))]
+pub(crate) fn check_supported() -> Ev3Result<()> {
+    Err(Ev3Error::InternalError {
+        msg: "JSON-lines logs require the `json` feature".to_owned(),
+        path: None,
+    })
+}
+
+#[cfg(not(feature = "json"))]
+pub(crate) fn format_row(_time: &str, _values: Vec<(String, Option<String>)>) -> Ev3Result<String> {
+    check_supported().map(|_| String::new())
+}
+
+#[cfg(not(feature = "json"))]
+pub(crate) fn parse_row(_line: &str, _number: usize) -> Ev3Result<Vec<(String, Option<String>)>> {
+    check_supported().map(|_| Vec::new())
+}
@@ -0,0 +1,13 @@
+//! Recording of sensor values and motor attributes for post-run analysis and their replay,
+//! and buffers of timestamped samples.
+
+mod json_row;
+
+mod recorder;
+pub use self::recorder::{Channel, LogFormat, Recorder, RecorderOptions};
+
+mod replay;
+pub use self::replay::{Replay, ReplayDevice};
+
+mod time_series;
+pub use self::time_series::TimeSeries;
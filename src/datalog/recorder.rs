@@ -0,0 +1,320 @@
+//! Background recorder that writes timestamped rows to CSV or JSON-lines files.
+
+use std::fmt::{self, Display};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use super::json_row;
+use crate::task::CancelToken;
+use crate::{Attribute, Ev3Error, Ev3Result};
+
+/// File format of a `Recorder`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Comma separated values with a header line: `time,gyro,left`
+    Csv,
+    /// One JSON object per line: `{"time":0.010,"gyro":-3,"left":120}` (feature `json`)
+    JsonLines,
+}
+
+/// Options for a `Recorder`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecorderOptions {
+    /// Time between two rows.
+    pub interval: Duration,
+    /// Format of the log file.
+    pub format: LogFormat,
+    /// The log file is rotated when it grows beyond this size in bytes. (None to never rotate)
+    pub max_file_size: Option<u64>,
+    /// Number of rotated files that are kept, e.g. `log.1.csv` to `log.3.csv`.
+    pub max_files: usize,
+}
+
+impl Default for RecorderOptions {
+    /// Write a CSV row every 10 ms and rotate after 10 MB, keeping 3 old files.
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(10),
+            format: LogFormat::Csv,
+            max_file_size: Some(10 * 1024 * 1024),
+            max_files: 3,
+        }
+    }
+}
+
+/// A named value that is sampled by a `Recorder`.
+pub struct Channel {
+    name: String,
+    read: Box<dyn FnMut() -> Ev3Result<String> + Send>,
+}
+
+impl Channel {
+    /// Creates a channel that calls `read` for every row.
+    ///
+    /// ```no_run
+    /// use ev3dev_lang_rust::datalog::Channel;
+    /// use ev3dev_lang_rust::sensors::GyroSensor;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let gyro = GyroSensor::find()?;
+    /// let channel = Channel::new("angle", move || gyro.get_angle());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new<T: Display>(
+        name: &str,
+        mut read: impl FnMut() -> Ev3Result<T> + Send + 'static,
+    ) -> Self {
+        Channel {
+            name: name.to_owned(),
+            read: Box::new(move || read().map(|value| value.to_string())),
+        }
+    }
+
+    /// Creates a channel that reads the raw value of the `attribute`, e.g. `position` of a motor.
+    pub fn attribute(name: &str, attribute: Attribute) -> Self {
+        Channel::new(name, move || attribute.get::<String>())
+    }
+
+    /// Returns the name of the channel, used as column name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl fmt::Debug for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Channel").field("name", &self.name).finish()
+    }
+}
+
+/// Appends `value` as CSV field to `out`.
+fn write_csv_field(out: &mut String, value: &str) {
+    if value.contains([',', '"', '\n', '\r']) {
+        out.push('"');
+        out.push_str(&value.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(value);
+    }
+}
+
+/// Log file with size based rotation.
+#[derive(Debug)]
+struct LogFile {
+    path: PathBuf,
+    options: RecorderOptions,
+    header: Option<String>,
+    writer: BufWriter<File>,
+    size: u64,
+}
+
+impl LogFile {
+    fn create(path: &Path, options: RecorderOptions, header: Option<String>) -> Ev3Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)
+            .map_err(Ev3Error::io(path))?;
+
+        let mut log_file = LogFile {
+            path: path.to_path_buf(),
+            options,
+            header,
+            writer: BufWriter::new(file),
+            size: 0,
+        };
+        if let Some(header) = log_file.header.clone() {
+            log_file.write_line(&header)?;
+        }
+        Ok(log_file)
+    }
+
+    /// Returns the path of the `index`-th rotated file, e.g. `log.2.csv` for `log.csv`.
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let stem = self.path.file_stem().unwrap_or_default().to_string_lossy();
+        let name = match self.path.extension() {
+            Some(extension) => format!("{stem}.{index}.{}", extension.to_string_lossy()),
+            None => format!("{stem}.{index}"),
+        };
+        self.path.with_file_name(name)
+    }
+
+    fn rotate(&mut self) -> Ev3Result<()> {
+        self.writer.flush().map_err(Ev3Error::io(&self.path))?;
+
+        if self.options.max_files == 0 {
+            fs::remove_file(&self.path).map_err(Ev3Error::io(&self.path))?;
+        } else {
+            for index in (1..self.options.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(index + 1)).map_err(Ev3Error::io(&from))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1)).map_err(Ev3Error::io(&self.path))?;
+        }
+
+        *self = LogFile::create(&self.path, self.options.clone(), self.header.take())?;
+        Ok(())
+    }
+
+    fn write_line(&mut self, line: &str) -> Ev3Result<()> {
+        if self
+            .options
+            .max_file_size
+            .is_some_and(|max| self.size > 0 && self.size + line.len() as u64 + 1 > max)
+        {
+            self.rotate()?;
+        }
+
+        writeln!(self.writer, "{line}").map_err(Ev3Error::io(&self.path))?;
+        self.size += line.len() as u64 + 1;
+        Ok(())
+    }
+}
+
+/// Samples a set of channels with a fixed rate in a background thread
+/// and appends timestamped rows to a log file.
+///
+/// The `time` column contains the seconds since the start of the recorder.
+/// Failed readings are written as empty fields (CSV) or `null` (JSON-lines).
+/// The thread is stopped when the recorder is dropped.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::datalog::{Channel, Recorder, RecorderOptions};
+/// use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+/// use ev3dev_lang_rust::sensors::GyroSensor;
+/// use ev3dev_lang_rust::Device;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let gyro = GyroSensor::find()?;
+/// let motor = LargeMotor::get(MotorPort::OutA)?;
+///
+/// let recorder = Recorder::start(
+///     "/home/robot/run.csv",
+///     vec![
+///         Channel::new("angle", move || gyro.get_angle()),
+///         Channel::attribute("position", motor.get_attribute("position")?),
+///     ],
+///     RecorderOptions::default(),
+/// )?;
+///
+/// // Run the robot...
+///
+/// recorder.stop()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Recorder {
+    token: CancelToken,
+    handle: Option<JoinHandle<Ev3Result<()>>>,
+}
+
+impl Recorder {
+    /// Creates the log file at `path` and starts sampling the `channels`.
+    pub fn start(
+        path: impl AsRef<Path>,
+        mut channels: Vec<Channel>,
+        options: RecorderOptions,
+    ) -> Ev3Result<Self> {
+        let header = match options.format {
+            LogFormat::Csv => {
+                let mut header = "time".to_owned();
+                for channel in &channels {
+                    header.push(',');
+                    write_csv_field(&mut header, &channel.name);
+                }
+                Some(header)
+            }
+            LogFormat::JsonLines => {
+                json_row::check_supported()?;
+                None
+            }
+        };
+        let mut log_file = LogFile::create(path.as_ref(), options.clone(), header)?;
+
+        let token = CancelToken::new();
+        let thread_token = token.clone();
+        let handle = thread::spawn(move || {
+            let start = Instant::now();
+            let mut next_row = start;
+
+            loop {
+                let time = format!("{:.3}", start.elapsed().as_secs_f64());
+                let line = match options.format {
+                    LogFormat::Csv => {
+                        let mut line = time;
+                        for channel in &mut channels {
+                            line.push(',');
+                            if let Ok(value) = (channel.read)() {
+                                write_csv_field(&mut line, &value);
+                            }
+                        }
+                        line
+                    }
+                    LogFormat::JsonLines => {
+                        let values = channels
+                            .iter_mut()
+                            .map(|channel| (channel.name.clone(), (channel.read)().ok()))
+                            .collect();
+                        json_row::format_row(&time, values)?
+                    }
+                };
+                log_file.write_line(&line)?;
+
+                next_row += options.interval;
+                let delay = next_row.saturating_duration_since(Instant::now());
+                if !thread_token.sleep(delay) {
+                    break;
+                }
+            }
+
+            log_file
+                .writer
+                .flush()
+                .map_err(Ev3Error::io(&log_file.path))
+        });
+
+        Ok(Recorder {
+            token,
+            handle: Some(handle),
+        })
+    }
+
+    /// Checks if the recorder is still sampling. It stops on the first failed write.
+    pub fn is_running(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// Stops the recorder and flushes the log file. Returns the error that stopped the recorder, if any.
+    pub fn stop(mut self) -> Ev3Result<()> {
+        self.shutdown()
+    }
+
+    fn shutdown(&mut self) -> Ev3Result<()> {
+        self.token.cancel();
+        match self.handle.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            Some(Err(_)) => Err(Ev3Error::InternalError {
+                msg: "Recorder thread panicked".to_owned(),
+                path: None,
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let _ = self.shutdown();
+    }
+}
@@ -0,0 +1,448 @@
+//! Backend that plays recorded logs back through the device types.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use super::json_row;
+use crate::driver::DRIVER_PATH;
+use crate::{Attribute, AttributeSource, Backend, Ev3Error, Ev3Result, Port};
+
+/// Rows of a log file written by a `Recorder`.
+#[derive(Debug, Default)]
+struct Log {
+    columns: Vec<String>,
+    rows: Vec<(f64, Vec<Option<String>>)>,
+}
+
+impl Log {
+    /// Splits a CSV line into its fields.
+    fn csv_fields(line: &str) -> Vec<String> {
+        let mut fields = vec![String::new()];
+        let mut quoted = false;
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if quoted && chars.peek() == Some(&'"') => {
+                    chars.next();
+                    fields.last_mut().unwrap().push('"');
+                }
+                '"' => quoted = !quoted,
+                ',' if !quoted => fields.push(String::new()),
+                c => fields.last_mut().unwrap().push(c),
+            }
+        }
+        fields
+    }
+
+    fn parse_time(value: Option<&str>, line: usize) -> Ev3Result<f64> {
+        value
+            .and_then(|time| time.parse().ok())
+            .ok_or_else(|| Ev3Error::InternalError {
+                msg: format!("Missing time in line {line} of the log"),
+                path: None,
+            })
+    }
+
+    fn parse_csv(content: &str) -> Ev3Result<Self> {
+        let mut lines = content.lines().filter(|line| !line.is_empty());
+        let mut columns = Self::csv_fields(lines.next().unwrap_or_default());
+        if columns.first().map(String::as_str) != Some("time") {
+            return Err(Ev3Error::InternalError {
+                msg: "The first column of the log must be `time`".to_owned(),
+                path: None,
+            });
+        }
+        columns.remove(0);
+
+        let mut rows = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let mut fields = Self::csv_fields(line).into_iter();
+            let time = Self::parse_time(fields.next().as_deref(), i + 2)?;
+            let values = fields
+                .map(|field| Some(field).filter(|field| !field.is_empty()))
+                .collect();
+            rows.push((time, values));
+        }
+
+        Ok(Log { columns, rows })
+    }
+
+    fn parse_json_lines(content: &str) -> Ev3Result<Self> {
+        let mut log = Log::default();
+
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut time = None;
+            let mut values = vec![None; log.columns.len()];
+            for (key, value) in json_row::parse_row(line, i + 1)? {
+                if key == "time" {
+                    time = value;
+                    continue;
+                }
+                let index = match log.columns.iter().position(|column| *column == key) {
+                    Some(index) => index,
+                    None => {
+                        log.columns.push(key);
+                        values.push(None);
+                        log.columns.len() - 1
+                    }
+                };
+                values[index] = value;
+            }
+
+            let time = Self::parse_time(time.as_deref(), i + 1)?;
+            log.rows.push((time, values));
+        }
+
+        Ok(log)
+    }
+
+    /// Returns the index of the last row at or before `time`.
+    fn row_at(&self, time: f64) -> usize {
+        self.rows
+            .partition_point(|(row_time, _)| *row_time <= time)
+            .saturating_sub(1)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Clock {
+    Paused {
+        time: f64,
+    },
+    Playing {
+        started: Instant,
+        offset: f64,
+        speed: f64,
+    },
+}
+
+impl Clock {
+    fn time(&self) -> f64 {
+        match *self {
+            Clock::Paused { time } => time,
+            Clock::Playing {
+                started,
+                offset,
+                speed,
+            } => offset + started.elapsed().as_secs_f64() * speed,
+        }
+    }
+}
+
+/// Declaration of a device of a `Replay`.
+///
+/// Attributes are either mapped to a column of the log or have a fixed initial value.
+/// Every attribute can be written, e.g. the `mode` of a sensor or the `command` of a motor.
+#[derive(Debug, Clone)]
+pub struct ReplayDevice {
+    class_name: String,
+    driver_name: String,
+    address: String,
+    columns: Vec<(String, String)>,
+    attributes: Vec<(String, String)>,
+}
+
+impl ReplayDevice {
+    /// Declares a device of the class `class_name` with the driver `driver_name` on the given port.
+    pub fn new(class_name: &str, driver_name: &str, port: &dyn Port) -> Self {
+        ReplayDevice {
+            class_name: class_name.to_owned(),
+            driver_name: driver_name.to_owned(),
+            address: format!("replay:{}", port.address()),
+            columns: Vec::new(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Reads the attribute `attribute_name` from the log column `column`.
+    pub fn column(mut self, attribute_name: &str, column: &str) -> Self {
+        self.columns
+            .push((attribute_name.to_owned(), column.to_owned()));
+        self
+    }
+
+    /// Sets the initial value of the attribute `attribute_name`, e.g. the `mode` of a sensor.
+    pub fn attribute(mut self, attribute_name: &str, value: &str) -> Self {
+        self.attributes
+            .push((attribute_name.to_owned(), value.to_owned()));
+        self
+    }
+}
+
+#[derive(Debug)]
+struct Device {
+    class_name: String,
+    name: String,
+    columns: HashMap<String, usize>,
+    values: Mutex<HashMap<String, String>>,
+}
+
+#[derive(Debug)]
+struct ReplayInner {
+    log: Log,
+    devices: Mutex<Vec<Arc<Device>>>,
+    clock: Mutex<Clock>,
+}
+
+/// Backend that returns the values of a log written by a `Recorder` in timestamped order.
+///
+/// Control algorithms can be debugged and tested off-robot against real captured data:
+/// the devices are created with the `_from()` constructors and read the logged values.
+/// The replay starts paused at the first row; it is either played in real time with `play()`
+/// or moved manually with `seek()` and `step()`.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::datalog::{Replay, ReplayDevice};
+/// use ev3dev_lang_rust::sensors::{GyroSensor, SensorPort};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let replay = Replay::open("run.csv")?;
+/// replay.add(
+///     ReplayDevice::new("lego-sensor", "lego-ev3-gyro", &SensorPort::In2)
+///         .attribute("mode", GyroSensor::MODE_GYRO_ANG)
+///         .column("value0", "angle"),
+/// )?;
+///
+/// let gyro = GyroSensor::get_from(&replay, SensorPort::In2)?;
+/// while replay.step() {
+///     println!("{:.3} s: {}°", replay.time(), gyro.get_angle()?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Replay {
+    inner: Arc<ReplayInner>,
+}
+
+impl Replay {
+    /// Reads the log file at `path`. See `parse()`.
+    pub fn open(path: impl AsRef<Path>) -> Ev3Result<Self> {
+        let path = path.as_ref();
+        Self::parse(&fs::read_to_string(path).map_err(Ev3Error::io(path))?)
+    }
+
+    /// Parses a log in the CSV or JSON-lines format of a `Recorder`.
+    pub fn parse(content: &str) -> Ev3Result<Self> {
+        let log = if content.trim_start().starts_with('{') {
+            Log::parse_json_lines(content)?
+        } else {
+            Log::parse_csv(content)?
+        };
+        let time = log.rows.first().map_or(0.0, |(time, _)| *time);
+
+        Ok(Replay {
+            inner: Arc::new(ReplayInner {
+                log,
+                devices: Mutex::new(Vec::new()),
+                clock: Mutex::new(Clock::Paused { time }),
+            }),
+        })
+    }
+
+    /// Returns the names of all columns of the log, without `time`.
+    pub fn columns(&self) -> &[String] {
+        &self.inner.log.columns
+    }
+
+    /// Adds a device and returns its name, e.g. `sensor0`.
+    /// Fails if a mapped column does not exist in the log.
+    pub fn add(&self, device: ReplayDevice) -> Ev3Result<String> {
+        let mut columns = HashMap::new();
+        for (attribute_name, column) in &device.columns {
+            let index = self
+                .columns()
+                .iter()
+                .position(|c| c == column)
+                .ok_or_else(|| Ev3Error::InternalError {
+                    msg: format!("Column '{column}' not found in the log"),
+                    path: None,
+                })?;
+            columns.insert(attribute_name.clone(), index);
+        }
+
+        let mut devices = self.inner.devices.lock().unwrap();
+        let prefix = match device.class_name.as_str() {
+            "lego-sensor" => "sensor",
+            "tacho-motor" | "dc-motor" | "servo-motor" => "motor",
+            _ => "device",
+        };
+        let count = devices
+            .iter()
+            .filter(|d| d.class_name == device.class_name)
+            .count();
+        let name = format!("{prefix}{count}");
+
+        let mut values = device.attributes.into_iter().collect::<HashMap<_, _>>();
+        values.insert("driver_name".to_owned(), device.driver_name);
+        values.insert("address".to_owned(), device.address);
+
+        devices.push(Arc::new(Device {
+            class_name: device.class_name,
+            name: name.clone(),
+            columns,
+            values: Mutex::new(values),
+        }));
+        Ok(name)
+    }
+
+    /// Returns the current replay time in seconds, the time scale of the log.
+    pub fn time(&self) -> f64 {
+        self.inner.clock.lock().unwrap().time()
+    }
+
+    /// Checks if the replay time is past the last row of the log.
+    pub fn is_finished(&self) -> bool {
+        self.inner
+            .log
+            .rows
+            .last()
+            .is_none_or(|(time, _)| self.time() >= *time)
+    }
+
+    /// Plays the log in real time, scaled by `speed`, starting at the current time.
+    pub fn play(&self, speed: f64) {
+        let mut clock = self.inner.clock.lock().unwrap();
+        *clock = Clock::Playing {
+            started: Instant::now(),
+            offset: clock.time(),
+            speed,
+        };
+    }
+
+    /// Pauses the replay at the current time.
+    pub fn pause(&self) {
+        let mut clock = self.inner.clock.lock().unwrap();
+        *clock = Clock::Paused { time: clock.time() };
+    }
+
+    /// Pauses the replay at `time` in seconds.
+    pub fn seek(&self, time: f64) {
+        *self.inner.clock.lock().unwrap() = Clock::Paused { time };
+    }
+
+    /// Pauses the replay at the next row of the log. Returns `false` if there is no next row.
+    pub fn step(&self) -> bool {
+        let mut clock = self.inner.clock.lock().unwrap();
+        let rows = &self.inner.log.rows;
+        let time = clock.time();
+
+        let next = rows.partition_point(|(row_time, _)| *row_time <= time);
+        match rows.get(next) {
+            Some((row_time, _)) => {
+                *clock = Clock::Paused { time: *row_time };
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn device(&self, class_name: &str, name: &str) -> Option<Arc<Device>> {
+        self.inner
+            .devices
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|device| device.class_name == class_name && device.name == name)
+            .cloned()
+    }
+}
+
+impl Backend for Replay {
+    fn find_names(&self, class_name: &str) -> Ev3Result<Vec<String>> {
+        Ok(self
+            .inner
+            .devices
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|device| device.class_name == class_name)
+            .map(|device| device.name.clone())
+            .collect())
+    }
+
+    fn open_attribute(
+        &self,
+        class_name: &str,
+        name: &str,
+        attribute_name: &str,
+    ) -> Ev3Result<Attribute> {
+        let device = self
+            .device(class_name, name)
+            .ok_or_else(|| Ev3Error::NotConnected {
+                device: format!("{class_name}/{name}"),
+                port: None,
+                path: Some(Path::new(DRIVER_PATH).join(class_name).join(name)),
+            })?;
+
+        let path = Path::new(DRIVER_PATH)
+            .join(class_name)
+            .join(name)
+            .join(attribute_name);
+
+        Ok(Attribute::from_source(
+            &path,
+            Arc::new(ReplayAttribute {
+                replay: self.clone(),
+                device,
+                attribute_name: attribute_name.to_owned(),
+            }),
+        ))
+    }
+}
+
+/// Attribute of a device of a `Replay`.
+#[derive(Debug)]
+struct ReplayAttribute {
+    replay: Replay,
+    device: Arc<Device>,
+    attribute_name: String,
+}
+
+impl AttributeSource for ReplayAttribute {
+    fn read(&self) -> Ev3Result<Vec<u8>> {
+        if let Some(value) = self.device.values.lock().unwrap().get(&self.attribute_name) {
+            return Ok(value.as_bytes().to_vec());
+        }
+
+        let column = self
+            .device
+            .columns
+            .get(&self.attribute_name)
+            .ok_or_else(|| Ev3Error::InternalError {
+                msg: format!("No value for attribute '{}'", self.attribute_name),
+                path: None,
+            })?;
+
+        let log = &self.replay.inner.log;
+        let time = self.replay.time();
+        log.rows
+            .get(log.row_at(time))
+            .and_then(|(_, values)| values.get(*column).cloned().flatten())
+            .map(String::into_bytes)
+            .ok_or_else(|| Ev3Error::InternalError {
+                msg: format!(
+                    "No value of column '{}' at {time:.3} s",
+                    log.columns[*column]
+                ),
+                path: None,
+            })
+    }
+
+    fn write(&self, data: &[u8]) -> Ev3Result<()> {
+        self.device.values.lock().unwrap().insert(
+            self.attribute_name.clone(),
+            String::from_utf8_lossy(data).into_owned(),
+        );
+        Ok(())
+    }
+}
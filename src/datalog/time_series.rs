@@ -0,0 +1,171 @@
+//! Ring buffer of timestamped samples.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Ring buffer of timestamped samples with a fixed capacity.
+///
+/// When the buffer is full the oldest sample is dropped.
+/// The statistics are computed over a time window that ends at the latest sample,
+/// so they do not depend on how often the buffer is read.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use ev3dev_lang_rust::datalog::TimeSeries;
+/// use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let motor = LargeMotor::get(MotorPort::OutA)?;
+/// let mut positions = TimeSeries::new(100);
+///
+/// loop {
+///     positions.push(motor.get_position()?);
+///
+///     // Speed in tacho counts per second over the last 200 ms.
+///     if let Some(speed) = positions.rate(Duration::from_millis(200)) {
+///         println!("speed: {speed:.1}");
+///     }
+/// #   break;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeSeries<T> {
+    capacity: usize,
+    samples: VecDeque<(Instant, T)>,
+}
+
+impl<T> TimeSeries<T> {
+    /// Creates an empty buffer that keeps at most `capacity` samples (at least one).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            samples: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Adds a sample that was taken now.
+    pub fn push(&mut self, value: T) {
+        self.push_at(Instant::now(), value);
+    }
+
+    /// Adds a sample that was taken at `time`. Samples older than the latest one are sorted in.
+    pub fn push_at(&mut self, time: Instant, value: T) {
+        if self.samples.len() >= self.capacity {
+            if self
+                .samples
+                .front()
+                .is_some_and(|(oldest, _)| time < *oldest)
+            {
+                return;
+            }
+            self.samples.pop_front();
+        }
+
+        let index = self
+            .samples
+            .partition_point(|(sample_time, _)| *sample_time <= time);
+        self.samples.insert(index, (time, value));
+    }
+
+    /// Returns the maximal number of samples.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of samples.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Checks if the buffer contains no samples.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Removes all samples.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Returns the latest sample.
+    pub fn latest(&self) -> Option<(Instant, &T)> {
+        self.samples.back().map(|(time, value)| (*time, value))
+    }
+
+    /// Returns the oldest sample.
+    pub fn oldest(&self) -> Option<(Instant, &T)> {
+        self.samples.front().map(|(time, value)| (*time, value))
+    }
+
+    /// Returns all samples, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Instant, &T)> {
+        self.samples.iter().map(|(time, value)| (*time, value))
+    }
+
+    /// Returns the samples of the last `duration` before the latest sample, oldest first.
+    pub fn window(&self, duration: Duration) -> impl DoubleEndedIterator<Item = (Instant, &T)> {
+        let start = self
+            .samples
+            .back()
+            .and_then(|(latest, _)| latest.checked_sub(duration));
+        let index = start.map_or(0, |start| {
+            self.samples.partition_point(|(time, _)| *time < start)
+        });
+
+        self.samples
+            .range(index..)
+            .map(|(time, value)| (*time, value))
+    }
+}
+
+impl<T: Copy + Into<f64>> TimeSeries<T> {
+    /// Returns the smallest value of the last `duration`.
+    pub fn min(&self, duration: Duration) -> Option<f64> {
+        self.window(duration)
+            .map(|(_, value)| (*value).into())
+            .reduce(f64::min)
+    }
+
+    /// Returns the largest value of the last `duration`.
+    pub fn max(&self, duration: Duration) -> Option<f64> {
+        self.window(duration)
+            .map(|(_, value)| (*value).into())
+            .reduce(f64::max)
+    }
+
+    /// Returns the mean of the values of the last `duration`.
+    pub fn mean(&self, duration: Duration) -> Option<f64> {
+        let (sum, count) = self
+            .window(duration)
+            .fold((0.0, 0usize), |(sum, count), (_, value)| {
+                (sum + (*value).into(), count + 1)
+            });
+
+        if count == 0 {
+            None
+        } else {
+            Some(sum / count as f64)
+        }
+    }
+
+    /// Returns the change of the value per second over the last `duration`,
+    /// e.g. the speed derived from position samples.
+    ///
+    /// Returns `None` if the window contains less than two samples at different times.
+    pub fn rate(&self, duration: Duration) -> Option<f64> {
+        let mut window = self.window(duration);
+        let (first_time, first) = window.next()?;
+        let (last_time, last) = window.next_back()?;
+
+        let seconds = last_time.duration_since(first_time).as_secs_f64();
+        if seconds > 0.0 {
+            Some(((*last).into() - (*first).into()) / seconds)
+        } else {
+            None
+        }
+    }
+}
@@ -1,27 +1,69 @@
-use crate::{Attribute, Ev3Result};
+use std::path::{Path, PathBuf};
+
+use crate::{Attribute, Ev3Result, ReadOnlyAttribute, WritableAttribute};
 
 /// The ev3dev device base trait
 pub trait Device {
     /// Returns the attribute wrapper for an attribute name.
-    fn get_attribute(&self, name: &str) -> Attribute;
+    /// Returns an error if the attribute does not exist or cannot be opened, e.g. because the device was unplugged.
+    fn get_attribute(&self, name: &str) -> Ev3Result<Attribute>;
+
+    /// Returns the attribute wrapper for an attribute name and panics if it cannot be opened.
+    /// This is the behavior of `get_attribute()` before it returned an `Ev3Result`.
+    #[deprecated(note = "use `get_attribute()`, which returns an error instead of panicking")]
+    fn get_attribute_unchecked(&self, name: &str) -> Attribute {
+        self.get_attribute(name)
+            .unwrap_or_else(|e| panic!("Error while accessing {name}: {e}"))
+    }
+
+    /// Returns the attribute wrapper for an attribute that can only be read, e.g. `value0`.
+    fn get_read_only_attribute(&self, name: &str) -> Ev3Result<ReadOnlyAttribute> {
+        Ok(self.get_attribute(name)?.into())
+    }
+
+    /// Returns the attribute wrapper for an attribute that can only be written, e.g. `command`.
+    fn get_writable_attribute(&self, name: &str) -> Ev3Result<WritableAttribute> {
+        Ok(self.get_attribute(name)?.into())
+    }
+
+    /// Checks if the device is still connected.
+    fn is_connected(&self) -> bool;
+
+    /// Finds the device again after it was unplugged and re-plugged.
+    ///
+    /// Devices that were found with a port or driver name do this automatically: attributes are re-resolved
+    /// when they are opened, and already opened attributes reopen themselves if an access fails with
+    /// `ENODEV` or `ENOENT`. Calling `reconnect()` is only necessary to check for the device explicitly.
+    fn reconnect(&self) -> Ev3Result<()>;
+
+    /// Drops the cached attribute handles of the device, so the next accesses open them again.
+    /// See `Driver::clear_cache()`.
+    fn clear_cache(&self) {}
 
     /// Returns the name of the port that the motor is connected to.
     fn get_address(&self) -> Ev3Result<String> {
-        self.get_attribute("address").get()
+        self.get_read_only_attribute("address")?.get()
     }
 
     /// Sends a command to the device controller.
     fn set_command(&self, command: &str) -> Ev3Result<()> {
-        self.get_attribute("command").set_str_slice(command)
+        self.get_writable_attribute("command")?
+            .set_str_slice(command)
     }
 
     /// Returns a space separated list of commands that are supported by the device controller.
     fn get_commands(&self) -> Ev3Result<Vec<String>> {
-        self.get_attribute("commands").get_vec()
+        self.get_read_only_attribute("commands")?.get_vec()
     }
 
     /// Returns the name of the driver that provides this device.
     fn get_driver_name(&self) -> Ev3Result<String> {
-        self.get_attribute("driver_name").get()
+        self.get_read_only_attribute("driver_name")?.get()
     }
 }
+
+/// Returns the directory of the `device`, e.g. `/sys/class/tacho-motor/motor0` (None if unknown).
+pub(crate) fn device_path<D: Device + ?Sized>(device: &D) -> Option<PathBuf> {
+    let address = device.get_attribute("address").ok()?;
+    address.get_file_path().parent().map(Path::to_path_buf)
+}
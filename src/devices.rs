@@ -0,0 +1,156 @@
+//! All devices that are attached to the brick, detected with a single `scan()`.
+
+use crate::motors::{LargeMotor, LinearActuator, MediumMotor, MotorPort, TachoMotor, WedoMotor};
+use crate::sensors::{
+    AccelerometerSensor, ColorSensor, CompassSensor, GenericSensor, GyroSensor,
+    HiTechnicColorSensor, I2cSensor, InfraredSensor, IrSeekerSensor, LightSensor, PixyCamera,
+    SoundSensor, TemperatureSensor, TouchSensor, UltrasonicSensor, WedoMotionSensor,
+    WedoTiltSensor,
+};
+use std::sync::Arc;
+
+use crate::{scan_from, Backend, DeviceDescriptor, Ev3Result, Led, Port, PowerSupply, Sysfs};
+
+macro_rules! devices {
+    ($( $field:ident: $device:ident ),* $(,)?) => {
+        /// All motors, sensors, leds and the battery that were connected at the time of `discover()`.
+        ///
+        /// Devices that are connected later are not included, call `discover()` again
+        /// or use a `DeviceWatcher`.
+        ///
+        /// # Example
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::MotorPort;
+        /// use ev3dev_lang_rust::Ev3;
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// let ev3 = Ev3::discover()?;
+        ///
+        /// for sensor in ev3.color_sensors() {
+        ///     sensor.set_mode_col_color()?;
+        /// }
+        ///
+        /// if let Some(motor) = ev3.motor_on(MotorPort::OutA) {
+        ///     motor.run_forever()?;
+        /// }
+        /// # Ok(())
+        /// # }
+        /// ```
+        #[derive(Debug, Clone)]
+        pub struct Ev3 {
+            backend: Arc<dyn Backend>,
+            descriptors: Vec<DeviceDescriptor>,
+            $( $field: Vec<$device>, )*
+            led: Option<Led>,
+            battery: Option<PowerSupply>,
+        }
+
+        impl Ev3 {
+            /// Scans all device classes and creates every motor, sensor, led and battery that is found.
+            pub fn discover() -> Ev3Result<Self> {
+                let mut ev3 = Self::discover_from(&Sysfs)?;
+                ev3.led = Led::new().ok();
+                ev3.battery = PowerSupply::new().ok();
+                Ok(ev3)
+            }
+
+            /// Scans all device classes of the given `backend`, e.g. a `MockEv3` or a `RemoteEv3`,
+            /// and creates every motor and sensor that is found.
+            /// The leds and the battery are only found by `discover()` on the brick itself.
+            pub fn discover_from<B: Backend + Clone + 'static>(backend: &B) -> Ev3Result<Self> {
+                let descriptors = scan_from(backend)?;
+
+                Ok(Ev3 {
+                    $(
+                        $field: descriptors
+                            .iter()
+                            .filter_map(|descriptor| {
+                                $device::from_descriptor_from(backend, descriptor).ok()
+                            })
+                            .collect(),
+                    )*
+                    led: None,
+                    battery: None,
+                    backend: Arc::new(backend.clone()),
+                    descriptors,
+                })
+            }
+
+            $(
+                #[doc = concat!("Returns all connected `", stringify!($device), "`s.")]
+                pub fn $field(&self) -> &[$device] {
+                    &self.$field
+                }
+            )*
+        }
+    };
+}
+
+devices!(
+    large_motors: LargeMotor,
+    medium_motors: MediumMotor,
+    linear_actuators: LinearActuator,
+    wedo_motors: WedoMotor,
+    accelerometer_sensors: AccelerometerSensor,
+    color_sensors: ColorSensor,
+    compass_sensors: CompassSensor,
+    gyro_sensors: GyroSensor,
+    hi_technic_color_sensors: HiTechnicColorSensor,
+    i2c_sensors: I2cSensor,
+    infrared_sensors: InfraredSensor,
+    ir_seeker_sensors: IrSeekerSensor,
+    light_sensors: LightSensor,
+    pixy_cameras: PixyCamera,
+    sound_sensors: SoundSensor,
+    temperature_sensors: TemperatureSensor,
+    touch_sensors: TouchSensor,
+    ultrasonic_sensors: UltrasonicSensor,
+    wedo_motion_sensors: WedoMotionSensor,
+    wedo_tilt_sensors: WedoTiltSensor,
+);
+
+impl Ev3 {
+    /// Returns the descriptors of all devices that were found, including unsupported devices.
+    pub fn descriptors(&self) -> &[DeviceDescriptor] {
+        &self.descriptors
+    }
+
+    /// Returns the descriptor of the device of the class `class_name` on the given port.
+    fn descriptor_on(&self, class_name: &str, port: &dyn Port) -> Option<&DeviceDescriptor> {
+        self.descriptors.iter().find(|descriptor| {
+            descriptor.class == class_name
+                && descriptor
+                    .address
+                    .as_deref()
+                    .is_some_and(|address| port.matches_address(address))
+        })
+    }
+
+    /// Returns the large or medium motor on the given port.
+    pub fn motor_on(&self, port: MotorPort) -> Option<TachoMotor> {
+        let descriptor = self.descriptor_on("tacho-motor", &port)?;
+
+        LargeMotor::from_descriptor_from(&self.backend, descriptor)
+            .map(TachoMotor::from)
+            .or_else(|_| {
+                MediumMotor::from_descriptor_from(&self.backend, descriptor).map(TachoMotor::from)
+            })
+            .ok()
+    }
+
+    /// Returns the sensor on the given port, e.g. a `MuxPort`.
+    pub fn sensor_on(&self, port: &dyn Port) -> Option<GenericSensor> {
+        let descriptor = self.descriptor_on("lego-sensor", port)?;
+        GenericSensor::from_descriptor_from(&self.backend, descriptor).ok()
+    }
+
+    /// Returns the leds of the brick. (None if the leds were not found)
+    pub fn led(&self) -> Option<&Led> {
+        self.led.as_ref()
+    }
+
+    /// Returns the battery of the brick. (None if the battery was not found)
+    pub fn battery(&self) -> Option<&PowerSupply> {
+        self.battery.as_ref()
+    }
+}
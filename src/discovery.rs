@@ -0,0 +1,267 @@
+//! Enumerate all devices that are attached to the brick.
+
+use crate::task::CancelToken;
+use crate::{Backend, Driver, Ev3Error, Ev3Result, Sysfs};
+use std::io;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Device classes that are included in a `scan()`.
+pub const SCAN_CLASSES: [&str; 6] = [
+    "lego-sensor",
+    "tacho-motor",
+    "dc-motor",
+    "servo-motor",
+    "leds",
+    "power_supply",
+];
+
+/// Description of a device in the `/sys/class/` directory.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceDescriptor {
+    /// Class of the device, e.g. `lego-sensor`.
+    pub class: String,
+    /// Name of the driver, e.g. `lego-ev3-color`. Not every class provides a driver name.
+    pub driver_name: Option<String>,
+    /// Address of the port the device is connected to, e.g. `ev3-ports:in1`.
+    /// Not every class provides an address.
+    pub address: Option<String>,
+    /// Name of the device directory, e.g. `sensor0`.
+    pub name: String,
+}
+
+impl DeviceDescriptor {
+    /// Read the descriptor of the device `/sys/class/{class}/{name}`.
+    pub fn read(class: &str, name: &str) -> Self {
+        DeviceDescriptor::read_from(&Sysfs, class, name)
+    }
+
+    /// Read the descriptor of the device `class/name` of the given `backend`.
+    pub fn read_from(backend: &dyn Backend, class: &str, name: &str) -> Self {
+        let read = |attribute_name| {
+            backend
+                .open_attribute(class, name, attribute_name)
+                .and_then(|attribute| attribute.get::<String>())
+                .ok()
+        };
+
+        DeviceDescriptor {
+            class: class.to_owned(),
+            driver_name: read("driver_name"),
+            address: read("address"),
+            name: name.to_owned(),
+        }
+    }
+
+    /// Returns a `Driver` to access the attributes of this device.
+    pub fn get_driver(&self) -> Driver {
+        Driver::new(&self.class, &self.name)
+    }
+}
+
+/// Returns the descriptors of all devices of the given class.
+///
+/// Returns an empty list if the class does not exist, e.g. because no such device was ever connected.
+pub fn scan_class(class: &str) -> Ev3Result<Vec<DeviceDescriptor>> {
+    scan_class_from(&Sysfs, class)
+}
+
+/// Returns the descriptors of all devices of the given class of the given `backend`. See `scan_class()`.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(backend), err)
+)]
+pub fn scan_class_from(backend: &dyn Backend, class: &str) -> Ev3Result<Vec<DeviceDescriptor>> {
+    let mut names = match backend.find_names(class) {
+        Ok(names) => names,
+        Err(Ev3Error::Io { source, .. }) if source.kind() == io::ErrorKind::NotFound => {
+            return Ok(Vec::new())
+        }
+        Err(e) => return Err(e),
+    };
+    names.sort();
+
+    let descriptors = names
+        .iter()
+        .map(|name| DeviceDescriptor::read_from(backend, class, name))
+        .collect::<Vec<_>>();
+
+    #[cfg(feature = "tracing")]
+    for descriptor in &descriptors {
+        tracing::debug!(
+            name = %descriptor.name,
+            driver_name = ?descriptor.driver_name,
+            address = ?descriptor.address,
+            "found device"
+        );
+    }
+
+    Ok(descriptors)
+}
+
+/// Returns the descriptors of all sensors, motors, leds and power supplies.
+///
+/// # Example
+///
+/// ```no_run
+/// use ev3dev_lang_rust::sensors::ColorSensor;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// for descriptor in ev3dev_lang_rust::scan()? {
+///     println!("{:?} at {:?}", descriptor.driver_name, descriptor.address);
+///
+///     if let Ok(sensor) = ColorSensor::from_descriptor(&descriptor) {
+///         println!("Found color sensor");
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn scan() -> Ev3Result<Vec<DeviceDescriptor>> {
+    scan_from(&Sysfs)
+}
+
+/// Returns the descriptors of all sensors, motors, leds and power supplies of the given `backend`,
+/// e.g. a `MockEv3` or a `RemoteEv3`. See `scan()`.
+pub fn scan_from(backend: &dyn Backend) -> Ev3Result<Vec<DeviceDescriptor>> {
+    let mut descriptors = Vec::new();
+    for class in SCAN_CLASSES {
+        descriptors.extend(scan_class_from(backend, class)?);
+    }
+    Ok(descriptors)
+}
+
+/// Event emitted by a `DeviceWatcher`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DeviceEvent {
+    /// A new device was connected.
+    Connected(DeviceDescriptor),
+    /// A device was disconnected.
+    Disconnected(DeviceDescriptor),
+}
+
+/// Watches device classes for connected and disconnected devices.
+///
+/// sysfs does not emit inotify events when devices appear or disappear, so the watcher
+/// rescans the classes in a background thread with a fixed interval.
+/// The thread is stopped when the watcher is dropped.
+///
+/// # Example
+///
+/// ```no_run
+/// use ev3dev_lang_rust::{DeviceEvent, DeviceWatcher};
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let watcher = DeviceWatcher::new(Duration::from_millis(500))?;
+///
+/// while let Some(event) = watcher.recv() {
+///     match event {
+///         DeviceEvent::Connected(device) => println!("Connected: {:?}", device.driver_name),
+///         DeviceEvent::Disconnected(device) => println!("Disconnected: {:?}", device.driver_name),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DeviceWatcher {
+    receiver: Receiver<DeviceEvent>,
+    token: CancelToken,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl DeviceWatcher {
+    /// Watch the `lego-sensor` and `tacho-motor` classes and rescan them every `interval`.
+    pub fn new(interval: Duration) -> Ev3Result<Self> {
+        Self::with_classes(&["lego-sensor", "tacho-motor"], interval)
+    }
+
+    /// Watch the given classes and rescan them every `interval`.
+    ///
+    /// Devices that are connected when the watcher is created do not emit an event.
+    pub fn with_classes(classes: &[&str], interval: Duration) -> Ev3Result<Self> {
+        let classes = classes.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        let scan_all = move || -> Ev3Result<Vec<DeviceDescriptor>> {
+            let mut descriptors = Vec::new();
+            for class in &classes {
+                descriptors.extend(scan_class(class)?);
+            }
+            Ok(descriptors)
+        };
+
+        let mut known = scan_all()?;
+
+        let (sender, receiver) = mpsc::channel();
+        let token = CancelToken::new();
+
+        let thread_token = token.clone();
+        let handle = thread::spawn(move || {
+            while thread_token.sleep(interval) {
+                // Devices can disappear while they are scanned, just try again next time.
+                let current = match scan_all() {
+                    Ok(current) => current,
+                    Err(_) => continue,
+                };
+
+                let disconnected = known
+                    .iter()
+                    .filter(|d| !current.contains(d))
+                    .cloned()
+                    .map(DeviceEvent::Disconnected);
+                let connected = current
+                    .iter()
+                    .filter(|d| !known.contains(d))
+                    .cloned()
+                    .map(DeviceEvent::Connected);
+
+                for event in disconnected.chain(connected) {
+                    if sender.send(event).is_err() {
+                        return;
+                    }
+                }
+
+                known = current;
+            }
+        });
+
+        Ok(DeviceWatcher {
+            receiver,
+            token,
+            handle: Some(handle),
+        })
+    }
+
+    /// Block until the next event occurs. Returns `None` if the watcher thread has stopped.
+    pub fn recv(&self) -> Option<DeviceEvent> {
+        self.receiver.recv().ok()
+    }
+
+    /// Returns the next event if one is pending.
+    pub fn try_recv(&self) -> Option<DeviceEvent> {
+        match self.receiver.try_recv() {
+            Ok(event) => Some(event),
+            Err(TryRecvError::Empty) | Err(TryRecvError::Disconnected) => None,
+        }
+    }
+
+    /// Wait for the next event for at most `timeout`.
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<DeviceEvent> {
+        match self.receiver.recv_timeout(timeout) {
+            Ok(event) => Some(event),
+            Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => None,
+        }
+    }
+}
+
+impl Drop for DeviceWatcher {
+    fn drop(&mut self) {
+        self.token.cancel();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
@@ -0,0 +1,64 @@
+//! `embedded-graphics` support for the `Display`.
+
+use std::convert::Infallible;
+
+use embedded_graphics_core::draw_target::DrawTarget;
+use embedded_graphics_core::geometry::{OriginDimensions, Size};
+use embedded_graphics_core::pixelcolor::BinaryColor;
+use embedded_graphics_core::Pixel;
+
+use super::Display;
+
+impl OriginDimensions for Display {
+    fn size(&self) -> Size {
+        Size::new(self.width(), self.height())
+    }
+}
+
+/// Draws `BinaryColor::On` as black and `BinaryColor::Off` as white pixels.
+///
+/// Like all other drawing operations, the result is shown after `Display::update()`.
+///
+/// # Example
+/// ```no_run
+/// use embedded_graphics::mono_font::{ascii::FONT_6X10, MonoTextStyle};
+/// use embedded_graphics::pixelcolor::BinaryColor;
+/// use embedded_graphics::prelude::*;
+/// use embedded_graphics::primitives::{Circle, PrimitiveStyle};
+/// use embedded_graphics::text::Text;
+/// use ev3dev_lang_rust::Display;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let mut display = Display::new()?;
+///
+/// Circle::new(Point::new(60, 40), 50)
+///     .into_styled(PrimitiveStyle::with_stroke(BinaryColor::On, 3))
+///     .draw(&mut display)
+///     .unwrap();
+/// Text::new("Hello EV3!", Point::new(4, 12), MonoTextStyle::new(&FONT_6X10, BinaryColor::On))
+///     .draw(&mut display)
+///     .unwrap();
+///
+/// display.update();
+/// # Ok(())
+/// # }
+/// ```
+impl DrawTarget for Display {
+    type Color = BinaryColor;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            self.set_pixel(point.x, point.y, color.is_on());
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.fill(color.is_on());
+        Ok(())
+    }
+}
@@ -0,0 +1,310 @@
+//! Monochrome drawing on the LCD of the EV3 brick.
+
+mod font;
+pub use self::font::Font;
+
+#[cfg(feature = "embedded-graphics")]
+mod draw_target;
+
+use framebuffer::Framebuffer;
+
+use crate::{Ev3Error, Ev3Result};
+
+/// The LCD of the EV3 brick (178x128 pixels, black and white).
+///
+/// All drawing operations go to an off-screen buffer.
+/// Nothing is shown on the screen until `update()` copies the buffer to the framebuffer,
+/// so a frame can be composed without flickering.
+///
+/// Coordinates start at the top left corner. Pixels outside of the screen are ignored.
+/// The `black` parameter selects if a black or a white pixel is drawn.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::{Display, Font};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let mut display = Display::new()?;
+///
+/// display.clear();
+/// display.draw_text(4, 4, "Hello EV3!", &Font::LARGE, true);
+/// display.draw_rectangle(0, 0, display.width(), display.height(), true);
+/// display.fill_circle(89, 80, 20, true);
+/// display.update();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Display {
+    framebuffer: Framebuffer,
+    width: u32,
+    height: u32,
+    bits_per_pixel: u32,
+    line_length: usize,
+    /// Off-screen buffer, `true` is a black pixel.
+    pixels: Vec<bool>,
+    /// Reused buffer for the converted framebuffer content.
+    frame: Vec<u8>,
+}
+
+impl Display {
+    /// Opens the framebuffer `/dev/fb0`.
+    pub fn new() -> Ev3Result<Self> {
+        Display::open("/dev/fb0")
+    }
+
+    /// Opens the framebuffer at the given `path`.
+    ///
+    /// Supports framebuffers with 1 (EV3), 16 or 32 bits per pixel.
+    pub fn open(path: &str) -> Ev3Result<Self> {
+        let framebuffer = Framebuffer::new(path)?;
+
+        let width = framebuffer.var_screen_info.xres;
+        let height = framebuffer.var_screen_info.yres;
+        let bits_per_pixel = framebuffer.var_screen_info.bits_per_pixel;
+        let line_length = framebuffer.fix_screen_info.line_length as usize;
+
+        if ![1, 16, 32].contains(&bits_per_pixel) {
+            return Err(Ev3Error::InternalError {
+                msg: format!("Framebuffer with {bits_per_pixel} bits per pixel is not supported"),
+                path: None,
+            });
+        }
+
+        Ok(Display {
+            framebuffer,
+            width,
+            height,
+            bits_per_pixel,
+            line_length,
+            pixels: vec![false; (width * height) as usize],
+            frame: vec![0; line_length * height as usize],
+        })
+    }
+
+    /// Horizontal screen resolution.
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Vertical screen resolution.
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Sets all pixels to white.
+    pub fn clear(&mut self) {
+        self.fill(false);
+    }
+
+    /// Sets all pixels to the given color.
+    pub fn fill(&mut self, black: bool) {
+        self.pixels.fill(black);
+    }
+
+    /// Returns the index of the pixel in the off-screen buffer or `None` if it is outside of the screen.
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x as u32 >= self.width || y as u32 >= self.height {
+            None
+        } else {
+            Some(y as usize * self.width as usize + x as usize)
+        }
+    }
+
+    /// Sets a single pixel.
+    pub fn set_pixel(&mut self, x: i32, y: i32, black: bool) {
+        if let Some(index) = self.index(x, y) {
+            self.pixels[index] = black;
+        }
+    }
+
+    /// Checks if the pixel is black. Pixels outside of the screen are white.
+    pub fn get_pixel(&self, x: i32, y: i32) -> bool {
+        self.index(x, y).is_some_and(|index| self.pixels[index])
+    }
+
+    /// Inverts the color of a single pixel.
+    pub fn invert_pixel(&mut self, x: i32, y: i32) {
+        if let Some(index) = self.index(x, y) {
+            self.pixels[index] = !self.pixels[index];
+        }
+    }
+
+    /// Draws a horizontal line from `x0` to `x1` (inclusive).
+    fn draw_span(&mut self, x0: i32, x1: i32, y: i32, black: bool) {
+        for x in x0.min(x1)..=x0.max(x1) {
+            self.set_pixel(x, y, black);
+        }
+    }
+
+    /// Draws a line from `(x0, y0)` to `(x1, y1)` (inclusive).
+    pub fn draw_line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, black: bool) {
+        // Bresenham's line algorithm for all octants.
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+
+        let (mut x, mut y) = (x0, y0);
+        let mut error = dx + dy;
+        loop {
+            self.set_pixel(x, y, black);
+            if x == x1 && y == y1 {
+                break;
+            }
+
+            let e2 = 2 * error;
+            if e2 >= dy {
+                error += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    /// Draws the outline of a rectangle with the top left corner at `(x, y)`.
+    pub fn draw_rectangle(&mut self, x: i32, y: i32, width: u32, height: u32, black: bool) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let x1 = x + width as i32 - 1;
+        let y1 = y + height as i32 - 1;
+
+        self.draw_span(x, x1, y, black);
+        self.draw_span(x, x1, y1, black);
+        for row in y..=y1 {
+            self.set_pixel(x, row, black);
+            self.set_pixel(x1, row, black);
+        }
+    }
+
+    /// Fills a rectangle with the top left corner at `(x, y)`.
+    pub fn fill_rectangle(&mut self, x: i32, y: i32, width: u32, height: u32, black: bool) {
+        for row in y..y + height as i32 {
+            for column in x..x + width as i32 {
+                self.set_pixel(column, row, black);
+            }
+        }
+    }
+
+    /// Inverts all pixels of a rectangle with the top left corner at `(x, y)`,
+    /// e.g. to highlight a line of text.
+    pub fn invert_rectangle(&mut self, x: i32, y: i32, width: u32, height: u32) {
+        for row in y..y + height as i32 {
+            for column in x..x + width as i32 {
+                self.invert_pixel(column, row);
+            }
+        }
+    }
+
+    /// Calls `f` with the offsets of the octant points of a circle with the given `radius`.
+    fn for_each_circle_point(radius: u32, mut f: impl FnMut(i32, i32)) {
+        // Midpoint circle algorithm.
+        let mut x = radius as i32;
+        let mut y = 0;
+        let mut error = 1 - x;
+
+        while x >= y {
+            f(x, y);
+            y += 1;
+            if error < 0 {
+                error += 2 * y + 1;
+            } else {
+                x -= 1;
+                error += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    /// Draws the outline of a circle around `(x, y)`.
+    pub fn draw_circle(&mut self, x: i32, y: i32, radius: u32, black: bool) {
+        Display::for_each_circle_point(radius, |dx, dy| {
+            for (px, py) in [(dx, dy), (dy, dx)] {
+                self.set_pixel(x + px, y + py, black);
+                self.set_pixel(x - px, y + py, black);
+                self.set_pixel(x + px, y - py, black);
+                self.set_pixel(x - px, y - py, black);
+            }
+        });
+    }
+
+    /// Fills a circle around `(x, y)`.
+    pub fn fill_circle(&mut self, x: i32, y: i32, radius: u32, black: bool) {
+        Display::for_each_circle_point(radius, |dx, dy| {
+            self.draw_span(x - dx, x + dx, y + dy, black);
+            self.draw_span(x - dx, x + dx, y - dy, black);
+            self.draw_span(x - dy, x + dy, y + dx, black);
+            self.draw_span(x - dy, x + dy, y - dx, black);
+        });
+    }
+
+    /// Draws `text` with the top left corner at `(x, y)`. Only the pixels of the glyphs are drawn.
+    ///
+    /// Line breaks start a new line at `x`. Returns the x coordinate after the last character.
+    pub fn draw_text(&mut self, x: i32, y: i32, text: &str, font: &Font, black: bool) -> i32 {
+        let scale = font.scale() as i32;
+        let (mut cursor_x, mut cursor_y) = (x, y);
+
+        for c in text.chars() {
+            if c == '\n' {
+                cursor_x = x;
+                cursor_y += font.line_height() as i32;
+                continue;
+            }
+
+            for (column, bits) in Font::glyph(c).iter().enumerate() {
+                for row in 0..font::GLYPH_HEIGHT as i32 {
+                    if bits & (1 << row) != 0 {
+                        self.fill_rectangle(
+                            cursor_x + column as i32 * scale,
+                            cursor_y + row * scale,
+                            scale as u32,
+                            scale as u32,
+                            black,
+                        );
+                    }
+                }
+            }
+            cursor_x += font.char_width() as i32;
+        }
+
+        cursor_x
+    }
+
+    /// Applies pending changes to the screen.
+    /// Nothing will be drawn on the screen until this function is called.
+    pub fn update(&mut self) {
+        let width = self.width as usize;
+        let frame = &mut self.frame;
+        frame.fill(0);
+
+        for (row, pixels) in frame
+            .chunks_exact_mut(self.line_length)
+            .zip(self.pixels.chunks_exact(width))
+        {
+            for (x, &black) in pixels.iter().enumerate() {
+                match self.bits_per_pixel {
+                    // The EV3 uses one bit per pixel, least significant bit first and `1` as black.
+                    1 => {
+                        if black {
+                            row[x / 8] |= 1 << (x % 8);
+                        }
+                    }
+                    16 => {
+                        let color: u16 = if black { 0x0000 } else { 0xffff };
+                        row[2 * x..2 * x + 2].copy_from_slice(&color.to_ne_bytes());
+                    }
+                    _ => {
+                        let color: u32 = if black { 0x0000_0000 } else { 0x00ff_ffff };
+                        row[4 * x..4 * x + 4].copy_from_slice(&color.to_ne_bytes());
+                    }
+                }
+            }
+        }
+
+        self.framebuffer.write_frame(frame);
+    }
+}
@@ -1,15 +1,15 @@
 //! Helper struct that manages attributes.
 //! It creates an `Attribute` instance if it does not exists or uses a cached one.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::fmt::{self, Debug};
 use std::fs;
 use std::path::Path;
 use std::string::String;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, RwLock, Weak};
 
-use crate::utils::OrErr;
-use crate::{Attribute, Ev3Error, Ev3Result, Port};
+use crate::attribute::Reopen;
+use crate::{Attribute, Backend, Ev3Error, Ev3Result, Port, Sysfs};
 
 /// The driver path `/sys/class/`.
 #[cfg(not(feature = "override-driver-path"))]
@@ -29,26 +29,249 @@ const fn get_driver_path() -> &'static str {
     }
 }
 
+/// Returns the entries of the directory `/sys/class/{class_name}`.
+pub(crate) fn read_class_dir(class_name: &str) -> Ev3Result<fs::ReadDir> {
+    let path = Path::new(DRIVER_PATH).join(class_name);
+    fs::read_dir(&path).map_err(Ev3Error::io(&path))
+}
+
+/// Strategy of the attribute cache of a `Driver`.
+///
+/// Opened attributes keep a file handle, so a program that touches many attributes of many devices
+/// can limit the number of cached attributes or disable the cache.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum CacheStrategy {
+    /// Keep every opened attribute (default).
+    #[default]
+    Unbounded,
+    /// Keep at most this many attributes per device. The attribute that was opened first is dropped first.
+    Capacity(usize),
+    /// Open the attribute on every access.
+    Disabled,
+}
+
+/// Strategy of drivers that are created after `Driver::set_default_cache_strategy()`.
+static DEFAULT_CACHE_STRATEGY: RwLock<CacheStrategy> = RwLock::new(CacheStrategy::Unbounded);
+
+/// Opened attributes of a driver in the order they were opened.
+#[derive(Debug, Default)]
+struct AttributeCache {
+    strategy: CacheStrategy,
+    attributes: HashMap<String, Attribute>,
+    order: VecDeque<String>,
+}
+
+impl AttributeCache {
+    fn new(strategy: CacheStrategy) -> Self {
+        Self {
+            strategy,
+            ..Default::default()
+        }
+    }
+
+    fn insert(&mut self, name: &str, attribute: Attribute) {
+        if self.strategy == CacheStrategy::Disabled {
+            return;
+        }
+        if self.attributes.insert(name.to_owned(), attribute).is_none() {
+            self.order.push_back(name.to_owned());
+        }
+        self.trim();
+    }
+
+    fn remove(&mut self, name: &str) -> bool {
+        self.order.retain(|entry| entry != name);
+        self.attributes.remove(name).is_some()
+    }
+
+    fn clear(&mut self) {
+        self.attributes.clear();
+        self.order.clear();
+    }
+
+    fn set_strategy(&mut self, strategy: CacheStrategy) {
+        self.strategy = strategy;
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        let capacity = match self.strategy {
+            CacheStrategy::Unbounded => return,
+            CacheStrategy::Capacity(capacity) => capacity,
+            CacheStrategy::Disabled => 0,
+        };
+        while self.attributes.len() > capacity {
+            match self.order.pop_front() {
+                Some(name) => {
+                    self.attributes.remove(&name);
+                }
+                None => break,
+            }
+        }
+    }
+}
+
+/// Information to find a device again after it was reconnected.
+#[derive(Debug)]
+struct Locator {
+    address: Option<String>,
+    driver_names: Vec<String>,
+}
+
+/// Port that matches a previously read device address.
+struct AddressPort(String);
+
+impl Port for AddressPort {
+    fn address(&self) -> String {
+        self.0.clone()
+    }
+}
+
+/// Reference to a `Driver` that does not keep its attribute cache alive.
+/// Held by the cached attributes of a reconnectable driver to reopen them.
+struct WeakDriver {
+    backend: Arc<dyn Backend>,
+    class_name: String,
+    name: Arc<RwLock<String>>,
+    locator: Option<Arc<Locator>>,
+    attributes: Weak<RwLock<AttributeCache>>,
+}
+
+impl WeakDriver {
+    fn upgrade(&self) -> Option<Driver> {
+        Some(Driver {
+            backend: self.backend.clone(),
+            class_name: self.class_name.clone(),
+            name: self.name.clone(),
+            locator: self.locator.clone(),
+            attributes: self.attributes.upgrade()?,
+        })
+    }
+}
+
 /// Helper struct that manages attributes.
 /// It creates an `Attribute` instance if it does not exists or uses a cached one.
+///
+/// The devices and attributes are provided by a `Backend`, by default the local `/sys/class/` directory.
 #[derive(Clone)]
 pub struct Driver {
+    backend: Arc<dyn Backend>,
     class_name: String,
-    name: String,
-    attributes: Arc<RwLock<HashMap<String, Attribute>>>,
+    name: Arc<RwLock<String>>,
+    locator: Option<Arc<Locator>>,
+    attributes: Arc<RwLock<AttributeCache>>,
 }
 
 impl Driver {
     /// Returns a new `Driver`.
     /// All attributes created by this driver will use the path `/sys/class/{class_name}/{name}`.
     pub fn new(class_name: &str, name: &str) -> Driver {
+        Driver::from_backend(Arc::new(Sysfs), class_name, name)
+    }
+
+    /// Returns a new `Driver` for the device `class_name/name` of the given `backend`.
+    pub fn from_backend(backend: Arc<dyn Backend>, class_name: &str, name: &str) -> Driver {
         Driver {
+            backend,
             class_name: class_name.to_owned(),
-            name: name.to_owned(),
-            attributes: Arc::new(RwLock::new(HashMap::new())),
+            name: Arc::new(RwLock::new(name.to_owned())),
+            locator: None,
+            attributes: Arc::new(RwLock::new(AttributeCache::new(
+                *DEFAULT_CACHE_STRATEGY.read().unwrap(),
+            ))),
         }
     }
 
+    /// Returns a new `Driver` that can be reconnected.
+    ///
+    /// Unplugging and re-plugging a device changes its name in `/sys/class/{class_name}/`.
+    /// The driver remembers the current address of the device and the accepted `driver_names`
+    /// to find the device again. An empty `driver_names` list accepts any driver.
+    pub fn reconnectable(class_name: &str, name: &str, driver_names: &[&str]) -> Driver {
+        Driver::reconnectable_from(Arc::new(Sysfs), class_name, name, driver_names)
+    }
+
+    /// Returns a new `Driver` of the given `backend` that can be reconnected. See `Driver::reconnectable()`.
+    pub fn reconnectable_from(
+        backend: Arc<dyn Backend>,
+        class_name: &str,
+        name: &str,
+        driver_names: &[&str],
+    ) -> Driver {
+        let address = backend
+            .open_attribute(class_name, name, "address")
+            .and_then(|attribute| attribute.get::<String>())
+            .ok();
+
+        Driver {
+            locator: Some(Arc::new(Locator {
+                address,
+                driver_names: driver_names.iter().map(|n| n.to_string()).collect(),
+            })),
+            ..Driver::from_backend(backend, class_name, name)
+        }
+    }
+
+    /// Returns the backend that provides the device.
+    pub fn get_backend(&self) -> Arc<dyn Backend> {
+        self.backend.clone()
+    }
+
+    /// Returns the current name of the device, e.g. `sensor0`.
+    pub fn get_name(&self) -> String {
+        self.name.read().unwrap().clone()
+    }
+
+    /// Checks if the device directory `/sys/class/{class_name}/{name}` still exists.
+    pub fn is_connected(&self) -> bool {
+        self.backend
+            .is_connected(&self.class_name, &self.name.read().unwrap())
+    }
+
+    /// Finds the device again after it was unplugged and re-plugged and drops all cached attributes.
+    ///
+    /// Only works for drivers created with `Driver::reconnectable()`.
+    /// Returns `Ev3Error::NotConnected` if the device is not connected (yet).
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "info", skip(self), fields(class_name = %self.class_name), err)
+    )]
+    pub fn reconnect(&self) -> Ev3Result<()> {
+        let locator = self
+            .locator
+            .as_ref()
+            .ok_or_else(|| Ev3Error::InternalError {
+                msg: format!("{self:?} does not support reconnecting"),
+                path: None,
+            })?;
+        let driver_names = locator
+            .driver_names
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+
+        let backend = &*self.backend;
+        let name = match &locator.address {
+            Some(address) if driver_names.is_empty() => Driver::find_name_by_port_from(
+                backend,
+                &self.class_name,
+                &AddressPort(address.clone()),
+            )?,
+            Some(address) => Driver::find_name_by_port_and_driver_from(
+                backend,
+                &self.class_name,
+                &AddressPort(address.clone()),
+                &driver_names,
+            )?,
+            None => Driver::find_name_by_driver_from(backend, &self.class_name, &driver_names)?,
+        };
+
+        *self.name.write().unwrap() = name;
+        self.clear_cache();
+
+        Ok(())
+    }
+
     /// Returns the name of the device with the given `class_name`, `driver_name` and at the given `port`.
     ///
     /// Returns `Ev3Error::NotFound` if no such device exists.
@@ -57,21 +280,30 @@ impl Driver {
         port: &dyn Port,
         driver_name_vec: &[&str],
     ) -> Ev3Result<String> {
-        let port_address = port.address();
-
-        let paths = fs::read_dir(Path::new(DRIVER_PATH).join(class_name))?;
+        Driver::find_name_by_port_and_driver_from(&Sysfs, class_name, port, driver_name_vec)
+    }
 
-        for path in paths {
-            let file_name = path?.file_name();
-            let name = file_name.to_str().or_err()?;
+    /// Returns the name of the device of the given `backend`. See `Driver::find_name_by_port_and_driver()`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(backend, port), fields(port = %port.address()), ret, err)
+    )]
+    pub fn find_name_by_port_and_driver_from(
+        backend: &dyn Backend,
+        class_name: &str,
+        port: &dyn Port,
+        driver_name_vec: &[&str],
+    ) -> Ev3Result<String> {
+        let port_address = port.address();
 
-            let address = Attribute::from_sys_class(class_name, name, "address")?;
+        for name in backend.find_names(class_name)? {
+            let address = backend.open_attribute(class_name, &name, "address")?;
 
-            if address.get::<String>()?.contains(&port_address) {
-                let driver = Attribute::from_sys_class(class_name, name, "driver_name")?;
+            if port.matches_address(&address.get::<String>()?) {
+                let driver = backend.open_attribute(class_name, &name, "driver_name")?;
                 let driver_name = driver.get::<String>()?;
                 if driver_name_vec.iter().any(|n| &driver_name == n) {
-                    return Ok(name.to_owned());
+                    return Ok(name);
                 }
             }
         }
@@ -79,20 +311,74 @@ impl Driver {
         Err(Ev3Error::NotConnected {
             device: format!("{driver_name_vec:?}"),
             port: Some(port_address),
+            path: Some(Path::new(DRIVER_PATH).join(class_name)),
         })
     }
 
+    /// Returns the name of the device with the given `class_name` at the given `port`, regardless of its driver.
+    ///
+    /// Returns `Ev3Error::NotFound` if no such device exists.
+    pub fn find_name_by_port(class_name: &str, port: &dyn Port) -> Ev3Result<String> {
+        Driver::find_name_by_port_from(&Sysfs, class_name, port)
+    }
+
+    /// Returns the name of the device of the given `backend`. See `Driver::find_name_by_port()`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(backend, port), fields(port = %port.address()), ret, err)
+    )]
+    pub fn find_name_by_port_from(
+        backend: &dyn Backend,
+        class_name: &str,
+        port: &dyn Port,
+    ) -> Ev3Result<String> {
+        let port_address = port.address();
+
+        for name in backend.find_names(class_name)? {
+            let address = backend.open_attribute(class_name, &name, "address")?;
+
+            if port.matches_address(&address.get::<String>()?) {
+                return Ok(name);
+            }
+        }
+
+        Err(Ev3Error::NotConnected {
+            device: class_name.to_owned(),
+            port: Some(port_address),
+            path: Some(Path::new(DRIVER_PATH).join(class_name)),
+        })
+    }
+
+    /// Returns the names of all devices with the given `class_name`.
+    pub fn find_names(class_name: &str) -> Ev3Result<Vec<String>> {
+        Sysfs.find_names(class_name)
+    }
+
     /// Returns the name of the device with the given `class_name`.
     ///
     /// Returns `Ev3Error::NotFound` if no such device exists.
     /// Returns `Ev3Error::MultipleMatches` if more then one matching device exists.
     pub fn find_name_by_driver(class_name: &str, driver_name_vec: &[&str]) -> Ev3Result<String> {
-        let mut names = Driver::find_names_by_driver(class_name, driver_name_vec)?;
+        Driver::find_name_by_driver_from(&Sysfs, class_name, driver_name_vec)
+    }
+
+    /// Returns the name of the device of the given `backend`. See `Driver::find_name_by_driver()`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(backend), ret, err)
+    )]
+    pub fn find_name_by_driver_from(
+        backend: &dyn Backend,
+        class_name: &str,
+        driver_name_vec: &[&str],
+    ) -> Ev3Result<String> {
+        let mut names = Driver::find_names_by_driver_from(backend, class_name, driver_name_vec)?;
 
         match names.len() {
             0 => Err(Ev3Error::NotConnected {
                 device: format!("{driver_name_vec:?}"),
                 port: None,
+                path: Some(Path::new(DRIVER_PATH).join(class_name)),
             }),
             1 => Ok(names
                 .pop()
@@ -100,6 +386,7 @@ impl Driver {
             _ => Err(Ev3Error::MultipleMatches {
                 device: format!("{driver_name_vec:?}"),
                 ports: names,
+                path: Some(Path::new(DRIVER_PATH).join(class_name)),
             }),
         }
     }
@@ -109,18 +396,26 @@ impl Driver {
         class_name: &str,
         driver_name_vec: &[&str],
     ) -> Ev3Result<Vec<String>> {
-        let paths = fs::read_dir(Path::new(DRIVER_PATH).join(class_name))?;
+        Driver::find_names_by_driver_from(&Sysfs, class_name, driver_name_vec)
+    }
 
+    /// Returns the names of the devices of the given `backend`. See `Driver::find_names_by_driver()`.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(backend), ret, err)
+    )]
+    pub fn find_names_by_driver_from(
+        backend: &dyn Backend,
+        class_name: &str,
+        driver_name_vec: &[&str],
+    ) -> Ev3Result<Vec<String>> {
         let mut found_names = Vec::new();
-        for path in paths {
-            let file_name = path?.file_name();
-            let name = file_name.to_str().or_err()?;
-
-            let driver = Attribute::from_sys_class(class_name, name, "driver_name")?;
+        for name in backend.find_names(class_name)? {
+            let driver = backend.open_attribute(class_name, &name, "driver_name")?;
 
             let driver_name = driver.get::<String>()?;
             if driver_name_vec.iter().any(|n| &driver_name == n) {
-                found_names.push(name.to_owned());
+                found_names.push(name);
             }
         }
 
@@ -129,30 +424,110 @@ impl Driver {
 
     /// Return the `Attribute` wrapper for the given `attribute_name`.
     /// Creates a new one if it does not exist.
-    pub fn get_attribute(&self, attribute_name: &str) -> Attribute {
+    ///
+    /// If the attribute cannot be opened because the device was re-plugged,
+    /// a reconnectable driver tries to find the device again.
+    /// The attributes of a reconnectable driver do the same if a read or write fails with `ENODEV` or `ENOENT`
+    /// and retry the access once. The new handle replaces the stale one in all clones of the attribute,
+    /// so already cached attributes keep working after re-plugging the device.
+    /// Returns an error with the path of the attribute if it cannot be opened.
+    pub fn get_attribute(&self, attribute_name: &str) -> Ev3Result<Attribute> {
         let outer = self.attributes.clone();
         let attributes = outer.read().unwrap();
 
-        if let Some(attr) = attributes.get(attribute_name) {
-            attr.clone()
+        if let Some(attr) = attributes.attributes.get(attribute_name) {
+            Ok(attr.clone())
         } else {
             drop(attributes);
 
-            if let Ok(attribute) = Attribute::from_sys_class(
-                self.class_name.as_ref(),
-                self.name.as_ref(),
-                attribute_name,
-            ) {
-                let mut guard = outer.write().unwrap();
-                guard.insert(attribute_name.to_owned(), attribute.clone());
-
-                attribute
-            } else {
-                panic!("Error while accessing {}, {}, {} Internal error in the attribute map", &self.class_name, &self.name,
-                attribute_name);
+            let open = || {
+                self.backend
+                    .open_attribute(&self.class_name, &self.get_name(), attribute_name)
+            };
+            let attribute = open().or_else(|e| {
+                if self.locator.is_some() && !self.is_connected() && self.reconnect().is_ok() {
+                    open()
+                } else {
+                    Err(e)
+                }
+            });
+
+            let mut attribute = attribute?;
+            if self.locator.is_some() {
+                attribute = attribute.with_reopen(self.reopen(attribute_name));
             }
+
+            outer
+                .write()
+                .unwrap()
+                .insert(attribute_name, attribute.clone());
+
+            Ok(attribute)
         }
     }
+
+    /// Returns a function that reconnects the driver if necessary and opens the attribute `attribute_name` again.
+    fn reopen(&self, attribute_name: &str) -> Reopen {
+        let driver = WeakDriver {
+            backend: self.backend.clone(),
+            class_name: self.class_name.clone(),
+            name: self.name.clone(),
+            locator: self.locator.clone(),
+            attributes: Arc::downgrade(&self.attributes),
+        };
+        let attribute_name = attribute_name.to_owned();
+
+        Arc::new(move || {
+            let driver = driver.upgrade().ok_or_else(|| Ev3Error::InternalError {
+                msg: "Driver was dropped".to_owned(),
+                path: None,
+            })?;
+            // A device that is still connected under its name only needs a new handle.
+            if !driver.is_connected() {
+                driver.reconnect()?;
+            }
+            driver
+                .backend
+                .open_attribute(&driver.class_name, &driver.get_name(), &attribute_name)
+        })
+    }
+
+    /// Sets the cache strategy of drivers that are created afterwards. Existing drivers keep their strategy.
+    pub fn set_default_cache_strategy(strategy: CacheStrategy) {
+        *DEFAULT_CACHE_STRATEGY.write().unwrap() = strategy;
+    }
+
+    /// Returns the cache strategy of drivers that are created now.
+    pub fn get_default_cache_strategy() -> CacheStrategy {
+        *DEFAULT_CACHE_STRATEGY.read().unwrap()
+    }
+
+    /// Sets the cache strategy of this driver and all its clones.
+    /// Cached attributes that exceed the new capacity are dropped.
+    pub fn set_cache_strategy(&self, strategy: CacheStrategy) {
+        self.attributes.write().unwrap().set_strategy(strategy);
+    }
+
+    /// Returns the cache strategy of this driver.
+    pub fn get_cache_strategy(&self) -> CacheStrategy {
+        self.attributes.read().unwrap().strategy
+    }
+
+    /// Returns the number of cached attributes.
+    pub fn cache_len(&self) -> usize {
+        self.attributes.read().unwrap().attributes.len()
+    }
+
+    /// Drops the cached attribute `attribute_name`, so the next access opens it again.
+    /// Returns `true` if the attribute was cached.
+    pub fn evict(&self, attribute_name: &str) -> bool {
+        self.attributes.write().unwrap().remove(attribute_name)
+    }
+
+    /// Drops all cached attributes, so the next accesses open them again.
+    pub fn clear_cache(&self) {
+        self.attributes.write().unwrap().clear();
+    }
 }
 
 impl Debug for Driver {
@@ -160,7 +535,8 @@ impl Debug for Driver {
         write!(
             f,
             "Driver {{ class_name: {}, name: {} }}",
-            self.class_name, self.name
+            self.class_name,
+            self.get_name()
         )
     }
 }
@@ -1,21 +1,24 @@
 //! EV3 specific features
 
-use std::fs;
-
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
 use std::fs::File;
+use std::io::Read;
+use std::mem;
 use std::os::unix::io::AsRawFd;
 use std::path::Path;
 use std::rc::Rc;
+use std::slice;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 use paste::paste;
 
-use crate::driver::DRIVER_PATH;
-use crate::utils::OrErr;
-use crate::{Attribute, Ev3Result};
+use crate::led::{blink_lit, find_led, set_trigger_all};
+use crate::task::CancelToken;
+use crate::{Driver, Ev3Error, Ev3Result, LedChannel, Trigger};
 
 /// Color type.
 pub type Color = (u8, u8);
@@ -23,10 +26,10 @@ pub type Color = (u8, u8);
 /// The led's on top of the EV3 brick.
 #[derive(Debug, Clone)]
 pub struct Led {
-    left_red: Attribute,
-    left_green: Attribute,
-    right_red: Attribute,
-    right_green: Attribute,
+    left_red: LedChannel,
+    left_green: LedChannel,
+    right_red: LedChannel,
+    right_green: LedChannel,
 }
 
 impl Led {
@@ -49,87 +52,89 @@ impl Led {
     pub const COLOR_YELLOW: Color = (25, 255);
 
     /// Create a new instance of the `Led` struct.
+    ///
+    /// Supports the led names of ev3dev-stretch (`led0:red:brick-status`)
+    /// and ev3dev-jessie (`ev3:left:red:ev3dev`).
     pub fn new() -> Ev3Result<Led> {
-        let mut left_red_name = String::new();
-        let mut left_green_name = String::new();
-        let mut right_red_name = String::new();
-        let mut right_green_name = String::new();
-
-        let paths = fs::read_dir(Path::new(DRIVER_PATH).join("leds"))?;
-
-        for path in paths {
-            let file_name = path?.file_name();
-            let name = file_name.to_str().or_err()?.to_owned();
-
-            if name.contains(":brick-status") || name.contains(":ev3dev") {
-                if name.contains("led0:") || name.contains("left:") {
-                    if name.contains("red:") {
-                        left_red_name = name;
-                    } else if name.contains("green:") {
-                        left_green_name = name
-                    }
-                } else if name.contains("led1:") || name.contains("right:") {
-                    if name.contains("red:") {
-                        right_red_name = name
-                    } else if name.contains("green:") {
-                        right_green_name = name
-                    }
-                }
-            }
-        }
-
-        let left_red = Attribute::from_sys_class("leds", left_red_name.as_str(), "brightness")?;
-        let left_green = Attribute::from_sys_class("leds", left_green_name.as_str(), "brightness")?;
-        let right_red = Attribute::from_sys_class("leds", right_red_name.as_str(), "brightness")?;
-        let right_green =
-            Attribute::from_sys_class("leds", right_green_name.as_str(), "brightness")?;
+        let names = Driver::find_names("leds")?;
+        let find = |led: &str, side: &str, color: &str| {
+            find_led(
+                &names,
+                &[&[led, color, "brick-status"], &[side, color, "ev3dev"]],
+            )
+        };
 
         Ok(Led {
-            left_red,
-            left_green,
-            right_red,
-            right_green,
+            left_red: find("led0", "left", "red")?,
+            left_green: find("led0", "left", "green")?,
+            right_red: find("led1", "right", "red")?,
+            right_green: find("led1", "right", "green")?,
         })
     }
 
+    /// Returns all leds in `/sys/class/leds/`, including the leds of this struct.
+    pub fn list() -> Ev3Result<Vec<LedChannel>> {
+        LedChannel::list()
+    }
+
+    /// Returns the red part of the left led.
+    pub fn left_red(&self) -> &LedChannel {
+        &self.left_red
+    }
+
+    /// Returns the green part of the left led.
+    pub fn left_green(&self) -> &LedChannel {
+        &self.left_green
+    }
+
+    /// Returns the red part of the right led.
+    pub fn right_red(&self) -> &LedChannel {
+        &self.right_red
+    }
+
+    /// Returns the green part of the right led.
+    pub fn right_green(&self) -> &LedChannel {
+        &self.right_green
+    }
+
     /// Returns the current red value of the left led.
     fn get_left_red(&self) -> Ev3Result<u8> {
-        self.left_red.get()
+        self.left_red.get_brightness()
     }
 
     /// Sets the red value of the left led.
     fn set_left_red(&self, brightness: u8) -> Ev3Result<()> {
-        self.left_red.set(brightness)
+        self.left_red.set_brightness(brightness)
     }
 
     /// Returns the current green value of the left led.
     fn get_left_green(&self) -> Ev3Result<u8> {
-        self.left_green.get()
+        self.left_green.get_brightness()
     }
 
     /// Sets the green value of the left led.
     fn set_left_green(&self, brightness: u8) -> Ev3Result<()> {
-        self.left_green.set(brightness)
+        self.left_green.set_brightness(brightness)
     }
 
     /// Returns the current red value of the right led.
     fn get_right_red(&self) -> Ev3Result<u8> {
-        self.right_red.get()
+        self.right_red.get_brightness()
     }
 
     /// Sets the red value of the right led.
     fn set_right_red(&self, brightness: u8) -> Ev3Result<()> {
-        self.right_red.set(brightness)
+        self.right_red.set_brightness(brightness)
     }
 
     /// Returns the current green value of the right led.
     fn get_right_green(&self) -> Ev3Result<u8> {
-        self.right_green.get()
+        self.right_green.get_brightness()
     }
 
     /// Sets the green value of the right led.
     fn set_right_green(&self, brightness: u8) -> Ev3Result<()> {
-        self.right_green.set(brightness)
+        self.right_green.set_brightness(brightness)
     }
 
     /// Returns the current color value of the left led.
@@ -177,11 +182,228 @@ impl Led {
         self.set_left_color(color)?;
         self.set_right_color(color)
     }
+
+    /// Returns the red and green parts of both leds.
+    fn channels(&self) -> [&LedChannel; 4] {
+        [
+            &self.left_red,
+            &self.left_green,
+            &self.right_red,
+            &self.right_green,
+        ]
+    }
+
+    /// Sets the kernel trigger of both leds.
+    ///
+    /// Use `Trigger::None` to stop blinking.
+    pub fn set_trigger(&self, trigger: &Trigger) -> Ev3Result<()> {
+        set_trigger_all(&self.channels(), trigger)
+    }
+
+    /// Blinks both leds with their current color. The blinking is done by the kernel.
+    ///
+    /// Setting a color to `COLOR_OFF` stops blinking, use `set_trigger(&Trigger::None)` to keep the color.
+    ///
+    /// ```no_run
+    /// use ev3dev_lang_rust::Led;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let led = Led::new()?;
+    ///
+    /// led.set_color(Led::COLOR_AMBER)?;
+    /// led.set_blink(250, 250)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_blink(&self, on_ms: u32, off_ms: u32) -> Ev3Result<()> {
+        blink_lit(&self.channels(), on_ms, off_ms)
+    }
 }
 
 const KEY_BUF_LEN: usize = 96;
 const EVIOCGKEY: u32 = 2_153_792_792;
 
+/// Input event device of the brick buttons.
+const BUTTON_EVENT_FILE: &str = "/dev/input/by-path/platform-gpio_keys-event";
+/// Event type of key presses and releases, see /include/uapi/linux/input-event-codes.h.
+const EV_KEY: u16 = 0x01;
+/// Poll interval of the background listener to check if it should stop.
+const LISTENER_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The buttons on the front of the EV3 brick.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BrickButton {
+    /// Up button
+    Up,
+    /// Down button
+    Down,
+    /// Left button
+    Left,
+    /// Right button
+    Right,
+    /// Center (enter) button
+    Enter,
+    /// Back button on the top left
+    Backspace,
+}
+
+impl BrickButton {
+    /// All buttons of the EV3 brick.
+    pub const ALL: [BrickButton; 6] = [
+        BrickButton::Up,
+        BrickButton::Down,
+        BrickButton::Left,
+        BrickButton::Right,
+        BrickButton::Enter,
+        BrickButton::Backspace,
+    ];
+
+    /// Returns the name that is used by `Button::get_pressed_buttons()`, e.g. `"enter"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BrickButton::Up => "up",
+            BrickButton::Down => "down",
+            BrickButton::Left => "left",
+            BrickButton::Right => "right",
+            BrickButton::Enter => "enter",
+            BrickButton::Backspace => "backspace",
+        }
+    }
+
+    /// Returns the linux key code of the button.
+    pub fn key_code(&self) -> u16 {
+        match self {
+            BrickButton::Up => 103,
+            BrickButton::Down => 108,
+            BrickButton::Left => 105,
+            BrickButton::Right => 106,
+            BrickButton::Enter => 28,
+            BrickButton::Backspace => 14,
+        }
+    }
+
+    /// Returns the button with the given linux key code.
+    fn from_key_code(key_code: u16) -> Option<BrickButton> {
+        BrickButton::ALL
+            .into_iter()
+            .find(|button| button.key_code() == key_code)
+    }
+}
+
+/// Reads the key events of the brick buttons from the input event device.
+struct ButtonEventReader {
+    file: File,
+}
+
+impl ButtonEventReader {
+    /// Opens the input event device.
+    /// Only events that occur after opening the device are reported.
+    fn open() -> Ev3Result<Self> {
+        let file =
+            File::open(BUTTON_EVENT_FILE).map_err(Ev3Error::io(Path::new(BUTTON_EVENT_FILE)))?;
+        Ok(ButtonEventReader { file })
+    }
+
+    /// Waits for the next button press or release.
+    /// Returns the button and `true` if it was pressed, or `None` if the `timeout` expired.
+    fn next(&mut self, timeout: Option<Duration>) -> Ev3Result<Option<(BrickButton, bool)>> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            let remaining = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+            if !self.poll(remaining)? {
+                return Ok(None);
+            }
+
+            let event = self.read_event()?;
+            // Value 2 is an auto repeat of a held key.
+            if event.type_ != EV_KEY || event.value == 2 {
+                continue;
+            }
+            if let Some(button) = BrickButton::from_key_code(event.code) {
+                return Ok(Some((button, event.value == 1)));
+            }
+        }
+    }
+
+    /// Waits until an event can be read. Returns `false` if the `timeout` expired.
+    fn poll(&self, timeout: Option<Duration>) -> Ev3Result<bool> {
+        let mut fds = [libc::pollfd {
+            fd: self.file.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        }];
+        let timeout = timeout.map_or(-1, |t| t.as_millis().min(i32::MAX as u128) as i32);
+
+        loop {
+            let result = unsafe { libc::poll(fds.as_mut_ptr(), 1, timeout) };
+            if result >= 0 {
+                return Ok(result > 0);
+            }
+
+            let err = std::io::Error::last_os_error();
+            if err.kind() != std::io::ErrorKind::Interrupted {
+                return Err(Ev3Error::io(Path::new(BUTTON_EVENT_FILE))(err));
+            }
+        }
+    }
+
+    /// Reads exactly one `input_event`, the kernel never returns partial events.
+    fn read_event(&mut self) -> Ev3Result<libc::input_event> {
+        let mut event: libc::input_event = unsafe { mem::zeroed() };
+        let buffer = unsafe {
+            slice::from_raw_parts_mut(
+                &mut event as *mut libc::input_event as *mut u8,
+                mem::size_of::<libc::input_event>(),
+            )
+        };
+        self.file
+            .read_exact(buffer)
+            .map_err(Ev3Error::io(Path::new(BUTTON_EVENT_FILE)))?;
+
+        Ok(event)
+    }
+}
+
+/// Background thread that calls a handler for every brick button event.
+/// The thread is stopped when the listener is dropped.
+///
+/// Created by `Button::listen()`.
+#[derive(Debug)]
+pub struct ButtonListener {
+    token: CancelToken,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ButtonListener {
+    /// Stops the background thread and waits for it to finish.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    /// Checks if the background thread is still running.
+    /// The thread stops early if the input event device cannot be read anymore.
+    pub fn is_running(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    fn shutdown(&mut self) {
+        self.token.cancel();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for ButtonListener {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
 /// Helper struct for ButtonFileHandler.
 struct FileMapEntry {
     pub file: File,
@@ -247,7 +469,7 @@ impl ButtonFileHandler {
     /// Add a button to the file handler.
     fn add_button(&mut self, name: &str, file_name: &str, key_code: u32) -> Ev3Result<()> {
         if !self.file_map.contains_key(file_name) {
-            let file = File::open(file_name)?;
+            let file = File::open(file_name).map_err(Ev3Error::io(Path::new(file_name)))?;
             let buffer_cache = [0u8; KEY_BUF_LEN];
 
             self.file_map
@@ -376,16 +598,13 @@ impl Button {
     pub fn new() -> Ev3Result<Self> {
         let mut handler = ButtonFileHandler::new();
 
-        handler.add_button("up", "/dev/input/by-path/platform-gpio_keys-event", 103)?;
-        handler.add_button("down", "/dev/input/by-path/platform-gpio_keys-event", 108)?;
-        handler.add_button("left", "/dev/input/by-path/platform-gpio_keys-event", 105)?;
-        handler.add_button("right", "/dev/input/by-path/platform-gpio_keys-event", 106)?;
-        handler.add_button("enter", "/dev/input/by-path/platform-gpio_keys-event", 28)?;
-        handler.add_button(
-            "backspace",
-            "/dev/input/by-path/platform-gpio_keys-event",
-            14,
-        )?;
+        for button in BrickButton::ALL {
+            handler.add_button(
+                button.name(),
+                BUTTON_EVENT_FILE,
+                u32::from(button.key_code()),
+            )?;
+        }
 
         Ok(Self {
             button_handler: Rc::new(RefCell::new(handler)),
@@ -475,6 +694,102 @@ impl Button {
             .set_button_change_handler(None)
     }
 
+    /// Updates the button state like `process()` and checks if the given button is pressed.
+    ///
+    /// ```no_run
+    /// use ev3dev_lang_rust::{BrickButton, Button};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let button = Button::new()?;
+    ///
+    /// if button.is_pressed(BrickButton::Enter) {
+    ///     println!("Enter is pressed");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn is_pressed(&self, button: BrickButton) -> bool {
+        self.process();
+        self.button_handler.borrow().get_button_state(button.name())
+    }
+
+    /// Blocks until a button is pressed and returns it.
+    /// Returns `None` if no button was pressed within the `timeout`.
+    ///
+    /// Buttons that are already held down when this function is called are ignored.
+    ///
+    /// ```no_run
+    /// use ev3dev_lang_rust::Button;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let button = Button::new()?;
+    ///
+    /// match button.wait_for_press(Some(Duration::from_secs(5)))? {
+    ///     Some(pressed) => println!("Pressed: {pressed:?}"),
+    ///     None => println!("No button pressed"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_for_press(&self, timeout: Option<Duration>) -> Ev3Result<Option<BrickButton>> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let mut reader = ButtonEventReader::open()?;
+
+        loop {
+            let remaining = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+            match reader.next(remaining)? {
+                Some((button, true)) => return Ok(Some(button)),
+                Some((_, false)) => continue,
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Starts a background thread that calls `handler` for every button press and release.
+    /// The handler gets the button and `true` if it was pressed or `false` if it was released.
+    ///
+    /// In contrast to the handlers of `process()` no polling is required.
+    /// The thread is stopped when the returned `ButtonListener` is dropped.
+    ///
+    /// ```no_run
+    /// use ev3dev_lang_rust::{BrickButton, Button};
+    /// use std::thread;
+    /// use std::time::Duration;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let listener = Button::listen(|button, is_pressed| {
+    ///     println!("{button:?} is pressed: {is_pressed}");
+    /// })?;
+    ///
+    /// thread::sleep(Duration::from_secs(10));
+    /// listener.stop();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn listen(
+        handler: impl Fn(BrickButton, bool) + Send + 'static,
+    ) -> Ev3Result<ButtonListener> {
+        let mut reader = ButtonEventReader::open()?;
+        let token = CancelToken::new();
+
+        let thread_token = token.clone();
+        let handle = thread::spawn(move || {
+            while !thread_token.is_cancelled() {
+                match reader.next(Some(LISTENER_POLL_INTERVAL)) {
+                    Ok(Some((button, is_pressed))) => handler(button, is_pressed),
+                    Ok(None) => {}
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Ok(ButtonListener {
+            token,
+            handle: Some(handle),
+        })
+    }
+
     ev3_button_functions!(up);
     ev3_button_functions!(down);
     ev3_button_functions!(left);
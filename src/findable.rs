@@ -1,6 +1,7 @@
 /// Helper to create a new `Device` instance.
 ///
-/// Generates `get()`, `find()` and `list()` methods. Therefore are 5 parameters required:
+/// Generates `get()`, `get_by_address()`, `get_by_env()`, `find()` and `list()` methods and their `_from()` variants for other backends,
+/// e.g. a remote brick. Therefore are 5 parameters required:
 /// * `class_name: &str`
 /// * `driver_name: &str`
 /// * `port: dyn ev3dev_lang_rust::Motor`
@@ -25,33 +26,78 @@ macro_rules! findable {
     ($class_name:expr, [$( $driver_name:expr ),*], $port: ty, $debug_name:expr, $port_prefix:expr) => {
         fn map_error(e: Ev3Error) -> Ev3Error {
             match e {
-                e @ Ev3Error::InternalError { .. } => e,
-                Ev3Error::NotConnected { device: _, port } => Ev3Error::NotConnected {
+                Ev3Error::NotConnected { device: _, port, path } => Ev3Error::NotConnected {
                     device: $debug_name.to_owned(),
                     port,
+                    path,
                 },
-                Ev3Error::MultipleMatches { device: _, ports } => Ev3Error::MultipleMatches {
+                Ev3Error::MultipleMatches {
+                    device: _,
+                    ports,
+                    path,
+                } => Ev3Error::MultipleMatches {
                     device: $debug_name.to_owned(),
                     ports: ports
                         .iter()
                         .map(|item| <$port>::format_name(item))
                         .collect(),
+                    path,
                 },
+                e => e,
             }
         }
 
         /// Try to get a `Self` on the given port. Returns `None` if port is not used or another device is connected.
-        #[allow(clippy::vec_init_then_push)]
         pub fn get(port: $port) -> Ev3Result<Self> {
+            Self::get_by_port(&port)
+        }
+
+        /// Try to get a `Self` on the port with the given name, e.g. `"in3"` or `"outA"`.
+        /// Accepts the same formats as `Port::parse()`.
+        pub fn get_by_address(address: &str) -> Ev3Result<Self> {
+            Self::get(<$port as $crate::Port>::parse(address)?)
+        }
+
+        /// Try to get a `Self` on the port named by the environment variable `var`, e.g. `LINE_SENSOR_PORT=in2`,
+        /// or on `default` if the variable is not set. This allows the same binary to run on robots with different wiring.
+        pub fn get_by_env(var: &str, default: $port) -> Ev3Result<Self> {
+            match std::env::var(var) {
+                Ok(address) => {
+                    let port = <$port as $crate::Port>::parse(&address).map_err(|e| match e {
+                        Ev3Error::InvalidPort {
+                            value,
+                            expected,
+                            path,
+                        } => Ev3Error::InvalidPort {
+                            value,
+                            expected: format!("{expected} (from environment variable {var})"),
+                            path,
+                        },
+                        e => e,
+                    })?;
+                    Self::get(port)
+                }
+                Err(std::env::VarError::NotPresent) => Self::get(default),
+                Err(std::env::VarError::NotUnicode(_)) => Err(Ev3Error::InternalError {
+                    msg: format!("Environment variable {var} is not valid unicode"),
+                    path: None,
+                }),
+            }
+        }
+
+        /// Try to get a `Self` on any kind of port, e.g. a sensor multiplexer sub-port.
+        /// Returns `None` if port is not used or another device is connected.
+        #[allow(clippy::vec_init_then_push)]
+        pub fn get_by_port(port: &dyn $crate::Port) -> Ev3Result<Self> {
             let mut driver_name_vec = Vec::new();
             $(
                 driver_name_vec.push($driver_name);
             )*
 
-            let name = Driver::find_name_by_port_and_driver($class_name, &port, &driver_name_vec)
+            let name = Driver::find_name_by_port_and_driver($class_name, port, &driver_name_vec)
                 .map_err(Self::map_error)?;
 
-            Ok(Self::new(Driver::new($class_name, &name)))
+            Ok(Self::new(Driver::reconnectable($class_name, &name, &driver_name_vec)))
         }
 
         /// Try to find a `Self`. Only returns a motor if their is exactly one connected, `Error::NotFound` otherwise.
@@ -65,7 +111,36 @@ macro_rules! findable {
             let name =
                 Driver::find_name_by_driver($class_name, &driver_name_vec).map_err(Self::map_error)?;
 
-            Ok(Self::new(Driver::new($class_name, &name)))
+            Ok(Self::new(Driver::reconnectable($class_name, &name, &driver_name_vec)))
+        }
+
+        /// Create a `Self` from a descriptor returned by `scan()`.
+        /// Returns an error if the descriptor belongs to another type of device.
+        pub fn from_descriptor(descriptor: &$crate::DeviceDescriptor) -> Ev3Result<Self> {
+            Self::from_descriptor_from(&$crate::Sysfs, descriptor)
+        }
+
+        /// Create a `Self` from a descriptor returned by `scan_from()` for the same `backend`.
+        /// Returns an error if the descriptor belongs to another type of device.
+        pub fn from_descriptor_from<B: $crate::Backend + Clone + 'static>(
+            backend: &B,
+            descriptor: &$crate::DeviceDescriptor,
+        ) -> Ev3Result<Self> {
+            let driver_name = descriptor.driver_name.as_deref().unwrap_or_default();
+
+            if descriptor.class != $class_name || ![$( $driver_name ),*].contains(&driver_name) {
+                return Err(Ev3Error::InternalError {
+                    msg: format!("{:?} is not a {}", descriptor, $debug_name),
+                    path: None,
+                });
+            }
+
+            Ok(Self::new(Driver::reconnectable_from(
+                std::sync::Arc::new(backend.clone()),
+                $class_name,
+                &descriptor.name,
+                &[$( $driver_name ),*],
+            )))
         }
 
         /// Extract list of connected 'Self'
@@ -78,7 +153,54 @@ macro_rules! findable {
 
             Ok(Driver::find_names_by_driver($class_name, &driver_name_vec)?
                 .iter()
-                .map(|name| Self::new(Driver::new($class_name, &name)))
+                .map(|name| Self::new(Driver::reconnectable($class_name, name, &driver_name_vec)))
+                .collect())
+        }
+
+        /// Try to get a `Self` of the given `backend` on the given port. See `get()`.
+        pub fn get_from<B: $crate::Backend + Clone + 'static>(backend: &B, port: $port) -> Ev3Result<Self> {
+            let driver_name_vec = [$( $driver_name ),*];
+
+            let name = Driver::find_name_by_port_and_driver_from(backend, $class_name, &port, &driver_name_vec)
+                .map_err(Self::map_error)?;
+
+            Ok(Self::new(Driver::reconnectable_from(
+                std::sync::Arc::new(backend.clone()),
+                $class_name,
+                &name,
+                &driver_name_vec,
+            )))
+        }
+
+        /// Try to find a `Self` of the given `backend`. See `find()`.
+        pub fn find_from<B: $crate::Backend + Clone + 'static>(backend: &B) -> Ev3Result<Self> {
+            let driver_name_vec = [$( $driver_name ),*];
+
+            let name = Driver::find_name_by_driver_from(backend, $class_name, &driver_name_vec)
+                .map_err(Self::map_error)?;
+
+            Ok(Self::new(Driver::reconnectable_from(
+                std::sync::Arc::new(backend.clone()),
+                $class_name,
+                &name,
+                &driver_name_vec,
+            )))
+        }
+
+        /// Extract list of connected 'Self' of the given `backend`. See `list()`.
+        pub fn list_from<B: $crate::Backend + Clone + 'static>(backend: &B) -> Ev3Result<Vec<Self>> {
+            let driver_name_vec = [$( $driver_name ),*];
+
+            Ok(Driver::find_names_by_driver_from(backend, $class_name, &driver_name_vec)?
+                .iter()
+                .map(|name| {
+                    Self::new(Driver::reconnectable_from(
+                        std::sync::Arc::new(backend.clone()),
+                        $class_name,
+                        name,
+                        &driver_name_vec,
+                    ))
+                })
                 .collect())
         }
     };
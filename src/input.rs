@@ -0,0 +1,345 @@
+//! USB and Bluetooth gamepads and joysticks, read from the Linux joystick devices `/dev/input/js*`.
+//!
+//! # Example
+//! ```no_run
+//! use ev3dev_lang_rust::control::Loop;
+//! use ev3dev_lang_rust::input::Gamepad;
+//! use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+//!
+//! # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+//! let mut gamepad = Gamepad::find()?.with_deadzone(0.1);
+//! let left = LargeMotor::get(MotorPort::OutB)?;
+//! let right = LargeMotor::get(MotorPort::OutC)?;
+//!
+//! // Tank steering with the two sticks, the y axes are negative if pushed forward.
+//! Loop::run_at(50.0, |ctx| {
+//!     gamepad.update()?;
+//!     left.set_speed_sp(-gamepad.scaled_axis(1, 900))?;
+//!     right.set_speed_sp(-gamepad.scaled_axis(4, 900))?;
+//!     left.run_forever()?;
+//!     right.run_forever()?;
+//!
+//!     if gamepad.button(0) {
+//!         ctx.stop();
+//!     }
+//!     Ok(())
+//! })?;
+//!
+//! left.stop()?;
+//! right.stop()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::{self, File};
+use std::io::Read;
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::task::CancelToken;
+use crate::{Ev3Error, Ev3Result};
+
+/// Directory of the joystick devices.
+pub const JOYSTICK_DIR: &str = "/dev/input";
+
+/// Maximal time the listener thread blocks before it checks for cancellation.
+const LISTENER_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Size of a `struct js_event`.
+const JS_EVENT_SIZE: usize = 8;
+const JS_EVENT_BUTTON: u8 = 0x01;
+const JS_EVENT_AXIS: u8 = 0x02;
+/// Flag of the synthetic events that report the initial state after opening the device.
+const JS_EVENT_INIT: u8 = 0x80;
+
+/// Event of a gamepad.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamepadEvent {
+    /// A button was pressed or released.
+    Button {
+        /// Number of the button, starting with 0.
+        number: u8,
+        /// `true` if the button was pressed.
+        pressed: bool,
+    },
+    /// An axis was moved.
+    Axis {
+        /// Number of the axis, starting with 0.
+        number: u8,
+        /// Position of the axis from -32767 to 32767.
+        value: i16,
+    },
+}
+
+/// Gamepad or joystick that is read from a Linux joystick device.
+///
+/// The gamepad keeps the current state of all axes and buttons, which is updated by `update()`
+/// and `next_event()`. The numbering of axes and buttons depends on the gamepad,
+/// use `jstest` or `next_event()` to find them.
+#[derive(Debug)]
+pub struct Gamepad {
+    file: File,
+    path: PathBuf,
+    axes: Vec<i16>,
+    buttons: Vec<bool>,
+    deadzone: f32,
+}
+
+impl Gamepad {
+    /// Opens the joystick device at `path`, e.g. `/dev/input/js0`.
+    pub fn open(path: impl AsRef<Path>) -> Ev3Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(Ev3Error::io(path))?;
+
+        Ok(Gamepad {
+            file,
+            path: path.to_owned(),
+            axes: Vec::new(),
+            buttons: Vec::new(),
+            deadzone: 0.0,
+        })
+    }
+
+    /// Opens the first joystick device. Returns `Ev3Error::NotConnected` if no gamepad is connected.
+    pub fn find() -> Ev3Result<Self> {
+        match Self::list_paths()?.first() {
+            Some(path) => Self::open(path),
+            None => Err(Ev3Error::NotConnected {
+                device: "Gamepad".to_owned(),
+                port: None,
+                path: Some(PathBuf::from(JOYSTICK_DIR)),
+            }),
+        }
+    }
+
+    /// Returns the paths of all joystick devices, sorted by number.
+    pub fn list_paths() -> Ev3Result<Vec<PathBuf>> {
+        let dir = Path::new(JOYSTICK_DIR);
+        let mut devices = fs::read_dir(dir)
+            .map_err(Ev3Error::io(dir))?
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name().into_string().ok()?;
+                let number = name.strip_prefix("js")?.parse::<u32>().ok()?;
+                Some((number, dir.join(name)))
+            })
+            .collect::<Vec<_>>();
+        devices.sort();
+
+        Ok(devices.into_iter().map(|(_, path)| path).collect())
+    }
+
+    /// Sets the part of the axis range around the center that is reported as 0 by `axis()`, from 0.0 to 1.0.
+    /// Worn sticks often don't return to the exact center.
+    pub fn with_deadzone(mut self, deadzone: f32) -> Self {
+        self.deadzone = deadzone.clamp(0.0, 0.99);
+        self
+    }
+
+    /// Returns the path of the joystick device.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Waits for the next event and updates the state of the gamepad.
+    /// Returns `None` if the `timeout` expired.
+    pub fn next_event(&mut self, timeout: Option<Duration>) -> Ev3Result<Option<GamepadEvent>> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+
+        loop {
+            let remaining = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+            if !self.poll(remaining)? {
+                return Ok(None);
+            }
+
+            match self.read_event()? {
+                Some(Some(event)) => return Ok(Some(event)),
+                // Unknown event type.
+                Some(None) => continue,
+                // End of file, only reached for regular files.
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Reads all pending events without blocking and updates the state of the gamepad.
+    /// Returns the number of events.
+    pub fn update(&mut self) -> Ev3Result<usize> {
+        let mut count = 0;
+        while self.next_event(Some(Duration::ZERO))?.is_some() {
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Returns the position of the axis `number` from -1.0 to 1.0 with the deadzone applied,
+    /// or 0.0 if the axis does not exist.
+    pub fn axis(&self, number: u8) -> f32 {
+        let value = (self.raw_axis(number) as f32 / i16::MAX as f32).clamp(-1.0, 1.0);
+        if value.abs() <= self.deadzone {
+            return 0.0;
+        }
+        value.signum() * (value.abs() - self.deadzone) / (1.0 - self.deadzone)
+    }
+
+    /// Returns the position of the axis `number` scaled to `-max..=max`, e.g. a motor speed.
+    pub fn scaled_axis(&self, number: u8, max: i32) -> i32 {
+        (self.axis(number) * max as f32).round() as i32
+    }
+
+    /// Returns the raw position of the axis `number` from -32767 to 32767, or 0 if the axis does not exist.
+    pub fn raw_axis(&self, number: u8) -> i16 {
+        self.axes.get(number as usize).copied().unwrap_or(0)
+    }
+
+    /// Checks if the button `number` is pressed.
+    pub fn button(&self, number: u8) -> bool {
+        self.buttons.get(number as usize).copied().unwrap_or(false)
+    }
+
+    /// Starts a background thread that calls `handler` for every event.
+    ///
+    /// The thread stops when the returned `GamepadListener` is dropped or the gamepad is disconnected.
+    ///
+    /// ```no_run
+    /// use ev3dev_lang_rust::input::{Gamepad, GamepadEvent};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let listener = Gamepad::find()?.listen(|event| {
+    ///     if let GamepadEvent::Button { number, pressed: true } = event {
+    ///         println!("Button {number} pressed");
+    ///     }
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn listen(
+        mut self,
+        mut handler: impl FnMut(GamepadEvent) + Send + 'static,
+    ) -> GamepadListener {
+        let token = CancelToken::new();
+
+        let thread_token = token.clone();
+        let handle = thread::spawn(move || {
+            while !thread_token.is_cancelled() {
+                match self.next_event(Some(LISTENER_POLL_INTERVAL)) {
+                    Ok(Some(event)) => handler(event),
+                    Ok(None) => {}
+                    Err(_) => break,
+                }
+            }
+        });
+
+        GamepadListener {
+            token,
+            handle: Some(handle),
+        }
+    }
+
+    /// Waits until an event can be read. Returns `false` if the `timeout` expired.
+    fn poll(&self, timeout: Option<Duration>) -> Ev3Result<bool> {
+        let mut fds = [libc::pollfd {
+            fd: self.file.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        }];
+        let timeout = timeout.map_or(-1, |t| t.as_millis().min(i32::MAX as u128) as i32);
+
+        loop {
+            let result = unsafe { libc::poll(fds.as_mut_ptr(), 1, timeout) };
+            if result >= 0 {
+                return Ok(result > 0);
+            }
+
+            let err = std::io::Error::last_os_error();
+            if err.kind() != std::io::ErrorKind::Interrupted {
+                return Err(Ev3Error::io(&self.path)(err));
+            }
+        }
+    }
+
+    /// Reads one `js_event` and updates the state. Returns `None` at the end of the file
+    /// and `Some(None)` for unknown event types.
+    fn read_event(&mut self) -> Ev3Result<Option<Option<GamepadEvent>>> {
+        let mut buffer = [0u8; JS_EVENT_SIZE];
+        let read = self
+            .file
+            .read(&mut buffer)
+            .map_err(Ev3Error::io(&self.path))?;
+        if read == 0 {
+            return Ok(None);
+        }
+        self.file
+            .read_exact(&mut buffer[read..])
+            .map_err(Ev3Error::io(&self.path))?;
+
+        // struct js_event { __u32 time; __s16 value; __u8 type; __u8 number; }
+        let value = i16::from_ne_bytes([buffer[4], buffer[5]]);
+        let type_ = buffer[6] & !JS_EVENT_INIT;
+        let number = buffer[7];
+
+        let event = match type_ {
+            JS_EVENT_BUTTON => {
+                set_state(&mut self.buttons, number, value != 0);
+                GamepadEvent::Button {
+                    number,
+                    pressed: value != 0,
+                }
+            }
+            JS_EVENT_AXIS => {
+                set_state(&mut self.axes, number, value);
+                GamepadEvent::Axis { number, value }
+            }
+            _ => return Ok(Some(None)),
+        };
+        Ok(Some(Some(event)))
+    }
+}
+
+fn set_state<T: Default + Clone>(states: &mut Vec<T>, number: u8, value: T) {
+    let index = number as usize;
+    if states.len() <= index {
+        states.resize(index + 1, T::default());
+    }
+    states[index] = value;
+}
+
+/// Background thread that calls a handler for every gamepad event.
+/// The thread is stopped when the listener is dropped.
+///
+/// Created by `Gamepad::listen()`.
+#[derive(Debug)]
+pub struct GamepadListener {
+    token: CancelToken,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl GamepadListener {
+    /// Stops the background thread and waits for it to finish.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    /// Checks if the background thread is still running.
+    /// The thread stops early if the gamepad is disconnected.
+    pub fn is_running(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    fn shutdown(&mut self) {
+        self.token.cancel();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for GamepadListener {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
@@ -0,0 +1,226 @@
+//! Access to single leds of the `leds` class.
+
+use std::fmt;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::driver::DRIVER_PATH;
+use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result};
+
+/// Kernel trigger that controls the brightness of a led.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Trigger {
+    /// The brightness is only controlled by the user.
+    None,
+    /// Blink with the `delay_on` and `delay_off` times.
+    Timer,
+    /// Blink in a heartbeat pattern depending on the system load.
+    Heartbeat,
+    /// Any other trigger that is supported by the led, e.g. `default-on`.
+    Other(String),
+}
+
+impl Trigger {
+    /// Returns the name of the trigger as used in the `trigger` attribute.
+    pub fn name(&self) -> &str {
+        match self {
+            Trigger::None => "none",
+            Trigger::Timer => "timer",
+            Trigger::Heartbeat => "heartbeat",
+            Trigger::Other(name) => name,
+        }
+    }
+
+    /// Returns the trigger with the given name.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "none" => Trigger::None,
+            "timer" => Trigger::Timer,
+            "heartbeat" => Trigger::Heartbeat,
+            name => Trigger::Other(name.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for Trigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A single led in `/sys/class/leds/`, e.g. the red part of the left EV3 led.
+///
+/// # Example
+///
+/// ```no_run
+/// use ev3dev_lang_rust::{LedChannel, Trigger};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let led = LedChannel::new("led0:red:brick-status");
+///
+/// led.set_brightness(led.get_max_brightness()?)?;
+/// led.set_blink(100, 900)?;
+///
+/// assert_eq!(led.get_trigger()?, Trigger::Timer);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Device)]
+pub struct LedChannel {
+    driver: Driver,
+}
+
+impl LedChannel {
+    /// Create a new instance for the led `/sys/class/leds/{name}`.
+    pub fn new(name: &str) -> Self {
+        LedChannel {
+            driver: Driver::new("leds", name),
+        }
+    }
+
+    /// Returns all leds in `/sys/class/leds/`.
+    pub fn list() -> Ev3Result<Vec<LedChannel>> {
+        Ok(Driver::find_names("leds")?
+            .iter()
+            .map(|name| LedChannel::new(name))
+            .collect())
+    }
+
+    /// Finds the led whose name contains all `segments` as `:`-separated parts.
+    ///
+    /// For example `["led0", "red"]` matches `led0:red:brick-status`.
+    ///
+    /// Returns `Ev3Error::NotConnected` if no such led exists.
+    pub fn find(segments: &[&str]) -> Ev3Result<LedChannel> {
+        find_led(&Driver::find_names("leds")?, &[segments])
+    }
+
+    /// Returns the name of the led, e.g. `led0:red:brick-status`.
+    pub fn get_name(&self) -> String {
+        self.driver.get_name()
+    }
+
+    /// Returns the maximum allowed brightness value.
+    pub fn get_max_brightness(&self) -> Ev3Result<u8> {
+        self.get_read_only_attribute("max_brightness")?.get()
+    }
+
+    /// Returns the current brightness value.
+    pub fn get_brightness(&self) -> Ev3Result<u8> {
+        self.get_attribute("brightness")?.get()
+    }
+
+    /// Sets the brightness value. Setting the brightness to `0` removes the current trigger.
+    pub fn set_brightness(&self, brightness: u8) -> Ev3Result<()> {
+        self.get_attribute("brightness")?.set(brightness)
+    }
+
+    /// Returns the names of all available triggers.
+    pub fn get_triggers(&self) -> Ev3Result<Vec<String>> {
+        let triggers = self.get_attribute("trigger")?.get_vec()?;
+        Ok(triggers
+            .into_iter()
+            .map(|t| t.trim_matches(|c| c == '[' || c == ']').to_owned())
+            .collect())
+    }
+
+    /// Returns the active trigger.
+    pub fn get_trigger(&self) -> Ev3Result<Trigger> {
+        let triggers = self.get_attribute("trigger")?.get_vec()?;
+        let active = triggers
+            .iter()
+            .find_map(|t| t.strip_prefix('[').and_then(|t| t.strip_suffix(']')))
+            .unwrap_or("none");
+        Ok(Trigger::from_name(active))
+    }
+
+    /// Sets the trigger.
+    pub fn set_trigger(&self, trigger: &Trigger) -> Ev3Result<()> {
+        self.get_attribute("trigger")?.set_str_slice(trigger.name())
+    }
+
+    /// Opens an attribute of the `timer` trigger.
+    ///
+    /// These attributes are created and removed by the kernel when the trigger changes,
+    /// so they are not cached by the driver.
+    fn timer_attribute(&self, name: &str) -> Ev3Result<Attribute> {
+        Attribute::from_sys_class("leds", &self.get_name(), name)
+    }
+
+    /// Returns the on time of the `timer` trigger.
+    pub fn get_delay_on(&self) -> Ev3Result<Duration> {
+        Ok(Duration::from_millis(
+            self.timer_attribute("delay_on")?.get()?,
+        ))
+    }
+
+    /// Sets the on time of the `timer` trigger in milliseconds.
+    pub fn set_delay_on(&self, delay_on: u32) -> Ev3Result<()> {
+        self.timer_attribute("delay_on")?.set(delay_on)
+    }
+
+    /// Returns the off time of the `timer` trigger.
+    pub fn get_delay_off(&self) -> Ev3Result<Duration> {
+        Ok(Duration::from_millis(
+            self.timer_attribute("delay_off")?.get()?,
+        ))
+    }
+
+    /// Sets the off time of the `timer` trigger in milliseconds.
+    pub fn set_delay_off(&self, delay_off: u32) -> Ev3Result<()> {
+        self.timer_attribute("delay_off")?.set(delay_off)
+    }
+
+    /// Blinks the led with the current brightness, or the maximum brightness if it is off.
+    /// The blinking is done by the kernel, no thread is required.
+    pub fn set_blink(&self, on_ms: u32, off_ms: u32) -> Ev3Result<()> {
+        self.set_trigger(&Trigger::Timer)?;
+        self.set_delay_on(on_ms)?;
+        self.set_delay_off(off_ms)
+    }
+}
+
+/// Sets the trigger of all `leds`.
+pub(crate) fn set_trigger_all(leds: &[&LedChannel], trigger: &Trigger) -> Ev3Result<()> {
+    for led in leds {
+        led.set_trigger(trigger)?;
+    }
+    Ok(())
+}
+
+/// Blinks all `leds` that are currently on, so a mixed color keeps its hue.
+pub(crate) fn blink_lit(leds: &[&LedChannel], on_ms: u32, off_ms: u32) -> Ev3Result<()> {
+    for led in leds {
+        if led.get_brightness()? > 0 {
+            led.set_blink(on_ms, off_ms)?;
+        }
+    }
+    Ok(())
+}
+
+/// Finds the first led in `names` that matches one of the `patterns`.
+/// A pattern matches if the name contains all its segments as `:`-separated parts.
+///
+/// Platforms use the patterns to support the led names of different kernel versions,
+/// e.g. `led0:red:brick-status` and `ev3:left:red:ev3dev`.
+pub(crate) fn find_led(names: &[String], patterns: &[&[&str]]) -> Ev3Result<LedChannel> {
+    for pattern in patterns {
+        let found = names.iter().find(|name| {
+            let parts = name.split(':').collect::<Vec<_>>();
+            pattern.iter().all(|segment| parts.contains(segment))
+        });
+        if let Some(name) = found {
+            return Ok(LedChannel::new(name));
+        }
+    }
+
+    Err(Ev3Error::NotConnected {
+        device: format!(
+            "led {}",
+            patterns.first().map_or(String::new(), |p| p.join(":"))
+        ),
+        port: None,
+        path: Some(Path::new(DRIVER_PATH).join("leds")),
+    })
+}
@@ -0,0 +1,209 @@
+//! Led animations that run on a background thread.
+
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::ev3::Color;
+use crate::task::CancelToken;
+use crate::{Ev3Error, Ev3Result, Led};
+
+/// Time between two color updates of an animation.
+const FRAME_DURATION: Duration = Duration::from_millis(40);
+
+/// Pattern for `LedAnimation`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedPattern {
+    /// Fade both leds from one color to another once.
+    Fade {
+        /// Start color
+        from: Color,
+        /// Final color
+        to: Color,
+        /// Duration of the fade
+        duration: Duration,
+    },
+    /// Fade both leds through the colors in a loop.
+    Cycle {
+        /// Colors to fade through, the last color fades back to the first one
+        colors: Vec<Color>,
+        /// Duration of the fade between two colors
+        fade: Duration,
+    },
+    /// Flash the left and the right led alternately.
+    Alternate {
+        /// Color of the left led
+        left: Color,
+        /// Color of the right led
+        right: Color,
+        /// Time until the other led flashes
+        interval: Duration,
+    },
+}
+
+impl LedPattern {
+    /// Flash the left led red and the right led green alternately, like a police car.
+    pub fn police() -> Self {
+        LedPattern::Alternate {
+            left: Led::COLOR_RED,
+            right: Led::COLOR_GREEN,
+            interval: Duration::from_millis(250),
+        }
+    }
+}
+
+/// Returns the color between `from` and `to` at the given `progress` (`0.0` to `1.0`).
+pub fn interpolate_color(from: Color, to: Color, progress: f32) -> Color {
+    let progress = progress.clamp(0.0, 1.0);
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * progress).round() as u8;
+    (mix(from.0, to.0), mix(from.1, to.1))
+}
+
+/// Runs a `LedPattern` on the ev3 leds in a background thread.
+///
+/// The animation is stopped when the `LedAnimation` is dropped. The leds keep their last color.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::{Led, LedAnimation, LedPattern};
+/// use std::thread;
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let led = Led::new()?;
+///
+/// let animation = LedAnimation::start(led.clone(), LedPattern::police());
+/// thread::sleep(Duration::from_secs(5));
+/// animation.stop()?;
+///
+/// led.set_color(Led::COLOR_GREEN)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct LedAnimation {
+    token: CancelToken,
+    handle: Option<JoinHandle<Ev3Result<()>>>,
+}
+
+impl LedAnimation {
+    /// Starts the `pattern` on the given leds.
+    pub fn start(led: Led, pattern: LedPattern) -> Self {
+        let token = CancelToken::new();
+
+        let thread_token = token.clone();
+        let handle = thread::spawn(move || run(&led, &pattern, &thread_token));
+
+        LedAnimation {
+            token,
+            handle: Some(handle),
+        }
+    }
+
+    /// Checks if the animation is still running. A `Fade` stops on its own.
+    pub fn is_running(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// Blocks until the animation is finished.
+    /// Returns the error that stopped the animation, if any.
+    ///
+    /// Only a `Fade` finishes on its own, all other animations run until they are stopped.
+    pub fn wait(mut self) -> Ev3Result<()> {
+        self.join()
+    }
+
+    /// Stops the animation and waits for the thread to finish.
+    /// Returns the error that stopped the animation, if any.
+    pub fn stop(mut self) -> Ev3Result<()> {
+        self.token.cancel();
+        self.join()
+    }
+
+    fn join(&mut self) -> Ev3Result<()> {
+        match self.handle.take() {
+            Some(handle) => handle.join().unwrap_or_else(|_| {
+                Err(Ev3Error::InternalError {
+                    msg: "Led animation thread panicked!".to_owned(),
+                    path: None,
+                })
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for LedAnimation {
+    fn drop(&mut self) {
+        self.token.cancel();
+        let _ = self.join();
+    }
+}
+
+/// Runs the pattern until it is finished or the `token` is cancelled.
+fn run(led: &Led, pattern: &LedPattern, token: &CancelToken) -> Ev3Result<()> {
+    match pattern {
+        LedPattern::Fade { from, to, duration } => {
+            fade(led, *from, *to, *duration, token)?;
+        }
+        LedPattern::Cycle { colors, fade: step } => {
+            if colors.is_empty() {
+                return Ok(());
+            }
+            'cycle: loop {
+                for (i, &from) in colors.iter().enumerate() {
+                    let to = colors[(i + 1) % colors.len()];
+                    if !fade(led, from, to, *step, token)? {
+                        break 'cycle;
+                    }
+                }
+            }
+        }
+        LedPattern::Alternate {
+            left,
+            right,
+            interval,
+        } => loop {
+            led.set_left_color(*left)?;
+            led.set_right_color(Led::COLOR_OFF)?;
+            if !token.sleep(*interval) {
+                break;
+            }
+
+            led.set_left_color(Led::COLOR_OFF)?;
+            led.set_right_color(*right)?;
+            if !token.sleep(*interval) {
+                break;
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Fades both leds from `from` to `to`. Returns `false` if the `token` was cancelled.
+fn fade(
+    led: &Led,
+    from: Color,
+    to: Color,
+    duration: Duration,
+    token: &CancelToken,
+) -> Ev3Result<bool> {
+    let start = Instant::now();
+
+    loop {
+        let progress = if duration.is_zero() {
+            1.0
+        } else {
+            start.elapsed().as_secs_f32() / duration.as_secs_f32()
+        };
+        led.set_color(interpolate_color(from, to, progress))?;
+
+        if progress >= 1.0 {
+            return Ok(true);
+        }
+        if !token.sleep(FRAME_DURATION) {
+            return Ok(false);
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! Access to the `lego-port` class to configure input and output ports.
+
+use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result, Port};
+
+/// An input or output port of the brick, a multiplexer or another port provider.
+///
+/// Most ports detect connected devices automatically. If the detection fails or a device
+/// is not supported by the auto detection, the port mode can be set manually.
+///
+/// # Example
+///
+/// ```no_run
+/// use ev3dev_lang_rust::LegoPort;
+/// use ev3dev_lang_rust::sensors::SensorPort;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let port = LegoPort::get(&SensorPort::In2)?;
+///
+/// // Attach a custom UART device.
+/// port.set_mode(LegoPort::MODE_OTHER_UART)?;
+/// println!("Port status: {}", port.get_status()?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Device)]
+pub struct LegoPort {
+    driver: Driver,
+}
+
+impl LegoPort {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    /// Automatically detect the connected device.
+    pub const MODE_AUTO: &'static str = "auto";
+
+    /// NXT analog sensors.
+    pub const MODE_NXT_ANALOG: &'static str = "nxt-analog";
+
+    /// NXT I2C sensors.
+    pub const MODE_NXT_I2C: &'static str = "nxt-i2c";
+
+    /// Other UART devices that are not LEGO sensors.
+    pub const MODE_OTHER_UART: &'static str = "other-uart";
+
+    /// Raw access to the pins of the port.
+    pub const MODE_RAW: &'static str = "raw";
+
+    /// Try to get the port with the given address.
+    pub fn get(port: &dyn Port) -> Ev3Result<Self> {
+        let name = Driver::find_name_by_port("lego-port", port).map_err(|e| match e {
+            Ev3Error::NotConnected {
+                device: _,
+                port,
+                path,
+            } => Ev3Error::NotConnected {
+                device: "LegoPort".to_owned(),
+                port,
+                path,
+            },
+            e => e,
+        })?;
+
+        Ok(Self::new(Driver::new("lego-port", &name)))
+    }
+
+    /// Extract list of all ports.
+    pub fn list() -> Ev3Result<Vec<Self>> {
+        Ok(Driver::find_names("lego-port")?
+            .iter()
+            .map(|name| Self::new(Driver::new("lego-port", name)))
+            .collect())
+    }
+
+    /// Returns the currently selected mode.
+    pub fn get_mode(&self) -> Ev3Result<String> {
+        self.get_attribute("mode")?.get()
+    }
+
+    /// Sets the mode of the port. The mode has to be one of `get_modes()`.
+    pub fn set_mode(&self, mode: &str) -> Ev3Result<()> {
+        self.get_attribute("mode")?.set_str_slice(mode)
+    }
+
+    /// Returns a list of the available modes of the port.
+    pub fn get_modes(&self) -> Ev3Result<Vec<String>> {
+        self.get_read_only_attribute("modes")?.get_vec()
+    }
+
+    /// Loads the given device driver for the connected device.
+    /// Only works if the port is in a mode without auto detection, e.g. `nxt-analog` or `other-uart`.
+    pub fn set_device(&self, driver_name: &str) -> Ev3Result<()> {
+        self.get_writable_attribute("set_device")?
+            .set_str_slice(driver_name)
+    }
+
+    /// Returns the status of the port, e.g. `no-sensor`, `ev3-uart` or `nxt-i2c`.
+    /// Many modes have a single status equal to the mode name.
+    pub fn get_status(&self) -> Ev3Result<String> {
+        self.get_read_only_attribute("status")?.get()
+    }
+}
@@ -52,9 +52,13 @@ mod findable;
 mod ev3_button_functions;
 
 mod attribute;
-pub use attribute::Attribute;
+pub use attribute::{Attribute, AttributeWatch, ReadOnlyAttribute, WritableAttribute};
+mod backend;
+pub use backend::{AttributeSource, Backend, Sysfs};
+mod mock;
+pub use mock::{MockDevice, MockEv3};
 mod driver;
-pub use driver::Driver;
+pub use driver::{CacheStrategy, Driver};
 #[cfg(feature = "override-driver-path")]
 pub use driver::DRIVER_PATH;
 mod device;
@@ -63,18 +67,37 @@ pub use device::Device;
 mod utils;
 pub use utils::{Ev3Error, Ev3Result, Port};
 
+mod discovery;
+pub use discovery::{
+    scan, scan_class, scan_class_from, scan_from, DeviceDescriptor, DeviceEvent, DeviceWatcher,
+    SCAN_CLASSES,
+};
+
+mod devices;
+pub use devices::Ev3;
+
 pub mod wait;
 
+pub mod task;
+
+pub mod claim;
+
+pub mod control;
+
 pub mod motors;
 pub mod sensors;
 
 #[cfg(feature = "ev3")]
 mod ev3;
 #[cfg(feature = "ev3")]
-pub use ev3::Button;
+pub use ev3::{BrickButton, Button, ButtonListener};
 #[cfg(feature = "ev3")]
 pub use ev3::Led;
 #[cfg(feature = "ev3")]
+mod led_animation;
+#[cfg(feature = "ev3")]
+pub use led_animation::{interpolate_color, LedAnimation, LedPattern};
+#[cfg(feature = "ev3")]
 mod port_constants {
     pub const OUTPUT_A: &str = "outA";
     pub const OUTPUT_B: &str = "outB";
@@ -121,12 +144,68 @@ mod port_constants {
     pub const INPUT_4: &str = "spi0.1:S4";
 }
 
+#[cfg(feature = "pistorms")]
+mod pistorms;
+#[cfg(feature = "pistorms")]
+pub use pistorms::Led;
+#[cfg(feature = "pistorms")]
+mod port_constants {
+    pub const OUTPUT_A: &str = "pistorms:BAM1";
+    pub const OUTPUT_B: &str = "pistorms:BAM2";
+    pub const OUTPUT_C: &str = "pistorms:BBM1";
+    pub const OUTPUT_D: &str = "pistorms:BBM2";
+
+    pub const INPUT_1: &str = "pistorms:BAS1";
+    pub const INPUT_2: &str = "pistorms:BAS2";
+    pub const INPUT_3: &str = "pistorms:BBS1";
+    pub const INPUT_4: &str = "pistorms:BBS2";
+}
+
 pub mod sound;
 
+mod lego_port;
+pub use lego_port::LegoPort;
+
+mod led;
+pub use led::{LedChannel, Trigger};
+
 mod power_supply;
-pub use power_supply::PowerSupply;
+pub use power_supply::{PowerSupply, Technology};
+
+mod wedo_port;
+pub use wedo_port::WedoPort;
+
+pub mod brick;
+
+pub mod mailbox;
+
+pub mod input;
+
+#[cfg(any(feature = "remote-server", feature = "remote-client"))]
+pub mod remote;
+
+#[cfg(feature = "config")]
+pub mod config;
+
+#[cfg(feature = "uom")]
+pub mod units;
+
+pub mod calibration;
+
+pub mod datalog;
+
+mod battery_watchdog;
+pub use battery_watchdog::{BatteryWatchdog, BatteryWatchdogOptions};
 
 #[cfg(feature = "screen")]
 mod screen;
 #[cfg(feature = "screen")]
 pub use screen::Screen;
+
+#[cfg(feature = "screen")]
+mod display;
+#[cfg(feature = "screen")]
+pub use display::{Display, Font};
+
+#[cfg(all(feature = "ev3", feature = "screen"))]
+pub mod ui;
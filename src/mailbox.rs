@@ -0,0 +1,327 @@
+//! Brick-to-brick messaging with the mailboxes of the EV3 firmware.
+//!
+//! Messages are sent to a named mailbox over a Bluetooth RFCOMM connection
+//! and can be exchanged with bricks running the stock LEGO firmware.
+//! A mailbox holds either text, a number or a logic value.
+//! The type is not transmitted, so both sides have to agree on it.
+//!
+//! # Example
+//! ```no_run
+//! use ev3dev_lang_rust::mailbox::{MailboxConnection, Message};
+//!
+//! # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+//! let mut connection = MailboxConnection::connect("00:16:53:4F:AB:CD")?;
+//! connection.send(&Message::text("status", "ready"))?;
+//!
+//! let message = connection.receive()?;
+//! if message.name == "speed" {
+//!     println!("New speed: {:?}", message.as_number());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::mem;
+use std::os::unix::io::{AsRawFd, FromRawFd, OwnedFd};
+
+use crate::{Ev3Error, Ev3Result};
+
+/// Message counter of sent messages, ignored by the firmware.
+const MESSAGE_COUNTER: u16 = 1;
+/// Command type of system commands that do not expect a reply.
+const SYSTEM_COMMAND_NO_REPLY: u8 = 0x81;
+/// System command to write to a mailbox.
+const WRITE_MAILBOX: u8 = 0x9E;
+/// Length of the fields between the packet length and the mailbox name.
+const HEADER_LEN: usize = 5;
+
+/// Bluetooth RFCOMM protocol, not defined by `libc`.
+const BTPROTO_RFCOMM: libc::c_int = 3;
+/// RFCOMM channel of the EV3 serial port profile.
+const RFCOMM_CHANNEL: u8 = 1;
+
+/// A message for a named mailbox.
+///
+/// The payload is encoded like the EV3 firmware does:
+/// text is null terminated UTF-8, numbers are little-endian `f32` and logic values are a single byte.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Message {
+    /// Name of the mailbox
+    pub name: String,
+    /// Encoded value
+    pub payload: Vec<u8>,
+}
+
+impl Message {
+    /// Creates a text message.
+    pub fn text(name: &str, text: &str) -> Self {
+        let mut payload = text.as_bytes().to_vec();
+        payload.push(0);
+        Message {
+            name: name.to_owned(),
+            payload,
+        }
+    }
+
+    /// Creates a number message.
+    pub fn number(name: &str, number: f32) -> Self {
+        Message {
+            name: name.to_owned(),
+            payload: number.to_le_bytes().to_vec(),
+        }
+    }
+
+    /// Creates a logic message.
+    pub fn logic(name: &str, logic: bool) -> Self {
+        Message {
+            name: name.to_owned(),
+            payload: vec![logic as u8],
+        }
+    }
+
+    /// Returns the payload as text. The null terminator is removed.
+    pub fn as_text(&self) -> Option<String> {
+        let text = self.payload.strip_suffix(&[0]).unwrap_or(&self.payload);
+        String::from_utf8(text.to_vec()).ok()
+    }
+
+    /// Returns the payload as number if it has exactly 4 bytes.
+    pub fn as_number(&self) -> Option<f32> {
+        let bytes: [u8; 4] = self.payload.as_slice().try_into().ok()?;
+        Some(f32::from_le_bytes(bytes))
+    }
+
+    /// Returns the payload as logic value if it has exactly 1 byte.
+    pub fn as_logic(&self) -> Option<bool> {
+        match self.payload[..] {
+            [value] => Some(value != 0),
+            _ => None,
+        }
+    }
+
+    /// Encodes the message as `WRITE_MAILBOX` system command, including the leading packet length.
+    ///
+    /// Returns `Ev3Error::InternalError` if the name or the payload are too long for the packet format.
+    pub fn encode(&self) -> Ev3Result<Vec<u8>> {
+        let name_len = self.name.len() + 1;
+        let packet_len = HEADER_LEN + name_len + 2 + self.payload.len();
+
+        if name_len > u8::MAX as usize || packet_len > u16::MAX as usize {
+            return Err(Ev3Error::InternalError {
+                msg: format!("Mailbox message '{}' is too long", self.name),
+                path: None,
+            });
+        }
+
+        let mut data = Vec::with_capacity(packet_len + 2);
+        data.extend_from_slice(&(packet_len as u16).to_le_bytes());
+        data.extend_from_slice(&MESSAGE_COUNTER.to_le_bytes());
+        data.push(SYSTEM_COMMAND_NO_REPLY);
+        data.push(WRITE_MAILBOX);
+        data.push(name_len as u8);
+        data.extend_from_slice(self.name.as_bytes());
+        data.push(0);
+        data.extend_from_slice(&(self.payload.len() as u16).to_le_bytes());
+        data.extend_from_slice(&self.payload);
+        Ok(data)
+    }
+
+    /// Decodes a `WRITE_MAILBOX` system command, including the leading packet length.
+    pub fn decode(data: &[u8]) -> Ev3Result<Self> {
+        let invalid = |msg: &str| Ev3Error::InternalError {
+            msg: format!("Invalid mailbox message: {msg}"),
+            path: None,
+        };
+
+        if data.len() < 2 + HEADER_LEN {
+            return Err(invalid("too short"));
+        }
+        let packet_len = u16::from_le_bytes([data[0], data[1]]) as usize;
+        if data.len() != packet_len + 2 {
+            return Err(invalid("length does not match"));
+        }
+        if data[4] != SYSTEM_COMMAND_NO_REPLY || data[5] != WRITE_MAILBOX {
+            return Err(invalid("not a mailbox command"));
+        }
+
+        let name_end = 7 + data[6] as usize;
+        let name = data
+            .get(7..name_end)
+            .ok_or_else(|| invalid("name exceeds message"))?;
+        let name = name.strip_suffix(&[0]).unwrap_or(name);
+
+        let payload_len = data
+            .get(name_end..name_end + 2)
+            .map(|len| u16::from_le_bytes([len[0], len[1]]) as usize)
+            .ok_or_else(|| invalid("payload length is missing"))?;
+        let payload = data
+            .get(name_end + 2..name_end + 2 + payload_len)
+            .ok_or_else(|| invalid("payload exceeds message"))?;
+
+        Ok(Message {
+            name: String::from_utf8(name.to_vec())?,
+            payload: payload.to_vec(),
+        })
+    }
+
+    /// Reads the next message from `reader`.
+    pub fn read_from(reader: &mut impl Read) -> Ev3Result<Self> {
+        let mut data = vec![0; 2];
+        reader.read_exact(&mut data)?;
+
+        let packet_len = u16::from_le_bytes([data[0], data[1]]) as usize;
+        data.resize(packet_len + 2, 0);
+        reader.read_exact(&mut data[2..])?;
+
+        Message::decode(&data)
+    }
+
+    /// Writes the message to `writer`.
+    pub fn write_to(&self, writer: &mut impl Write) -> Ev3Result<()> {
+        writer.write_all(&self.encode()?)?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// `struct sockaddr_rc` of the Linux Bluetooth stack.
+#[repr(C)]
+struct SockaddrRc {
+    rc_family: libc::sa_family_t,
+    /// Address bytes in reversed order
+    rc_bdaddr: [u8; 6],
+    rc_channel: u8,
+}
+
+impl SockaddrRc {
+    fn new(address: [u8; 6]) -> Self {
+        SockaddrRc {
+            rc_family: libc::AF_BLUETOOTH as libc::sa_family_t,
+            rc_bdaddr: address,
+            rc_channel: RFCOMM_CHANNEL,
+        }
+    }
+}
+
+/// Parses a Bluetooth address like `00:16:53:4F:AB:CD` into the reversed byte order of the kernel.
+fn parse_address(address: &str) -> Ev3Result<[u8; 6]> {
+    let invalid = || Ev3Error::InternalError {
+        msg: format!("Invalid bluetooth address '{address}'"),
+        path: None,
+    };
+
+    let parts = address.split(':').collect::<Vec<_>>();
+    if parts.len() != 6 {
+        return Err(invalid());
+    }
+
+    let mut bytes = [0; 6];
+    for (byte, part) in bytes.iter_mut().rev().zip(parts) {
+        *byte = u8::from_str_radix(part, 16).map_err(|_| invalid())?;
+    }
+    Ok(bytes)
+}
+
+/// Converts the return value of a socket call into a result.
+fn check(result: libc::c_int) -> io::Result<libc::c_int> {
+    if result < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(result)
+    }
+}
+
+fn rfcomm_socket() -> io::Result<OwnedFd> {
+    let fd = check(unsafe { libc::socket(libc::AF_BLUETOOTH, libc::SOCK_STREAM, BTPROTO_RFCOMM) })?;
+    Ok(unsafe { OwnedFd::from_raw_fd(fd) })
+}
+
+/// Bluetooth connection to another brick to exchange mailbox messages.
+///
+/// The brick has to be paired before a connection can be established.
+#[derive(Debug)]
+pub struct MailboxConnection {
+    socket: File,
+}
+
+impl MailboxConnection {
+    /// Connects to the brick with the given bluetooth address, e.g. `00:16:53:4F:AB:CD`.
+    pub fn connect(address: &str) -> Ev3Result<Self> {
+        let address = SockaddrRc::new(parse_address(address)?);
+        let socket = rfcomm_socket()?;
+
+        check(unsafe {
+            libc::connect(
+                socket.as_raw_fd(),
+                &address as *const SockaddrRc as *const libc::sockaddr,
+                mem::size_of::<SockaddrRc>() as libc::socklen_t,
+            )
+        })?;
+
+        Ok(MailboxConnection {
+            socket: File::from(socket),
+        })
+    }
+
+    /// Waits until another brick connects to this brick.
+    pub fn accept() -> Ev3Result<Self> {
+        let address = SockaddrRc::new([0; 6]);
+        let server = rfcomm_socket()?;
+
+        check(unsafe {
+            libc::bind(
+                server.as_raw_fd(),
+                &address as *const SockaddrRc as *const libc::sockaddr,
+                mem::size_of::<SockaddrRc>() as libc::socklen_t,
+            )
+        })?;
+        check(unsafe { libc::listen(server.as_raw_fd(), 1) })?;
+
+        let fd = check(unsafe {
+            libc::accept(
+                server.as_raw_fd(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+            )
+        })?;
+
+        Ok(MailboxConnection {
+            socket: File::from(unsafe { OwnedFd::from_raw_fd(fd) }),
+        })
+    }
+
+    /// Creates a second handle for the same connection, e.g. to receive on another thread.
+    pub fn try_clone(&self) -> Ev3Result<Self> {
+        Ok(MailboxConnection {
+            socket: self.socket.try_clone()?,
+        })
+    }
+
+    /// Sends a message.
+    pub fn send(&mut self, message: &Message) -> Ev3Result<()> {
+        message.write_to(&mut self.socket)
+    }
+
+    /// Sends `text` to the mailbox `name`.
+    pub fn send_text(&mut self, name: &str, text: &str) -> Ev3Result<()> {
+        self.send(&Message::text(name, text))
+    }
+
+    /// Sends `number` to the mailbox `name`.
+    pub fn send_number(&mut self, name: &str, number: f32) -> Ev3Result<()> {
+        self.send(&Message::number(name, number))
+    }
+
+    /// Sends `logic` to the mailbox `name`.
+    pub fn send_logic(&mut self, name: &str, logic: bool) -> Ev3Result<()> {
+        self.send(&Message::logic(name, logic))
+    }
+
+    /// Blocks until the next message is received.
+    pub fn receive(&mut self) -> Ev3Result<Message> {
+        Message::read_from(&mut self.socket)
+    }
+}
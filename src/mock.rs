@@ -0,0 +1,315 @@
+//! In-memory backend with fake devices for unit tests.
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Display;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::driver::DRIVER_PATH;
+use crate::motors::MotorPort;
+use crate::sensors::SensorPort;
+use crate::{Attribute, AttributeSource, Backend, Ev3Error, Ev3Result, Port};
+
+#[derive(Debug, Default)]
+struct MockValue {
+    value: Option<String>,
+    script: VecDeque<String>,
+    writes: Vec<String>,
+}
+
+#[derive(Debug)]
+struct MockState {
+    class_name: String,
+    name: String,
+    connected: AtomicBool,
+    values: Mutex<HashMap<String, MockValue>>,
+}
+
+/// Fake device of a `MockEv3`. Clones share the same device.
+#[derive(Debug, Clone)]
+pub struct MockDevice {
+    state: Arc<MockState>,
+}
+
+impl MockDevice {
+    /// Returns the name of the device, e.g. `sensor0`.
+    pub fn name(&self) -> &str {
+        &self.state.name
+    }
+
+    /// Returns the class of the device, e.g. `lego-sensor`.
+    pub fn class_name(&self) -> &str {
+        &self.state.class_name
+    }
+
+    /// Sets the value of the attribute `attribute_name` and removes its script.
+    pub fn set(&self, attribute_name: &str, value: impl Display) -> &Self {
+        let mut values = self.state.values.lock().unwrap();
+        let entry = values.entry(attribute_name.to_owned()).or_default();
+        entry.value = Some(value.to_string());
+        entry.script.clear();
+        self
+    }
+
+    /// Scripts the values of the attribute `attribute_name`.
+    ///
+    /// Every read returns the next value; the last value is kept after the script is finished.
+    pub fn script<T: Display>(
+        &self,
+        attribute_name: &str,
+        values: impl IntoIterator<Item = T>,
+    ) -> &Self {
+        let mut values_map = self.state.values.lock().unwrap();
+        let entry = values_map.entry(attribute_name.to_owned()).or_default();
+        entry.script = values.into_iter().map(|value| value.to_string()).collect();
+        self
+    }
+
+    /// Returns the current value of the attribute `attribute_name` without advancing its script.
+    pub fn value(&self, attribute_name: &str) -> Option<String> {
+        self.state
+            .values
+            .lock()
+            .unwrap()
+            .get(attribute_name)
+            .and_then(|entry| entry.value.clone())
+    }
+
+    /// Returns all values that were written to the attribute `attribute_name`, oldest first.
+    pub fn writes(&self, attribute_name: &str) -> Vec<String> {
+        self.state
+            .values
+            .lock()
+            .unwrap()
+            .get(attribute_name)
+            .map(|entry| entry.writes.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the last value that was written to the attribute `attribute_name`.
+    pub fn last_write(&self, attribute_name: &str) -> Option<String> {
+        self.writes(attribute_name).pop()
+    }
+
+    /// Forgets the written values of all attributes.
+    pub fn clear_writes(&self) {
+        for entry in self.state.values.lock().unwrap().values_mut() {
+            entry.writes.clear();
+        }
+    }
+
+    /// Simulates unplugging the device. All attribute accesses fail until it is connected again.
+    pub fn disconnect(&self) {
+        self.state.connected.store(false, Ordering::SeqCst);
+    }
+
+    /// Plugs the device in again.
+    pub fn connect(&self) {
+        self.state.connected.store(true, Ordering::SeqCst);
+    }
+
+    /// Checks if the device is plugged in.
+    pub fn is_connected(&self) -> bool {
+        self.state.connected.load(Ordering::SeqCst)
+    }
+}
+
+/// In-memory backend with fake sensors and motors for unit tests.
+///
+/// Devices are created with the `_from()` constructors and read and write the attributes
+/// of the `MockDevice`s, so control code can be tested without a brick and without a fake
+/// `/sys/class/` directory tree.
+///
+/// # Example
+/// ```
+/// use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+/// use ev3dev_lang_rust::sensors::{SensorPort, TouchSensor};
+/// use ev3dev_lang_rust::MockEv3;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let ev3 = MockEv3::new();
+/// let mock_touch = ev3.add_sensor("lego-ev3-touch", SensorPort::In1);
+/// let mock_motor = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+///
+/// mock_touch.set("mode", "TOUCH").script("value0", [0, 0, 1]);
+///
+/// let touch = TouchSensor::get_from(&ev3, SensorPort::In1)?;
+/// let motor = LargeMotor::get_from(&ev3, MotorPort::OutA)?;
+///
+/// motor.run_forever()?;
+/// while !touch.get_pressed_state()? {}
+/// motor.stop()?;
+///
+/// assert_eq!(mock_motor.writes("command"), ["run-forever", "stop"]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MockEv3 {
+    devices: Arc<Mutex<Vec<MockDevice>>>,
+}
+
+impl MockEv3 {
+    /// Creates a backend without devices.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a device of the class `class_name` with the driver `driver_name` on the given port.
+    pub fn add_device(&self, class_name: &str, driver_name: &str, port: &dyn Port) -> MockDevice {
+        let mut devices = self.devices.lock().unwrap();
+
+        let prefix = match class_name {
+            "lego-sensor" => "sensor",
+            "tacho-motor" | "dc-motor" | "servo-motor" => "motor",
+            _ => "device",
+        };
+        let count = devices
+            .iter()
+            .filter(|device| device.class_name() == class_name)
+            .count();
+
+        let device = MockDevice {
+            state: Arc::new(MockState {
+                class_name: class_name.to_owned(),
+                name: format!("{prefix}{count}"),
+                connected: AtomicBool::new(true),
+                values: Mutex::new(HashMap::new()),
+            }),
+        };
+        device
+            .set("driver_name", driver_name)
+            .set("address", format!("mock:{}", port.address()));
+
+        devices.push(device.clone());
+        device
+    }
+
+    /// Adds a sensor with the driver `driver_name`, e.g. `lego-ev3-color`.
+    pub fn add_sensor(&self, driver_name: &str, port: SensorPort) -> MockDevice {
+        self.add_device("lego-sensor", driver_name, &port)
+    }
+
+    /// Adds a tacho motor with the driver `driver_name`, e.g. `lego-ev3-l-motor`.
+    /// The motor starts at position 0 with 360 counts per rotation.
+    pub fn add_motor(&self, driver_name: &str, port: MotorPort) -> MockDevice {
+        let device = self.add_device("tacho-motor", driver_name, &port);
+        device
+            .set("count_per_rot", 360)
+            .set("position", 0)
+            .set("speed", 0)
+            .set("state", "");
+        device
+    }
+
+    /// Returns all devices.
+    pub fn devices(&self) -> Vec<MockDevice> {
+        self.devices.lock().unwrap().clone()
+    }
+
+    fn device(&self, class_name: &str, name: &str) -> Option<MockDevice> {
+        self.devices
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|device| device.class_name() == class_name && device.name() == name)
+            .cloned()
+    }
+}
+
+impl Backend for MockEv3 {
+    fn find_names(&self, class_name: &str) -> Ev3Result<Vec<String>> {
+        Ok(self
+            .devices
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|device| device.class_name() == class_name && device.is_connected())
+            .map(|device| device.name().to_owned())
+            .collect())
+    }
+
+    fn open_attribute(
+        &self,
+        class_name: &str,
+        name: &str,
+        attribute_name: &str,
+    ) -> Ev3Result<Attribute> {
+        let device = self
+            .device(class_name, name)
+            .filter(MockDevice::is_connected)
+            .ok_or_else(|| Ev3Error::NotConnected {
+                device: format!("{class_name}/{name}"),
+                port: None,
+                path: Some(Path::new(DRIVER_PATH).join(class_name).join(name)),
+            })?;
+
+        let path = Path::new(DRIVER_PATH)
+            .join(class_name)
+            .join(name)
+            .join(attribute_name);
+
+        Ok(Attribute::from_source(
+            &path,
+            Arc::new(MockAttribute {
+                device,
+                attribute_name: attribute_name.to_owned(),
+                path: path.clone(),
+            }),
+        ))
+    }
+}
+
+/// Attribute of a `MockDevice`.
+#[derive(Debug)]
+struct MockAttribute {
+    device: MockDevice,
+    attribute_name: String,
+    path: PathBuf,
+}
+
+impl MockAttribute {
+    /// Returns the error of a sysfs attribute whose device was unplugged or that does not exist.
+    fn not_found(&self) -> Ev3Error {
+        Ev3Error::Io {
+            path: Some(self.path.clone()),
+            source: io::Error::from(io::ErrorKind::NotFound),
+        }
+    }
+}
+
+impl AttributeSource for MockAttribute {
+    fn read(&self) -> Ev3Result<Vec<u8>> {
+        if !self.device.is_connected() {
+            return Err(self.not_found());
+        }
+
+        let mut values = self.device.state.values.lock().unwrap();
+        let entry = values
+            .get_mut(&self.attribute_name)
+            .ok_or_else(|| self.not_found())?;
+        if let Some(value) = entry.script.pop_front() {
+            entry.value = Some(value);
+        }
+        entry
+            .value
+            .as_ref()
+            .map(|value| value.as_bytes().to_vec())
+            .ok_or_else(|| self.not_found())
+    }
+
+    fn write(&self, data: &[u8]) -> Ev3Result<()> {
+        if !self.device.is_connected() {
+            return Err(self.not_found());
+        }
+
+        let value = String::from_utf8_lossy(data).into_owned();
+        let mut values = self.device.state.values.lock().unwrap();
+        let entry = values.entry(self.attribute_name.clone()).or_default();
+        entry.writes.push(value.clone());
+        entry.value = Some(value);
+        Ok(())
+    }
+}
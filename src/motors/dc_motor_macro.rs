@@ -45,29 +45,29 @@ macro_rules! dc_motor {
 
         /// Returns the current duty cycle of the motor. Units are percent. Values are -100 to 100.
         pub fn get_duty_cycle(&self) -> Ev3Result<i32> {
-            self.get_attribute("duty_cycle").get()
+            self.get_read_only_attribute("duty_cycle")?.get()
         }
 
         /// Returns the current duty cycle setpoint of the motor. Units are in percent.
         /// Valid values are -100 to 100. A negative value causes the motor to rotate in reverse.
         pub fn get_duty_cycle_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("duty_cycle_sp").get()
+            self.get_attribute("duty_cycle_sp")?.get()
         }
 
         /// Sets the duty cycle setpoint of the motor. Units are in percent.
         /// Valid values are -100 to 100. A negative value causes the motor to rotate in reverse.
         pub fn set_duty_cycle_sp(&self, duty_cycle_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("duty_cycle_sp").set(duty_cycle_sp)
+            self.get_attribute("duty_cycle_sp")?.set(duty_cycle_sp)
         }
 
         /// Returns the current polarity of the motor.
         pub fn get_polarity(&self) -> Ev3Result<String> {
-            self.get_attribute("polarity").get()
+            self.get_attribute("polarity")?.get()
         }
 
         /// Sets the polarity of the motor.
         pub fn set_polarity(&self, polarity: &str) -> Ev3Result<()> {
-            self.get_attribute("polarity").set_str_slice(polarity)
+            self.get_attribute("polarity")?.set_str_slice(polarity)
         }
 
         /// Returns the current ramp up setpoint.
@@ -76,7 +76,7 @@ macro_rules! dc_motor {
         /// The actual ramp time is the ratio of the difference between the speed_sp
         /// and the current speed and max_speed multiplied by ramp_up_sp. Values must not be negative.
         pub fn get_ramp_up_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("ramp_up_sp").get()
+            self.get_attribute("ramp_up_sp")?.get()
         }
 
         /// Sets the ramp up setpoint.
@@ -85,7 +85,7 @@ macro_rules! dc_motor {
         /// The actual ramp time is the ratio of the difference between the speed_sp
         /// and the current speed and max_speed multiplied by ramp_up_sp. Values must not be negative.
         pub fn set_ramp_up_sp(&self, ramp_up_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("ramp_up_sp").set(ramp_up_sp)
+            self.get_attribute("ramp_up_sp")?.set(ramp_up_sp)
         }
 
         /// Returns the current ramp down setpoint.
@@ -94,7 +94,7 @@ macro_rules! dc_motor {
         /// The actual ramp time is the ratio of the difference between the speed_sp
         /// and the current speed and 0 multiplied by ramp_down_sp. Values must not be negative.
         pub fn get_ramp_down_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("ramp_down_sp").get()
+            self.get_attribute("ramp_down_sp")?.get()
         }
 
         /// Sets the ramp down setpoint.
@@ -103,36 +103,37 @@ macro_rules! dc_motor {
         /// The actual ramp time is the ratio of the difference between the speed_sp
         /// and the current speed and 0 multiplied by ramp_down_sp. Values must not be negative.
         pub fn set_ramp_down_sp(&self, ramp_down_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("ramp_down_sp").set(ramp_down_sp)
+            self.get_attribute("ramp_down_sp")?.set(ramp_down_sp)
         }
 
         /// Returns a list of state flags.
         pub fn get_state(&self) -> Ev3Result<Vec<String>> {
-            self.get_attribute("state").get_vec()
+            self.get_read_only_attribute("state")?.get_vec()
         }
 
         /// Returns the current stop action.
         /// The value determines the motors behavior when command is set to stop.
         pub fn get_stop_action(&self) -> Ev3Result<String> {
-            self.get_attribute("stop_action").get()
+            self.get_attribute("stop_action")?.get()
         }
 
         /// Sets the stop action.
         /// The value determines the motors behavior when command is set to stop.
         pub fn set_stop_action(&self, stop_action: &str) -> Ev3Result<()> {
-            self.get_attribute("stop_action").set_str_slice(stop_action)
+            self.get_attribute("stop_action")?
+                .set_str_slice(stop_action)
         }
 
         /// Returns the current amount of time the motor will run when using the run-timed command.
         /// Units are in milliseconds. Values must not be negative.
         pub fn get_time_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("time_sp").get()
+            self.get_attribute("time_sp")?.get()
         }
 
         /// Sets the amount of time the motor will run when using the run-timed command.
         /// Units are in milliseconds. Values must not be negative.
         pub fn set_time_sp(&self, time_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("time_sp").set(time_sp)
+            self.get_attribute("time_sp")?.set(time_sp)
         }
 
         /// Runs the motor using the duty cycle specified by `duty_cycle_sp`.
@@ -0,0 +1,169 @@
+//! Software ramp for the duty cycle of a motor.
+
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::task::CancelToken;
+use crate::{Device, Ev3Result};
+
+/// Options for a `DutyRamp`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DutyRampOptions {
+    /// Maximal change of the duty cycle in percent per second.
+    pub rate: f32,
+    /// Time between two updates of `duty_cycle_sp`.
+    pub interval: Duration,
+}
+
+impl Default for DutyRampOptions {
+    /// Change the duty cycle by at most 200 % per second (a full reversal takes one second), updated every 20 ms.
+    fn default() -> Self {
+        Self {
+            rate: 200.0,
+            interval: Duration::from_millis(20),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RampState {
+    target: i32,
+    current: f32,
+}
+
+/// Slews the `duty_cycle_sp` of a motor toward a target with a limited rate in a background thread.
+///
+/// The ramp attributes of the kernel (`ramp_up_sp`, `ramp_down_sp`) don't apply to the `run-direct` command
+/// and to some dc motors, so an instant reversal from 100 % to -100 % would stress the gears.
+/// The ramp only writes `duty_cycle_sp`, the motor has to be started with `run_direct()`.
+///
+/// The thread ends when writing the duty cycle fails and is stopped when the ramp is dropped.
+///
+/// # Example
+/// ```no_run
+/// use std::thread;
+/// use std::time::Duration;
+/// use ev3dev_lang_rust::motors::{DutyRamp, DutyRampOptions, LargeMotor, MotorPort};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let motor = LargeMotor::get(MotorPort::OutA)?;
+/// motor.set_duty_cycle_sp(0)?;
+/// motor.run_direct()?;
+///
+/// let ramp = DutyRamp::start(motor.clone(), DutyRampOptions::default());
+/// ramp.set_target(100);
+/// thread::sleep(Duration::from_secs(2));
+///
+/// // Slows down to zero before the motor reverses.
+/// ramp.set_target(-100);
+/// thread::sleep(Duration::from_secs(2));
+///
+/// ramp.stop();
+/// motor.stop()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct DutyRamp {
+    state: Arc<Mutex<RampState>>,
+    token: CancelToken,
+    handle: Option<JoinHandle<Ev3Result<()>>>,
+}
+
+impl DutyRamp {
+    /// Starts the ramp for `motor`. The ramp starts at the current `duty_cycle_sp` (0 if it cannot be read),
+    /// which is also the initial target.
+    pub fn start(motor: impl Device + Send + 'static, options: DutyRampOptions) -> Self {
+        let initial = motor
+            .get_attribute("duty_cycle_sp")
+            .and_then(|attribute| attribute.get::<i32>())
+            .unwrap_or(0);
+        let state = Arc::new(Mutex::new(RampState {
+            target: initial,
+            current: initial as f32,
+        }));
+        let token = CancelToken::new();
+
+        let thread_state = state.clone();
+        let thread_token = token.clone();
+        let handle = thread::spawn(move || {
+            let duty_cycle_sp = motor.get_attribute("duty_cycle_sp")?;
+            let mut written = initial;
+            let mut last_update = Instant::now();
+
+            while thread_token.sleep(options.interval) {
+                let now = Instant::now();
+                let max_step = options.rate.abs() * (now - last_update).as_secs_f32();
+                last_update = now;
+
+                let value = {
+                    let mut state = thread_state.lock().unwrap();
+                    let delta = state.target as f32 - state.current;
+                    state.current += delta.clamp(-max_step, max_step);
+                    state.current.round() as i32
+                };
+
+                if value != written {
+                    duty_cycle_sp.set(value)?;
+                    written = value;
+                }
+            }
+            Ok(())
+        });
+
+        DutyRamp {
+            state,
+            token,
+            handle: Some(handle),
+        }
+    }
+
+    /// Sets the duty cycle in percent (-100 to 100) the ramp slews toward.
+    pub fn set_target(&self, duty_cycle: i32) {
+        self.state.lock().unwrap().target = duty_cycle.clamp(-100, 100);
+    }
+
+    /// Returns the duty cycle the ramp slews toward.
+    pub fn get_target(&self) -> i32 {
+        self.state.lock().unwrap().target
+    }
+
+    /// Returns the current duty cycle of the ramp.
+    pub fn get_current(&self) -> i32 {
+        self.state.lock().unwrap().current.round() as i32
+    }
+
+    /// Checks if the ramp has reached its target.
+    pub fn is_settled(&self) -> bool {
+        let state = self.state.lock().unwrap();
+        state.current.round() as i32 == state.target
+    }
+
+    /// Checks if the background thread is still running.
+    pub fn is_running(&self) -> bool {
+        self.handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+    }
+
+    /// Stops the ramp at the current duty cycle without stopping the motor.
+    /// Returns the error that ended the thread early, if any.
+    pub fn stop(mut self) -> Ev3Result<()> {
+        self.shutdown()
+    }
+
+    fn shutdown(&mut self) -> Ev3Result<()> {
+        self.token.cancel();
+        match self.handle.take().map(JoinHandle::join) {
+            Some(Ok(result)) => result,
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Drop for DutyRamp {
+    fn drop(&mut self) {
+        let _ = self.shutdown();
+    }
+}
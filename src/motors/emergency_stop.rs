@@ -0,0 +1,483 @@
+//! Stop every motor that is connected to the brick or a registered set of motors on an emergency stop input.
+
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::os::unix::io::FromRawFd;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::device::device_path;
+use crate::sensors::{InfraredSensor, Sensor, TouchSensor};
+use crate::task::CancelToken;
+use crate::{Backend, Device, Ev3Error, Ev3Result, Sysfs};
+
+/// Stop all tacho, dc and servo motors in parallel.
+///
+/// Tacho and dc motors are stopped with the given `stop_action` (`"coast"`, `"brake"` or `"hold"`),
+/// servo motors are set to `"float"`.
+/// All motors are stopped even if some of them fail. In this case the first error is returned.
+///
+/// The function does not need any motor handles and can be used from watchdogs,
+/// button handlers or other threads that do not own the motors.
+/// Only the motors of this brick are stopped, use `emergency_stop_all_from()` for other backends.
+///
+/// # Signal handlers
+///
+/// This function is not async-signal-safe: it allocates, opens files and spawns threads,
+/// so it must not be called from a signal handler. Use `emergency_stop_on_signal()` instead.
+///
+/// # Example
+///
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{self, LargeMotor};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// motors::emergency_stop_all(LargeMotor::STOP_ACTION_BRAKE)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn emergency_stop_all(stop_action: &str) -> Ev3Result<()> {
+    emergency_stop_all_from(&Sysfs, stop_action)
+}
+
+/// Stop all tacho, dc and servo motors of the given `backend` in parallel. See `emergency_stop_all()`.
+pub fn emergency_stop_all_from(backend: &dyn Backend, stop_action: &str) -> Ev3Result<()> {
+    let mut motors = Vec::new();
+    for class_name in ["tacho-motor", "dc-motor", "servo-motor"] {
+        match backend.find_names(class_name) {
+            Ok(names) => motors.extend(names.into_iter().map(|name| (class_name, name))),
+            // A missing class directory means that no motor of this class was ever connected.
+            Err(Ev3Error::Io { source, .. }) if source.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    thread::scope(|s| {
+        let handles = motors
+            .iter()
+            .map(|(class_name, name)| {
+                let handle = s.spawn(move || stop_motor(backend, class_name, name, stop_action));
+                (name, handle)
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|(name, handle)| {
+                handle.join().unwrap_or_else(|_| {
+                    Err(Ev3Error::InternalError {
+                        msg: format!("Stopping {name} panicked"),
+                        path: None,
+                    })
+                })
+            })
+            .fold(Ok(()), Result::and)
+    })
+}
+
+/// Write ends of the pipes that wake the threads of `emergency_stop_on_signal()`, indexed by the signal number.
+static SIGNAL_PIPES: [AtomicI32; 65] = [const { AtomicI32::new(-1) }; 65];
+
+extern "C" fn on_signal(signal: libc::c_int) {
+    // Only async-signal-safe operations are allowed here.
+    let fd = SIGNAL_PIPES[signal as usize].load(Ordering::SeqCst);
+    if fd >= 0 {
+        unsafe { libc::write(fd, [1u8].as_ptr().cast(), 1) };
+    }
+}
+
+/// Stops all tacho, dc and servo motors with the given `stop_action` when the process receives `signal`,
+/// e.g. `libc::SIGINT` on Ctrl+C or `libc::SIGTERM`.
+///
+/// The signal handler only wakes a thread that is spawned by this function, which stops the motors
+/// with `emergency_stop_all()`. The returned token is cancelled afterwards, so the program can observe it,
+/// e.g. with `control::Loop::cancel_token()`, and end normally. The handler is removed after the first signal,
+/// so a second signal has its default effect, e.g. it terminates the program.
+///
+/// Returns an error if `signal` is invalid, cannot be caught or is already handled by this function.
+///
+/// # Example
+///
+/// ```no_run
+/// use ev3dev_lang_rust::control::Loop;
+/// use ev3dev_lang_rust::motors;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let interrupted = motors::emergency_stop_on_signal(libc::SIGINT, "brake")?;
+///
+/// // The control loop ends after the motors were stopped on Ctrl+C.
+/// Loop::new(std::time::Duration::from_millis(20))
+///     .cancel_token(interrupted)
+///     .run(|_| {
+///         // Drive...
+///         Ok(())
+///     })?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn emergency_stop_on_signal(signal: libc::c_int, stop_action: &str) -> Ev3Result<CancelToken> {
+    emergency_stop_on_signal_from(Arc::new(Sysfs), signal, stop_action)
+}
+
+/// Stops all motors of the given `backend` when the process receives `signal`. See `emergency_stop_on_signal()`.
+pub fn emergency_stop_on_signal_from(
+    backend: Arc<dyn Backend>,
+    signal: libc::c_int,
+    stop_action: &str,
+) -> Ev3Result<CancelToken> {
+    let slot = usize::try_from(signal)
+        .ok()
+        .and_then(|index| SIGNAL_PIPES.get(index))
+        .filter(|_| signal != 0)
+        .ok_or_else(|| Ev3Error::OutOfRange {
+            name: "Signal".to_owned(),
+            value: signal,
+            min: 1,
+            max: SIGNAL_PIPES.len() as i32 - 1,
+            path: None,
+        })?;
+
+    let mut fds = [0; 2];
+    if unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    let [read_fd, write_fd] = fds;
+    let mut reader = unsafe { File::from_raw_fd(read_fd) };
+
+    if slot
+        .compare_exchange(-1, write_fd, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        unsafe { libc::close(write_fd) };
+        return Err(Ev3Error::InternalError {
+            msg: format!("Signal {signal} already stops the motors"),
+            path: None,
+        });
+    }
+
+    let handler = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+    if unsafe { libc::signal(signal, handler) } == libc::SIG_ERR {
+        let error = io::Error::last_os_error();
+        slot.store(-1, Ordering::SeqCst);
+        unsafe { libc::close(write_fd) };
+        return Err(error.into());
+    }
+
+    let token = CancelToken::new();
+    let thread_token = token.clone();
+    let stop_action = stop_action.to_owned();
+    thread::spawn(move || {
+        if reader.read_exact(&mut [0]).is_err() {
+            return;
+        }
+        unsafe { libc::signal(signal, libc::SIG_DFL) };
+        unsafe { libc::close(slot.swap(-1, Ordering::SeqCst)) };
+
+        let _ = emergency_stop_all_from(&*backend, &stop_action);
+        thread_token.cancel();
+    });
+
+    Ok(token)
+}
+
+fn stop_motor(
+    backend: &dyn Backend,
+    class_name: &str,
+    name: &str,
+    stop_action: &str,
+) -> Ev3Result<()> {
+    let command = backend.open_attribute(class_name, name, "command")?;
+
+    if class_name == "servo-motor" {
+        command.set_str_slice("float")
+    } else {
+        backend
+            .open_attribute(class_name, name, "stop_action")?
+            .set_str_slice(stop_action)?;
+        command.set_str_slice("stop")
+    }
+}
+
+/// Options for an `EStop`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EStopOptions {
+    /// Time between two readings of the input.
+    pub interval: Duration,
+    /// Stop action of the registered tacho and dc motors (`"coast"`, `"brake"` or `"hold"`).
+    /// Servo motors are set to `"float"`.
+    pub stop_action: String,
+    /// Number of consecutive failed readings of the input after which the emergency stop triggers,
+    /// e.g. because the sensor was unplugged.
+    pub max_errors: u32,
+}
+
+impl Default for EStopOptions {
+    /// Read the input every 10 ms, coast the motors and trigger after 3 failed readings.
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(10),
+            stop_action: "coast".to_owned(),
+            max_errors: 3,
+        }
+    }
+}
+
+struct EStopShared {
+    motors: Mutex<Vec<Box<dyn Device + Send>>>,
+    stop_action: String,
+    triggered: CancelToken,
+    error: Mutex<Option<Ev3Error>>,
+}
+
+impl EStopShared {
+    /// Stops all registered motors and raises the flag. Only the first call has an effect.
+    fn fire(&self) {
+        // The lock serializes concurrent triggers, so the flag is only raised after the motors were stopped.
+        let motors = self.motors.lock().unwrap();
+        if self.triggered.is_cancelled() {
+            return;
+        }
+
+        for motor in motors.iter() {
+            let _ = stop_device(motor.as_ref(), &self.stop_action);
+        }
+        self.triggered.cancel();
+    }
+}
+
+fn stop_device(motor: &dyn Device, stop_action: &str) -> Ev3Result<()> {
+    match motor.get_attribute("stop_action") {
+        Ok(attribute) if attribute.set_str_slice(stop_action).is_ok() => motor.set_command("stop"),
+        // Servo motors have no stop action and are stopped by letting them float.
+        _ => motor.set_command("float"),
+    }
+}
+
+/// Watches an emergency stop input in a background thread and, when it triggers,
+/// immediately stops a registered set of motors and raises a flag the main loop can observe.
+///
+/// The emergency stop latches: once triggered it stays triggered and stops watching the input.
+/// The thread is stopped when the `EStop` is dropped.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::control::Loop;
+/// use ev3dev_lang_rust::motors::{EStop, EStopOptions, LargeMotor, MotorPort};
+/// use ev3dev_lang_rust::sensors::{SensorPort, TouchSensor};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let left = LargeMotor::get(MotorPort::OutB)?;
+/// let right = LargeMotor::get(MotorPort::OutC)?;
+///
+/// let estop = EStop::touch_sensor(
+///     TouchSensor::get(SensorPort::In1)?,
+///     vec![Box::new(left.clone()), Box::new(right.clone())],
+///     EStopOptions::default(),
+/// );
+///
+/// // The control loop ends as soon as the touch sensor is pressed.
+/// Loop::new(std::time::Duration::from_millis(20))
+///     .cancel_token(estop.token())
+///     .run(|_| {
+///         // Drive...
+///         Ok(())
+///     })?;
+///
+/// if estop.is_triggered() {
+///     println!("Emergency stop!");
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct EStop {
+    shared: Arc<EStopShared>,
+    token: CancelToken,
+    handle: Option<JoinHandle<()>>,
+    #[cfg(feature = "ev3")]
+    listener: Option<crate::ButtonListener>,
+}
+
+impl EStop {
+    /// Starts polling `trigger` and stops the `motors` as soon as it returns `true`.
+    ///
+    /// The emergency stop fails safe: it also triggers after `options.max_errors` consecutive failed readings.
+    /// The last error is returned by `take_error()`.
+    pub fn start(
+        mut trigger: impl FnMut() -> Ev3Result<bool> + Send + 'static,
+        motors: Vec<Box<dyn Device + Send>>,
+        options: EStopOptions,
+    ) -> Self {
+        let shared = Self::shared(motors, &options);
+        let token = CancelToken::new();
+
+        let thread_shared = shared.clone();
+        let thread_token = token.clone();
+        let handle = thread::spawn(move || {
+            let mut errors = 0;
+            while !thread_shared.triggered.is_cancelled() {
+                match trigger() {
+                    Ok(true) => {
+                        thread_shared.fire();
+                        return;
+                    }
+                    Ok(false) => errors = 0,
+                    Err(e) => {
+                        errors += 1;
+                        if errors >= options.max_errors {
+                            *thread_shared.error.lock().unwrap() = Some(e);
+                            thread_shared.fire();
+                            return;
+                        }
+                    }
+                }
+                if !thread_token.sleep(options.interval) {
+                    return;
+                }
+            }
+        });
+
+        EStop {
+            shared,
+            token,
+            handle: Some(handle),
+            #[cfg(feature = "ev3")]
+            listener: None,
+        }
+    }
+
+    /// Triggers when the touch `sensor` is pressed.
+    pub fn touch_sensor(
+        sensor: TouchSensor,
+        motors: Vec<Box<dyn Device + Send>>,
+        options: EStopOptions,
+    ) -> Self {
+        Self::start(move || sensor.get_pressed_state(), motors, options)
+    }
+
+    /// Triggers when any button of the IR remote control on `channel` (1 to 4) is pressed.
+    /// Switches the `sensor` to the mode `IR-REMOTE`.
+    ///
+    /// Returns `Ev3Error::OutOfRange` for other channels.
+    pub fn ir_remote(
+        sensor: InfraredSensor,
+        channel: u8,
+        motors: Vec<Box<dyn Device + Send>>,
+        options: EStopOptions,
+    ) -> Ev3Result<Self> {
+        if !(1..=4).contains(&channel) {
+            return Err(Ev3Error::OutOfRange {
+                name: "IR remote channel".to_owned(),
+                value: channel.into(),
+                min: 1,
+                max: 4,
+                path: device_path(&sensor),
+            });
+        }
+        sensor.set_mode_ir_remote()?;
+        let index = channel - 1;
+
+        Ok(Self::start(
+            move || Ok(sensor.get_value(index)? != 0),
+            motors,
+            options,
+        ))
+    }
+
+    /// Triggers when the brick `button` is pressed.
+    #[cfg(feature = "ev3")]
+    pub fn brick_button(
+        button: crate::BrickButton,
+        motors: Vec<Box<dyn Device + Send>>,
+        options: EStopOptions,
+    ) -> Ev3Result<Self> {
+        let shared = Self::shared(motors, &options);
+
+        let listener_shared = shared.clone();
+        let listener = crate::Button::listen(move |pressed, is_pressed| {
+            if pressed == button && is_pressed {
+                listener_shared.fire();
+            }
+        })?;
+
+        Ok(EStop {
+            shared,
+            token: CancelToken::new(),
+            handle: None,
+            listener: Some(listener),
+        })
+    }
+
+    fn shared(motors: Vec<Box<dyn Device + Send>>, options: &EStopOptions) -> Arc<EStopShared> {
+        Arc::new(EStopShared {
+            motors: Mutex::new(motors),
+            stop_action: options.stop_action.clone(),
+            triggered: CancelToken::new(),
+            error: Mutex::new(None),
+        })
+    }
+
+    /// Registers another motor that is stopped when the emergency stop triggers.
+    /// The motor is stopped immediately if the emergency stop has already triggered.
+    pub fn add_motor(&self, motor: Box<dyn Device + Send>) {
+        let mut motors = self.shared.motors.lock().unwrap();
+        if self.is_triggered() {
+            let _ = stop_device(motor.as_ref(), &self.shared.stop_action);
+        }
+        motors.push(motor);
+    }
+
+    /// Triggers the emergency stop from software.
+    pub fn trigger(&self) {
+        self.shared.fire();
+    }
+
+    /// Checks if the emergency stop has triggered.
+    pub fn is_triggered(&self) -> bool {
+        self.shared.triggered.is_cancelled()
+    }
+
+    /// Returns the error of the input if the emergency stop triggered because the input could not be read.
+    pub fn take_error(&self) -> Option<Ev3Error> {
+        self.shared.error.lock().unwrap().take()
+    }
+
+    /// Returns a token that is cancelled when the emergency stop triggers,
+    /// e.g. for `control::Loop::cancel_token()` or to wait for the trigger with `CancelToken::wait()`.
+    pub fn token(&self) -> CancelToken {
+        self.shared.triggered.clone()
+    }
+
+    /// Stops watching the input without stopping the motors.
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.token.cancel();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        #[cfg(feature = "ev3")]
+        if let Some(listener) = self.listener.take() {
+            listener.stop();
+        }
+    }
+}
+
+impl fmt::Debug for EStop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EStop")
+            .field("triggered", &self.is_triggered())
+            .field("motors", &self.shared.motors.lock().unwrap().len())
+            .finish()
+    }
+}
+
+impl Drop for EStop {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
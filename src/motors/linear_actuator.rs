@@ -0,0 +1,129 @@
+use super::MotorPort;
+use crate::device::device_path;
+use crate::{wait, Attribute, Device, Driver, Ev3Error, Ev3Result};
+use std::time::Duration;
+
+/// Firgelli L12 EV3 linear actuators (50 mm and 100 mm travel).
+///
+/// Besides the common tacho motor functions, positions can be given in millimeters of travel.
+/// The conversion uses the `count_per_m` and `full_travel_count` attributes reported by the driver.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{LinearActuator, MotorPort};
+/// use std::time::Duration;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let actuator = LinearActuator::get(MotorPort::OutA)?;
+///
+/// // Retract until the end stop is reached and use this as zero point.
+/// actuator.home(200, Some(Duration::from_secs(10)))?;
+///
+/// actuator.extend_to_mm(25.0, 200)?;
+/// actuator.wait_until_not_moving(None);
+///
+/// println!("Extended {:.1} mm", actuator.get_position_mm()?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Device)]
+pub struct LinearActuator {
+    driver: Driver,
+}
+
+impl LinearActuator {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    findable!(
+        "tacho-motor",
+        ["act-l12-ev3-50", "act-l12-ev3-100"],
+        MotorPort,
+        "LinearActuator",
+        "out"
+    );
+    tacho_motor!();
+
+    /// Converts a distance in millimeters to tacho counts.
+    pub fn mm_to_counts(&self, mm: f32) -> Ev3Result<i32> {
+        let count_per_m = self.get_count_per_m()? as f32;
+        Ok((mm * count_per_m / 1000.0).round() as i32)
+    }
+
+    /// Converts tacho counts to a distance in millimeters.
+    pub fn counts_to_mm(&self, counts: i32) -> Ev3Result<f32> {
+        let count_per_m = self.get_count_per_m()? as f32;
+        Ok(counts as f32 * 1000.0 / count_per_m)
+    }
+
+    /// Returns the maximal travel distance in millimeters.
+    pub fn get_full_travel_mm(&self) -> Ev3Result<f32> {
+        self.counts_to_mm(self.get_full_travel_count()?)
+    }
+
+    /// Returns the current extension in millimeters, relative to the zero point set by `home()`.
+    pub fn get_position_mm(&self) -> Ev3Result<f32> {
+        self.counts_to_mm(self.get_position()?)
+    }
+
+    /// Extends the actuator to the absolute position `mm` with the given `speed_sp`.
+    ///
+    /// Returns `Ev3Error::OutOfRange` with the target in counts without moving if `mm` is outside of the travel range.
+    pub fn extend_to_mm(&self, mm: f32, speed_sp: i32) -> Ev3Result<()> {
+        let counts = self.mm_to_counts(mm)?;
+        self.check_travel_limits(counts)?;
+
+        self.set_speed_sp(speed_sp.abs())?;
+        self.run_to_abs_pos(Some(counts))
+    }
+
+    /// Extends (or retracts for negative values) the actuator by `mm` with the given `speed_sp`.
+    ///
+    /// Returns `Ev3Error::OutOfRange` without moving if the target position is outside of the travel range.
+    pub fn extend_by_mm(&self, mm: f32, speed_sp: i32) -> Ev3Result<()> {
+        let target = self.get_position()? + self.mm_to_counts(mm)?;
+        self.check_travel_limits(target)?;
+
+        self.set_speed_sp(speed_sp.abs())?;
+        self.run_to_abs_pos(Some(target))
+    }
+
+    /// Retracts the actuator until it stalls at the end stop and sets this position as zero point.
+    ///
+    /// Returns `Ev3Error::Timeout` if the end stop is not reached within the `timeout`.
+    pub fn home(&self, speed_sp: i32, timeout: Option<Duration>) -> Ev3Result<()> {
+        self.set_speed_sp(-speed_sp.abs())?;
+        self.run_forever()?;
+
+        let reached = self.wait_until(Self::STATE_STALLED, timeout);
+        self.stop()?;
+
+        if !reached {
+            // Without a timeout the wait only ends early if the state cannot be read.
+            self.get_state()?;
+            return Err(Ev3Error::Timeout {
+                timeout: timeout.unwrap_or_default(),
+                path: device_path(self),
+            });
+        }
+
+        self.set_position(0)
+    }
+
+    fn check_travel_limits(&self, counts: i32) -> Ev3Result<()> {
+        let full_travel_count = self.get_full_travel_count()?;
+
+        if counts < 0 || counts > full_travel_count {
+            return Err(Ev3Error::OutOfRange {
+                name: "Target position".to_owned(),
+                value: counts,
+                min: 0,
+                max: full_travel_count,
+                path: device_path(self),
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -7,19 +7,41 @@ mod servo_motor_macro;
 #[macro_use]
 mod tacho_motor_macro;
 
+mod duty_ramp;
+pub use self::duty_ramp::{DutyRamp, DutyRampOptions};
+
+mod emergency_stop;
+pub use self::emergency_stop::{
+    emergency_stop_all, emergency_stop_all_from, emergency_stop_on_signal,
+    emergency_stop_on_signal_from, EStop, EStopOptions,
+};
+
 mod large_motor;
 pub use self::large_motor::LargeMotor;
 
+mod linear_actuator;
+pub use self::linear_actuator::LinearActuator;
+
 mod medium_motor;
 pub use self::medium_motor::MediumMotor;
 
+mod run_handle;
+pub use self::run_handle::{RunHandle, RunOutcome};
+
 mod tacho_motor;
 pub use self::tacho_motor::TachoMotor;
 
-use crate::{port_constants, Port};
+mod wedo_motor;
+pub use self::wedo_motor::WedoMotor;
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{port_constants, Ev3Error, Port};
 
 /// EV3 ports `outA` to `outD`
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MotorPort {
     /// EV3 `outA` port
     OutA,
@@ -32,6 +54,24 @@ pub enum MotorPort {
 }
 
 impl MotorPort {
+    /// All motor ports.
+    pub const ALL: [MotorPort; 4] = [
+        MotorPort::OutA,
+        MotorPort::OutB,
+        MotorPort::OutC,
+        MotorPort::OutD,
+    ];
+
+    /// Returns the EV3 name of the port, e.g. `outA`, on every platform.
+    pub fn name(&self) -> &'static str {
+        match self {
+            MotorPort::OutA => "outA",
+            MotorPort::OutB => "outB",
+            MotorPort::OutC => "outC",
+            MotorPort::OutD => "outD",
+        }
+    }
+
     /// Try to format a device name path to a  port name.
     pub fn format_name(name: &str) -> String {
         match name {
@@ -54,3 +94,34 @@ impl Port for MotorPort {
         }
     }
 }
+
+impl fmt::Display for MotorPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for MotorPort {
+    type Err = Ev3Error;
+
+    /// Parses `outA`, `A` or the address of the port (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let letter = s
+            .get(..3)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("out"))
+            .map_or(s, |_| &s[3..]);
+
+        MotorPort::ALL
+            .into_iter()
+            .find(|port| {
+                port.name()[3..].eq_ignore_ascii_case(letter)
+                    || port.address().eq_ignore_ascii_case(s)
+            })
+            .ok_or_else(|| Ev3Error::InvalidPort {
+                value: s.to_owned(),
+                expected: "outA to outD".to_owned(),
+                path: None,
+            })
+    }
+}
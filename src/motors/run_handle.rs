@@ -0,0 +1,62 @@
+//! Completion handle for motor commands that end on their own.
+
+use std::time::Duration;
+
+use crate::{wait, Ev3Result, ReadOnlyAttribute};
+
+/// Reason why `RunHandle::wait()` returned.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RunOutcome {
+    /// The motor finished the command and the `running` state was cleared.
+    Completed,
+    /// The motor stalled before the command finished.
+    Stalled,
+    /// The timeout was reached while the motor was still running.
+    TimedOut,
+}
+
+/// Handle to a running motor command like `run-timed`.
+///
+/// Can be used to wait for the command to complete, including an early return if the motor stalls.
+#[derive(Debug, Clone)]
+pub struct RunHandle {
+    state: ReadOnlyAttribute,
+}
+
+impl RunHandle {
+    /// Create a new handle that observes the given `state` attribute of a motor.
+    pub(crate) fn new(state: ReadOnlyAttribute) -> Self {
+        Self { state }
+    }
+
+    /// Returns `true` while the motor is still running.
+    pub fn is_running(&self) -> Ev3Result<bool> {
+        Ok(self.state.get_vec()?.iter().any(|s| s == "running"))
+    }
+
+    /// Wait until the motor has stopped running, has stalled or the `timeout` is reached.
+    ///
+    /// Uses `wait::wait()`, so motors of backends without sysfs files are checked periodically.
+    /// If the `timeout` is `None` it will wait an infinite time.
+    pub fn wait(&self, timeout: Option<Duration>) -> Ev3Result<RunOutcome> {
+        let cond = || {
+            self.state
+                .get_vec()
+                .map(|state| {
+                    state.iter().all(|s| s != "running") || state.iter().any(|s| s == "stalled")
+                })
+                .unwrap_or(true)
+        };
+        wait::wait(self.state.get_raw_fd(), cond, timeout);
+
+        let state = self.state.get_vec()?;
+        if state.iter().any(|s| s == "stalled") {
+            Ok(RunOutcome::Stalled)
+        } else if state.iter().any(|s| s == "running") {
+            Ok(RunOutcome::TimedOut)
+        } else {
+            Ok(RunOutcome::Completed)
+        }
+    }
+}
@@ -21,12 +21,12 @@ macro_rules! servo_motor {
 
         /// Returns the current polarity of the motor.
         pub fn get_polarity(&self) -> Ev3Result<String> {
-            self.get_attribute("polarity").get()
+            self.get_attribute("polarity")?.get()
         }
 
         /// Sets the polarity of the motor.
         pub fn set_polarity(&self, polarity: &str) -> Ev3Result<()> {
-            self.get_attribute("polarity").set_str_slice(polarity)
+            self.get_attribute("polarity")?.set_str_slice(polarity)
         }
 
         /// Returns the current max pulse setpoint.
@@ -35,7 +35,7 @@ macro_rules! servo_motor {
         /// Default value is 2400. Valid values are 2300 to 2700.
         /// You must write to the position_sp attribute for changes to this attribute to take effect.
         pub fn get_max_pulse_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("max_pulse_sp").get()
+            self.get_attribute("max_pulse_sp")?.get()
         }
 
         /// Sets the max pulse setpoint.
@@ -44,7 +44,7 @@ macro_rules! servo_motor {
         /// Default value is 2400. Valid values are 2300 to 2700.
         /// You must write to the position_sp attribute for changes to this attribute to take effect.
         pub fn set_max_pulse_sp(&self, max_pulse_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("max_pulse_sp").set(max_pulse_sp)
+            self.get_attribute("max_pulse_sp")?.set(max_pulse_sp)
         }
 
         /// Returns the current mid pulse setpoint.
@@ -54,7 +54,7 @@ macro_rules! servo_motor {
         /// Valid values are 300 to 700.
         ///  You must write to the position_sp attribute for changes to this attribute to take effect.
         pub fn get_mid_pulse_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("mid_pulse_sp").get()
+            self.get_attribute("mid_pulse_sp")?.get()
         }
 
         /// Sets the mid pulse setpoint.
@@ -64,7 +64,7 @@ macro_rules! servo_motor {
         /// Valid values are 300 to 700.
         ///  You must write to the position_sp attribute for changes to this attribute to take effect.
         pub fn set_mid_pulse_sp(&self, max_pulse_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("mid_pulse_sp").set(max_pulse_sp)
+            self.get_attribute("mid_pulse_sp")?.set(max_pulse_sp)
         }
 
         /// Returns the current min pulse setpoint.
@@ -73,7 +73,7 @@ macro_rules! servo_motor {
         /// Default value is 600. Valid values are 300 to 700.
         /// You must write to the position_sp attribute for changes to this attribute to take effect.
         pub fn get_min_pulse_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("min_pulse_sp").get()
+            self.get_attribute("min_pulse_sp")?.get()
         }
         /// Sets the min pulse setpoint.
         /// Used to set the pulse size in milliseconds for the signal
@@ -81,14 +81,14 @@ macro_rules! servo_motor {
         /// Default value is 600. Valid values are 300 to 700.
         /// You must write to the position_sp attribute for changes to this attribute to take effect.
         pub fn set_min_pulse_sp(&self, min_pulse_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("min_pulse_sp").set(min_pulse_sp)
+            self.get_attribute("min_pulse_sp")?.set(min_pulse_sp)
         }
 
         /// Returns the current target position for the `run-to-abs-pos` and `run-to-rel-pos` commands. Units are in tacho counts.
         /// You can use the value returned by `counts_per_rot` to convert tacho counts to/from rotations or degrees.
         /// The range is -2,147,483,648 and +2,147,483,647 tachometer counts (32-bit signed integer).
         pub fn get_position_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("position_sp").get()
+            self.get_attribute("position_sp")?.get()
         }
 
         /// Sets the target position for the `run-to-abs-pos` and `run-to-rel-pos` commands.
@@ -96,7 +96,7 @@ macro_rules! servo_motor {
         /// You can use the value returned by `counts_per_rot` to convert tacho counts to/from rotations or degrees.
         /// The range is -2,147,483,648 and +2,147,483,647 tachometer counts (32-bit signed integer).
         pub fn set_position_sp(&self, position_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("position_sp").set(position_sp)
+            self.get_attribute("position_sp")?.set(position_sp)
         }
 
         /// Returns the current the rate_sp at which the servo travels from 0 to 100.0%
@@ -115,7 +115,7 @@ macro_rules! servo_motor {
         /// In continuous rotation servos, this value will affect the
         /// rate_sp at which the speed ramps up or down.
         pub fn get_rate_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("rate_sp").get()
+            self.get_attribute("rate_sp")?.get()
         }
 
         /// Sets the rate_sp at which the servo travels from 0 to 100.0%
@@ -134,12 +134,12 @@ macro_rules! servo_motor {
         /// In continuous rotation servos, this value will affect the
         /// rate_sp at which the speed ramps up or down.
         pub fn set_rate_sp(&self, rate_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("rate_sp").set(rate_sp)
+            self.get_attribute("rate_sp")?.set(rate_sp)
         }
 
         /// Returns a list of state flags.
         pub fn get_state(&self) -> Ev3Result<Vec<String>> {
-            self.get_attribute("state").get_vec()
+            self.get_read_only_attribute("state")?.get_vec()
         }
 
         /// Power is being sent to the motor.
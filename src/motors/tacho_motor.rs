@@ -4,9 +4,9 @@
 
 use std::time::Duration;
 
-use crate::{Ev3Error, Ev3Result};
+use crate::{Device, Ev3Error, Ev3Result};
 
-use super::{LargeMotor, MediumMotor, MotorPort};
+use super::{LargeMotor, MediumMotor, MotorPort, RunHandle};
 
 #[derive(Debug, Clone)]
 enum TachoMotorInner {
@@ -42,6 +42,7 @@ impl TachoMotor {
 
         Err(Ev3Error::InternalError {
             msg: "Could not find a tacho motor at requested port!".to_owned(),
+            path: None,
         })
     }
 
@@ -63,6 +64,7 @@ impl TachoMotor {
 
         Err(Ev3Error::InternalError {
             msg: "Could not find a tacho motor at requested port!".to_owned(),
+            path: None,
         })
     }
 
@@ -100,6 +102,22 @@ impl TachoMotor {
         }
     }
 
+    /// Checks if the motor is still connected.
+    pub fn is_connected(&self) -> bool {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.is_connected(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.is_connected(),
+        }
+    }
+
+    /// Finds the motor again after it was unplugged and re-plugged.
+    pub fn reconnect(&self) -> Ev3Result<()> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.reconnect(),
+            TachoMotorInner::MediumMotor { ref motor } => motor.reconnect(),
+        }
+    }
+
     /// Causes the motor to run until another command is sent.
     pub const COMMAND_RUN_FOREVER: &'static str = "run-forever";
 
@@ -751,6 +769,15 @@ impl TachoMotor {
         }
     }
 
+    /// Run the motor with `speed_sp` for the given `duration`
+    /// and return a handle to wait for the completion of the command.
+    pub fn start_timed(&self, duration: Duration, speed_sp: i32) -> Ev3Result<RunHandle> {
+        match self.inner {
+            TachoMotorInner::LargeMotor { ref motor } => motor.start_timed(duration, speed_sp),
+            TachoMotorInner::MediumMotor { ref motor } => motor.start_timed(duration, speed_sp),
+        }
+    }
+
     /// Stop any of the run commands before they are complete using the command specified by `stop_action`.
     pub fn stop(&self) -> Ev3Result<()> {
         match self.inner {
@@ -96,7 +96,7 @@ macro_rules! tacho_motor {
         /// # }
         /// ```
         pub fn get_count_per_rot(&self) -> Ev3Result<i32> {
-            self.get_attribute("count_per_rot").get()
+            self.get_read_only_attribute("count_per_rot")?.get()
         }
 
         /// Returns the number of tacho counts in one meter of travel of the motor.
@@ -105,7 +105,7 @@ macro_rules! tacho_motor {
         /// so you can use this value to convert from distance to tacho counts.
         /// (linear motors only)
         pub fn get_count_per_m(&self) -> Ev3Result<i32> {
-            self.get_attribute("count_per_m").get()
+            self.get_read_only_attribute("count_per_m")?.get()
         }
 
         /// Returns the number of tacho counts in the full travel of the motor.
@@ -114,7 +114,7 @@ macro_rules! tacho_motor {
         /// you can use this value to calculate the maximum travel distance of the motor.
         /// (linear motors only)
         pub fn get_full_travel_count(&self) -> Ev3Result<i32> {
-            self.get_attribute("full_travel_count").get()
+            self.get_read_only_attribute("full_travel_count")?.get()
         }
 
         /// Returns the current duty cycle of the motor. Units are percent.
@@ -143,7 +143,7 @@ macro_rules! tacho_motor {
         /// # Ok(())
         /// # }
         pub fn get_duty_cycle(&self) -> Ev3Result<i32> {
-            self.get_attribute("duty_cycle").get()
+            self.get_read_only_attribute("duty_cycle")?.get()
         }
 
         /// Returns the current duty cycle setpoint of the motor.
@@ -170,7 +170,7 @@ macro_rules! tacho_motor {
         /// # Ok(())
         /// # }
         pub fn get_duty_cycle_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("duty_cycle_sp").get()
+            self.get_attribute("duty_cycle_sp")?.get()
         }
 
         /// Sets the duty cycle setpoint of the motor.
@@ -202,17 +202,17 @@ macro_rules! tacho_motor {
         /// # Ok(())
         /// # }
         pub fn set_duty_cycle_sp(&self, duty_cycle: i32) -> Ev3Result<()> {
-            self.get_attribute("duty_cycle_sp").set(duty_cycle)
+            self.get_attribute("duty_cycle_sp")?.set(duty_cycle)
         }
 
         /// Returns the current polarity of the motor.
         pub fn get_polarity(&self) -> Ev3Result<String> {
-            self.get_attribute("polarity").get()
+            self.get_attribute("polarity")?.get()
         }
 
         /// Sets the polarity of the motor.
         pub fn set_polarity(&self, polarity: &str) -> Ev3Result<()> {
-            self.get_attribute("polarity").set_str_slice(polarity)
+            self.get_attribute("polarity")?.set_str_slice(polarity)
         }
 
         /// Returns the current position of the motor in pulses of the rotary encoder.
@@ -242,7 +242,7 @@ macro_rules! tacho_motor {
         /// # }
         /// ```
         pub fn get_position(&self) -> Ev3Result<i32> {
-            self.get_attribute("position").get()
+            self.get_attribute("position")?.get()
         }
 
         /// Sets the current position of the motor in pulses of the rotary encoder.
@@ -270,37 +270,37 @@ macro_rules! tacho_motor {
         /// # }
         /// ```
         pub fn set_position(&self, position: i32) -> Ev3Result<()> {
-            self.get_attribute("position").set(position)
+            self.get_attribute("position")?.set(position)
         }
 
         /// Returns the proportional pub constant for the position PID.
         pub fn get_hold_pid_kp(&self) -> Ev3Result<f32> {
-            self.get_attribute("hold_pid/Kp").get()
+            self.get_attribute("hold_pid/Kp")?.get()
         }
 
         /// Sets the proportional pub constant for the position PID.
         pub fn set_hold_pid_kp(&self, kp: f32) -> Ev3Result<()> {
-            self.get_attribute("hold_pid/Kp").set(kp)
+            self.get_attribute("hold_pid/Kp")?.set(kp)
         }
 
         /// Returns the integral pub constant for the position PID.
         pub fn get_hold_pid_ki(&self) -> Ev3Result<f32> {
-            self.get_attribute("hold_pid/Ki").get()
+            self.get_attribute("hold_pid/Ki")?.get()
         }
 
         /// Sets the integral pub constant for the position PID.
         pub fn set_hold_pid_ki(&self, ki: f32) -> Ev3Result<()> {
-            self.get_attribute("hold_pid/Ki").set(ki)
+            self.get_attribute("hold_pid/Ki")?.set(ki)
         }
 
         /// Returns the derivative pub constant for the position PID.
         pub fn get_hold_pid_kd(&self) -> Ev3Result<f32> {
-            self.get_attribute("hold_pid/Kd").get()
+            self.get_attribute("hold_pid/Kd")?.get()
         }
 
         /// Sets the derivative pub constant for the position PID.
         pub fn set_hold_pid_kd(&self, kd: f32) -> Ev3Result<()> {
-            self.get_attribute("hold_pid/Kd").set(kd)
+            self.get_attribute("hold_pid/Kd")?.set(kd)
         }
 
         /// Returns the maximum value that is accepted by the `speed_sp` attribute.
@@ -309,7 +309,7 @@ macro_rules! tacho_motor {
         /// Note: The actual maximum obtainable speed will be less than this
         /// and will depend on battery voltage and mechanical load on the motor.
         pub fn get_max_speed(&self) -> Ev3Result<i32> {
-            self.get_attribute("max_speed").get()
+            self.get_read_only_attribute("max_speed")?.get()
         }
 
         /// Returns the current target position for the `run-to-abs-pos` and `run-to-rel-pos` commands.
@@ -319,7 +319,7 @@ macro_rules! tacho_motor {
         ///
         /// The range is -2,147,483,648 and +2,147,483,647 tachometer counts (32-bit signed integer).
         pub fn get_position_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("position_sp").get()
+            self.get_attribute("position_sp")?.get()
         }
 
         /// Sets the target position for the `run-to-abs-pos` and `run-to-rel-pos` commands.
@@ -357,7 +357,7 @@ macro_rules! tacho_motor {
         /// # }
         /// ```
         pub fn set_position_sp(&self, position_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("position_sp").set(position_sp)
+            self.get_attribute("position_sp")?.set(position_sp)
         }
 
         /// Returns the current motor speed in tacho counts per second.
@@ -365,7 +365,7 @@ macro_rules! tacho_motor {
         /// Note, this is not necessarily degrees (although it is for LEGO motors).
         /// Use the `count_per_rot` attribute to convert this value to RPM or deg/sec.
         pub fn get_speed(&self) -> Ev3Result<i32> {
-            self.get_attribute("speed").get()
+            self.get_read_only_attribute("speed")?.get()
         }
 
         /// Returns the target speed in tacho counts per second used for all run-* commands except run-direct.
@@ -375,7 +375,7 @@ macro_rules! tacho_motor {
         /// Use the `count_per_rot` attribute to convert RPM or deg/sec to tacho counts per second.
         /// Use the `count_per_m` attribute to convert m/s to tacho counts per second.
         pub fn get_speed_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("speed_sp").get()
+            self.get_attribute("speed_sp")?.get()
         }
 
         /// Sets the target speed in tacho counts per second used for all run-* commands except run-direct.
@@ -385,7 +385,7 @@ macro_rules! tacho_motor {
         /// Use the `count_per_rot` attribute to convert RPM or deg/sec to tacho counts per second.
         /// Use the `count_per_m` attribute to convert m/s to tacho counts per second.
         pub fn set_speed_sp(&self, speed_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("speed_sp").set(speed_sp)
+            self.get_attribute("speed_sp")?.set(speed_sp)
         }
 
         /// Returns the current ramp up setpoint.
@@ -395,7 +395,7 @@ macro_rules! tacho_motor {
         /// The actual ramp time is the ratio of the difference between the speed_sp
         /// and the current speed and max_speed multiplied by ramp_up_sp. Values must not be negative.
         pub fn get_ramp_up_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("ramp_up_sp").get()
+            self.get_attribute("ramp_up_sp")?.get()
         }
 
         /// Sets the ramp up setpoint.
@@ -405,7 +405,7 @@ macro_rules! tacho_motor {
         /// The actual ramp time is the ratio of the difference between the speed_sp
         /// and the current speed and max_speed multiplied by ramp_up_sp. Values must not be negative.
         pub fn set_ramp_up_sp(&self, ramp_up_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("ramp_up_sp").set(ramp_up_sp)
+            self.get_attribute("ramp_up_sp")?.set(ramp_up_sp)
         }
 
         /// Returns the current ramp down setpoint.
@@ -415,7 +415,7 @@ macro_rules! tacho_motor {
         /// The actual ramp time is the ratio of the difference between the speed_sp
         /// and the current speed and 0 multiplied by ramp_down_sp. Values must not be negative.
         pub fn get_ramp_down_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("ramp_down_sp").get()
+            self.get_attribute("ramp_down_sp")?.get()
         }
 
         /// Sets the ramp down setpoint.
@@ -425,75 +425,76 @@ macro_rules! tacho_motor {
         /// The actual ramp time is the ratio of the difference between the speed_sp
         /// and the current speed and 0 multiplied by ramp_down_sp. Values must not be negative.
         pub fn set_ramp_down_sp(&self, ramp_down_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("ramp_down_sp").set(ramp_down_sp)
+            self.get_attribute("ramp_down_sp")?.set(ramp_down_sp)
         }
 
         /// Returns the proportional pub constant for the speed regulation PID.
         pub fn get_speed_pid_kp(&self) -> Ev3Result<f32> {
-            self.get_attribute("speed_pid/Kp").get()
+            self.get_attribute("speed_pid/Kp")?.get()
         }
 
         /// Sets the proportional pub constant for the speed regulation PID.
         pub fn set_speed_pid_kp(&self, kp: f32) -> Ev3Result<()> {
-            self.get_attribute("speed_pid/Kp").set(kp)
+            self.get_attribute("speed_pid/Kp")?.set(kp)
         }
 
         /// Returns the integral pub constant for the speed regulation PID.
         pub fn get_speed_pid_ki(&self) -> Ev3Result<f32> {
-            self.get_attribute("speed_pid/Ki").get()
+            self.get_attribute("speed_pid/Ki")?.get()
         }
 
         /// Sets the integral pub constant for the speed regulation PID.
         pub fn set_speed_pid_ki(&self, ki: f32) -> Ev3Result<()> {
-            self.get_attribute("speed_pid/Ki").set(ki)
+            self.get_attribute("speed_pid/Ki")?.set(ki)
         }
 
         /// Returns the derivative pub constant for the speed regulation PID.
         pub fn get_speed_pid_kd(&self) -> Ev3Result<f32> {
-            self.get_attribute("speed_pid/Kd").get()
+            self.get_attribute("speed_pid/Kd")?.get()
         }
 
         /// Sets the derivative pub constant for the speed regulation PID.
         pub fn set_speed_pid_kd(&self, kd: f32) -> Ev3Result<()> {
-            self.get_attribute("speed_pid/Kd").set(kd)
+            self.get_attribute("speed_pid/Kd")?.set(kd)
         }
 
         /// Returns a list of state flags.
         pub fn get_state(&self) -> Ev3Result<Vec<String>> {
-            self.get_attribute("state").get_vec()
+            self.get_read_only_attribute("state")?.get_vec()
         }
 
         /// Returns the current stop action.
         ///
         /// The value determines the motors behavior when command is set to stop.
         pub fn get_stop_action(&self) -> Ev3Result<String> {
-            self.get_attribute("stop_action").get()
+            self.get_attribute("stop_action")?.get()
         }
 
         /// Sets the stop action.
         ///
         /// The value determines the motors behavior when command is set to stop.
         pub fn set_stop_action(&self, stop_action: &str) -> Ev3Result<()> {
-            self.get_attribute("stop_action").set_str_slice(stop_action)
+            self.get_attribute("stop_action")?
+                .set_str_slice(stop_action)
         }
 
         /// Returns a list of stop actions supported by the motor controller.
         pub fn get_stop_actions(&self) -> Ev3Result<Vec<String>> {
-            self.get_attribute("stop_actions").get_vec()
+            self.get_read_only_attribute("stop_actions")?.get_vec()
         }
 
         /// Returns the current amount of time the motor will run when using the run-timed command.
         ///
         /// Units are in milliseconds. Values must not be negative.
         pub fn get_time_sp(&self) -> Ev3Result<i32> {
-            self.get_attribute("time_sp").get()
+            self.get_attribute("time_sp")?.get()
         }
 
         /// Sets the amount of time the motor will run when using the run-timed command.
         ///
         /// Units are in milliseconds. Values must not be negative.
         pub fn set_time_sp(&self, time_sp: i32) -> Ev3Result<()> {
-            self.get_attribute("time_sp").set(time_sp)
+            self.get_attribute("time_sp")?.set(time_sp)
         }
 
         /// Runs the motor using the duty cycle specified by `duty_cycle_sp`.
@@ -540,6 +541,39 @@ macro_rules! tacho_motor {
             self.set_command(Self::COMMAND_RUN_TIMED)
         }
 
+        /// Run the motor with `speed_sp` for the given `duration`
+        /// and return a handle to wait for the completion of the command.
+        ///
+        /// # Example
+        ///
+        /// ```no_run
+        /// use ev3dev_lang_rust::motors::{LargeMotor, RunOutcome};
+        /// use std::time::Duration;
+        ///
+        /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+        /// // Init a tacho motor.
+        /// let motor = LargeMotor::find()?;
+        ///
+        /// let handle = motor.start_timed(Duration::from_secs(2), 500)?;
+        ///
+        /// if handle.wait(None)? == RunOutcome::Stalled {
+        ///     println!("Motor is blocked!");
+        /// }
+        /// # Ok(())
+        /// # }
+        /// ```
+        pub fn start_timed(
+            &self,
+            duration: Duration,
+            speed_sp: i32,
+        ) -> Ev3Result<$crate::motors::RunHandle> {
+            self.set_speed_sp(speed_sp)?;
+            self.run_timed(Some(duration))?;
+            Ok($crate::motors::RunHandle::new(
+                self.get_read_only_attribute("state")?,
+            ))
+        }
+
         /// Stop any of the run commands before they are complete using the command specified by `stop_action`.
         pub fn stop(&self) -> Ev3Result<()> {
             self.set_command(Self::COMMAND_STOP)
@@ -594,6 +628,7 @@ macro_rules! tacho_motor {
         /// Wait until condition `cond` returns true or the `timeout` is reached.
         ///
         /// The condition is checked when to the `state` attribute has changed.
+        /// Motors of backends without sysfs files, e.g. `MockEv3` or `RemoteEv3`, are checked periodically instead.
         /// If the `timeout` is `None` it will wait an infinite time.
         ///
         /// # Examples
@@ -624,7 +659,11 @@ macro_rules! tacho_motor {
         where
             F: Fn() -> bool,
         {
-            let fd = self.get_attribute("state").get_raw_fd();
+            // A disconnected motor will never fulfill the condition.
+            let fd = match self.get_read_only_attribute("state") {
+                Ok(state) => state.get_raw_fd(),
+                Err(_) => return false,
+            };
             wait::wait(fd, cond, timeout)
         }
 
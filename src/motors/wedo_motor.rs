@@ -0,0 +1,32 @@
+use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result, WedoPort};
+use std::time::Duration;
+
+/// LEGO WeDo motor (45303) on a WeDo USB hub.
+///
+/// The motor has no encoder and is controlled by the duty cycle.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::WedoMotor;
+/// use ev3dev_lang_rust::WedoPort;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let motor = WedoMotor::get(WedoPort::A)?;
+/// motor.set_duty_cycle_sp(60)?;
+/// motor.run_direct()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Device)]
+pub struct WedoMotor {
+    driver: Driver,
+}
+
+impl WedoMotor {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    findable!("dc-motor", ["wedo-motor"], WedoPort, "WedoMotor", "wedo");
+    dc_motor!();
+}
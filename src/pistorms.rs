@@ -0,0 +1,168 @@
+//! PiStorms specific features
+
+use crate::led::{blink_lit, find_led, set_trigger_all};
+use crate::{Driver, Ev3Result, LedChannel, Trigger};
+
+/// Color type (red, green, blue).
+pub type Color = (u8, u8, u8);
+
+/// The rgb led's of the PiStorms banks. The left led belongs to bank B, the right led to bank A.
+#[derive(Debug, Clone)]
+pub struct Led {
+    left_red: LedChannel,
+    left_green: LedChannel,
+    left_blue: LedChannel,
+    right_red: LedChannel,
+    right_green: LedChannel,
+    right_blue: LedChannel,
+}
+
+impl Led {
+    /// Led off.
+    pub const COLOR_OFF: Color = (0, 0, 0);
+
+    /// Led color red.
+    pub const COLOR_RED: Color = (255, 0, 0);
+
+    /// Led color green.
+    pub const COLOR_GREEN: Color = (0, 255, 0);
+
+    /// Led color blue.
+    pub const COLOR_BLUE: Color = (0, 0, 255);
+
+    /// Led color yellow.
+    pub const COLOR_YELLOW: Color = (255, 255, 0);
+
+    /// Led color cyan.
+    pub const COLOR_CYAN: Color = (0, 255, 255);
+
+    /// Led color magenta.
+    pub const COLOR_MAGENTA: Color = (255, 0, 255);
+
+    /// Led color white.
+    pub const COLOR_WHITE: Color = (255, 255, 255);
+
+    /// Create a new instance of the `Led` struct.
+    pub fn new() -> Ev3Result<Led> {
+        let names = Driver::find_names("leds")?;
+        let find = |bank: &str, color: &str| find_led(&names, &[&["pistorms", bank, color]]);
+
+        Ok(Led {
+            left_red: find("BB", "red")?,
+            left_green: find("BB", "green")?,
+            left_blue: find("BB", "blue")?,
+            right_red: find("BA", "red")?,
+            right_green: find("BA", "green")?,
+            right_blue: find("BA", "blue")?,
+        })
+    }
+
+    /// Returns all leds in `/sys/class/leds/`, including the leds of this struct.
+    pub fn list() -> Ev3Result<Vec<LedChannel>> {
+        LedChannel::list()
+    }
+
+    /// Returns the red part of the left led.
+    pub fn left_red(&self) -> &LedChannel {
+        &self.left_red
+    }
+
+    /// Returns the green part of the left led.
+    pub fn left_green(&self) -> &LedChannel {
+        &self.left_green
+    }
+
+    /// Returns the blue part of the left led.
+    pub fn left_blue(&self) -> &LedChannel {
+        &self.left_blue
+    }
+
+    /// Returns the red part of the right led.
+    pub fn right_red(&self) -> &LedChannel {
+        &self.right_red
+    }
+
+    /// Returns the green part of the right led.
+    pub fn right_green(&self) -> &LedChannel {
+        &self.right_green
+    }
+
+    /// Returns the blue part of the right led.
+    pub fn right_blue(&self) -> &LedChannel {
+        &self.right_blue
+    }
+
+    /// Returns the current color value of the left led.
+    pub fn get_left_color(&self) -> Ev3Result<Color> {
+        Ok((
+            self.left_red.get_brightness()?,
+            self.left_green.get_brightness()?,
+            self.left_blue.get_brightness()?,
+        ))
+    }
+
+    /// Sets the color value of the left led.
+    pub fn set_left_color(&self, color: Color) -> Ev3Result<()> {
+        self.left_red.set_brightness(color.0)?;
+        self.left_green.set_brightness(color.1)?;
+        self.left_blue.set_brightness(color.2)
+    }
+
+    /// Returns the current color value of the right led.
+    pub fn get_right_color(&self) -> Ev3Result<Color> {
+        Ok((
+            self.right_red.get_brightness()?,
+            self.right_green.get_brightness()?,
+            self.right_blue.get_brightness()?,
+        ))
+    }
+
+    /// Sets the color value of the right led.
+    pub fn set_right_color(&self, color: Color) -> Ev3Result<()> {
+        self.right_red.set_brightness(color.0)?;
+        self.right_green.set_brightness(color.1)?;
+        self.right_blue.set_brightness(color.2)
+    }
+
+    /// Returns the color value of both leds or `None` if they are different.
+    pub fn get_color(&self) -> Ev3Result<Option<Color>> {
+        let left = self.get_left_color()?;
+        let right = self.get_right_color()?;
+
+        if left == right {
+            Ok(Some(left))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Sets the color value of both leds.
+    pub fn set_color(&self, color: Color) -> Ev3Result<()> {
+        self.set_left_color(color)?;
+        self.set_right_color(color)
+    }
+
+    /// Returns the red, green and blue parts of both leds.
+    fn channels(&self) -> [&LedChannel; 6] {
+        [
+            &self.left_red,
+            &self.left_green,
+            &self.left_blue,
+            &self.right_red,
+            &self.right_green,
+            &self.right_blue,
+        ]
+    }
+
+    /// Sets the kernel trigger of both leds.
+    ///
+    /// Use `Trigger::None` to stop blinking.
+    pub fn set_trigger(&self, trigger: &Trigger) -> Ev3Result<()> {
+        set_trigger_all(&self.channels(), trigger)
+    }
+
+    /// Blinks both leds with their current color. The blinking is done by the kernel.
+    pub fn set_blink(&self, on_ms: u32, off_ms: u32) -> Ev3Result<()> {
+        blink_lit(&self.channels(), on_ms, off_ms)
+    }
+}
@@ -1,73 +1,213 @@
 //! An interface to read data from the system’s power_supply class.
-//! Uses the built-in legoev3-battery if none is specified.
+//! Uses the built-in battery of the brick if none is specified.
 
-use std::{fs, path::Path};
+use std::fmt;
+use std::path::Path;
 
 use crate::driver::DRIVER_PATH;
-use crate::utils::OrErr;
 use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result};
 
+/// Names of the built-in batteries of the supported platforms.
+const BRICK_BATTERIES: [&str; 4] = [
+    "lego-ev3-battery",
+    "brickpi-battery",
+    "brickpi3-battery",
+    "pistorms-battery",
+];
+
+/// Battery technology as reported by the `technology` attribute.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Technology {
+    /// No rechargeable battery, e.g. AA batteries in the EV3.
+    Unknown,
+    /// Nickel-metal hydride
+    NiMH,
+    /// Lithium-ion, e.g. the EV3 rechargeable battery.
+    LiIon,
+    /// Lithium polymer
+    LiPoly,
+    /// Lithium iron phosphate
+    LiFe,
+    /// Nickel-cadmium
+    NiCd,
+    /// Lithium manganese
+    LiMn,
+    /// Any other value of the `technology` attribute.
+    Other(String),
+}
+
+impl Technology {
+    /// Returns the name as used in the `technology` attribute.
+    pub fn name(&self) -> &str {
+        match self {
+            Technology::Unknown => "Unknown",
+            Technology::NiMH => "NiMH",
+            Technology::LiIon => "Li-ion",
+            Technology::LiPoly => "Li-poly",
+            Technology::LiFe => "LiFe",
+            Technology::NiCd => "NiCd",
+            Technology::LiMn => "LiMn",
+            Technology::Other(name) => name,
+        }
+    }
+
+    /// Returns the technology with the given name.
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "Unknown" => Technology::Unknown,
+            "NiMH" => Technology::NiMH,
+            "Li-ion" => Technology::LiIon,
+            "Li-poly" => Technology::LiPoly,
+            "LiFe" => Technology::LiFe,
+            "NiCd" => Technology::NiCd,
+            "LiMn" => Technology::LiMn,
+            name => Technology::Other(name.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for Technology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 /// An interface to read data from the system’s power_supply class.
-/// Uses the built-in legoev3-battery if none is specified.
+/// Uses the built-in battery of the brick if none is specified.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::PowerSupply;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// for supply in PowerSupply::list()? {
+///     println!("{}: {:.2} V", supply.get_name(), supply.voltage_volts()?);
+/// }
+///
+/// let battery = PowerSupply::new()?;
+/// println!("Technology: {}", battery.technology()?);
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug, Clone, Device)]
 pub struct PowerSupply {
     driver: Driver,
 }
 
 impl PowerSupply {
-    /// Create a new instance of `PowerSupply`.
+    fn with_name(name: &str) -> Self {
+        PowerSupply {
+            driver: Driver::new("power_supply", name),
+        }
+    }
+
+    /// Create a new instance of `PowerSupply` for the built-in battery of the brick.
+    ///
+    /// Supports the batteries of the EV3, BrickPi, BrickPi3 and PiStorms.
+    /// Falls back to the first power supply of type `Battery`, e.g. on a laptop.
     pub fn new() -> Ev3Result<PowerSupply> {
-        let paths = fs::read_dir(Path::new(DRIVER_PATH).join("power_supply"))?;
+        let names = Driver::find_names("power_supply")?;
 
-        for path in paths {
-            let file_name = path?.file_name();
-            let name = file_name.to_str().or_err()?;
+        if let Some(name) = names
+            .iter()
+            .find(|name| BRICK_BATTERIES.iter().any(|b| name.contains(b)))
+        {
+            return Ok(PowerSupply::with_name(name));
+        }
 
-            if name.contains("ev3-battery") {
-                return Ok(PowerSupply {
-                    driver: Driver::new("power_supply", name),
-                });
+        for name in &names {
+            let supply = PowerSupply::with_name(name);
+            if supply.get_type().is_ok_and(|t| t == "Battery") {
+                return Ok(supply);
             }
         }
 
         Err(Ev3Error::NotConnected {
             device: "power_supply".to_owned(),
             port: None,
+            path: Some(Path::new(DRIVER_PATH).join("power_supply")),
         })
     }
 
+    /// Returns all power supplies in `/sys/class/power_supply/`.
+    pub fn list() -> Ev3Result<Vec<PowerSupply>> {
+        Ok(Driver::find_names("power_supply")?
+            .iter()
+            .map(|name| PowerSupply::with_name(name))
+            .collect())
+    }
+
+    /// Returns the power supply `/sys/class/power_supply/{name}`.
+    ///
+    /// Returns `Ev3Error::NotConnected` if no such power supply exists.
+    pub fn find_by_name(name: &str) -> Ev3Result<PowerSupply> {
+        if Driver::find_names("power_supply")?
+            .iter()
+            .any(|n| n == name)
+        {
+            Ok(PowerSupply::with_name(name))
+        } else {
+            Err(Ev3Error::NotConnected {
+                device: name.to_owned(),
+                port: None,
+                path: Some(Path::new(DRIVER_PATH).join("power_supply")),
+            })
+        }
+    }
+
+    /// Returns the name of the power supply, e.g. `lego-ev3-battery`.
+    pub fn get_name(&self) -> String {
+        self.driver.get_name()
+    }
+
     /// Returns the battery current in microamps
     pub fn get_current_now(&self) -> Ev3Result<i32> {
-        self.get_attribute("current_now").get()
+        self.get_read_only_attribute("current_now")?.get()
     }
 
     /// Always returns System.
     pub fn get_scope(&self) -> Ev3Result<String> {
-        self.get_attribute("zscope").get()
+        self.get_read_only_attribute("zscope")?.get()
     }
 
     /// Returns Unknown or Li-ion depending on if the rechargeable battery is present.
     pub fn get_technology(&self) -> Ev3Result<String> {
-        self.get_attribute("technology").get()
+        self.get_read_only_attribute("technology")?.get()
     }
 
     /// Always returns Battery.
     pub fn get_type(&self) -> Ev3Result<String> {
-        self.get_attribute("type").get()
+        self.get_read_only_attribute("type")?.get()
     }
 
     /// Returns the nominal “full” battery voltage. The value returned depends on technology.
     pub fn get_voltage_max_design(&self) -> Ev3Result<i32> {
-        self.get_attribute("voltage_max_design").get()
+        self.get_read_only_attribute("voltage_max_design")?.get()
     }
 
     /// Returns the nominal “empty” battery voltage. The value returned depends on technology.
     pub fn get_voltage_min_design(&self) -> Ev3Result<i32> {
-        self.get_attribute("voltage_min_design").get()
+        self.get_read_only_attribute("voltage_min_design")?.get()
     }
 
     /// Returns the battery voltage in microvolts.
     pub fn get_voltage_now(&self) -> Ev3Result<i32> {
-        self.get_attribute("voltage_now").get()
+        self.get_read_only_attribute("voltage_now")?.get()
+    }
+
+    /// Returns the battery technology.
+    pub fn technology(&self) -> Ev3Result<Technology> {
+        Ok(Technology::from_name(&self.get_technology()?))
+    }
+
+    /// Returns the battery voltage in volts.
+    pub fn voltage_volts(&self) -> Ev3Result<f32> {
+        Ok(self.get_voltage_now()? as f32 / 1_000_000.0)
+    }
+
+    /// Returns the battery current in amps.
+    pub fn current_amps(&self) -> Ev3Result<f32> {
+        Ok(self.get_current_now()? as f32 / 1_000_000.0)
     }
 }
@@ -0,0 +1,201 @@
+//! Client backend that accesses the devices of a remote brick.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::{protocol_error, Outcome, Request, Response};
+use crate::driver::DRIVER_PATH;
+use crate::{Attribute, AttributeSource, Backend, DeviceDescriptor, Ev3Error, Ev3Result};
+
+#[derive(Debug)]
+struct Connection {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    next_id: u64,
+}
+
+/// Connection to a brick that runs a `remote::Server`.
+///
+/// `RemoteEv3` is a `Backend`, so all devices with `get_from()`, `find_from()` and `list_from()`
+/// constructors can be created on the remote brick and used like local devices.
+/// Every attribute access is a request to the server, so it is much slower than on the brick itself.
+///
+/// The connection is shared by all clones and all devices created from it.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+/// use ev3dev_lang_rust::remote::RemoteEv3;
+/// use ev3dev_lang_rust::sensors::GyroSensor;
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let ev3 = RemoteEv3::connect("ev3dev.local:6061")?;
+///
+/// let motor = LargeMotor::get_from(&ev3, MotorPort::OutA)?;
+/// let gyro = GyroSensor::find_from(&ev3)?;
+///
+/// motor.run_forever()?;
+/// while gyro.get_angle()? < 90 {}
+/// motor.stop()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RemoteEv3 {
+    connection: Arc<Mutex<Connection>>,
+}
+
+impl RemoteEv3 {
+    /// Connects to the server at the given address, e.g. `ev3dev.local:6061`.
+    pub fn connect(address: impl ToSocketAddrs) -> Ev3Result<Self> {
+        let stream = TcpStream::connect(address)?;
+        stream.set_nodelay(true)?;
+
+        Ok(RemoteEv3 {
+            connection: Arc::new(Mutex::new(Connection {
+                writer: stream.try_clone()?,
+                reader: BufReader::new(stream),
+                next_id: 1,
+            })),
+        })
+    }
+
+    /// Sends the `request` with the given `method` and returns its result.
+    fn request<T: DeserializeOwned>(&self, method: &str, request: Request) -> Ev3Result<T> {
+        let mut connection = self.connection.lock().unwrap();
+        let id = Value::from(connection.next_id);
+        connection.next_id += 1;
+
+        let request = Request {
+            id: id.clone(),
+            method: Some(method.to_owned()),
+            ..request
+        };
+        let request = serde_json::to_string(&request).map_err(protocol_error)?;
+        writeln!(connection.writer, "{request}")?;
+
+        let mut line = String::new();
+        if connection.reader.read_line(&mut line)? == 0 {
+            return Err(Ev3Error::InternalError {
+                msg: "Remote server closed the connection".to_owned(),
+                path: None,
+            });
+        }
+        drop(connection);
+
+        let response: Response<T> = serde_json::from_str(&line).map_err(protocol_error)?;
+        if response.id != id {
+            return Err(Ev3Error::InternalError {
+                msg: format!(
+                    "Unexpected response of the remote server: {}",
+                    line.trim_end()
+                ),
+                path: None,
+            });
+        }
+        match response.outcome {
+            Outcome::Result(result) => Ok(result),
+            Outcome::Error(error) => Err(Ev3Error::InternalError {
+                msg: format!("Remote error: {error}"),
+                path: None,
+            }),
+        }
+    }
+
+    /// Returns the descriptors of the devices of the given class or of all `SCAN_CLASSES` of the remote brick.
+    pub fn scan(&self, class_name: Option<&str>) -> Ev3Result<Vec<DeviceDescriptor>> {
+        self.request(
+            "list",
+            Request {
+                class: class_name.map(str::to_owned),
+                ..Request::default()
+            },
+        )
+    }
+
+    /// Stops all motors of the remote brick. See `motors::emergency_stop_all()`.
+    pub fn stop_all(&self, stop_action: &str) -> Ev3Result<()> {
+        self.request(
+            "stop_all",
+            Request {
+                stop_action: Some(stop_action.to_owned()),
+                ..Request::default()
+            },
+        )
+    }
+}
+
+impl Backend for RemoteEv3 {
+    fn find_names(&self, class_name: &str) -> Ev3Result<Vec<String>> {
+        Ok(self
+            .scan(Some(class_name))?
+            .into_iter()
+            .map(|descriptor| descriptor.name)
+            .collect())
+    }
+
+    fn open_attribute(
+        &self,
+        class_name: &str,
+        name: &str,
+        attribute_name: &str,
+    ) -> Ev3Result<Attribute> {
+        let path = Path::new(DRIVER_PATH)
+            .join(class_name)
+            .join(name)
+            .join(attribute_name);
+
+        Ok(Attribute::from_source(
+            &path,
+            Arc::new(RemoteAttribute {
+                ev3: self.clone(),
+                class_name: class_name.to_owned(),
+                name: name.to_owned(),
+                attribute_name: attribute_name.to_owned(),
+            }),
+        ))
+    }
+}
+
+/// Attribute of a device on the remote brick.
+#[derive(Debug)]
+struct RemoteAttribute {
+    ev3: RemoteEv3,
+    class_name: String,
+    name: String,
+    attribute_name: String,
+}
+
+impl RemoteAttribute {
+    /// Returns a request that addresses this attribute.
+    fn request(&self) -> Request {
+        Request {
+            class: Some(self.class_name.clone()),
+            name: Some(self.name.clone()),
+            attribute: Some(self.attribute_name.clone()),
+            ..Request::default()
+        }
+    }
+}
+
+impl AttributeSource for RemoteAttribute {
+    fn read(&self) -> Ev3Result<Vec<u8>> {
+        let value: String = self.ev3.request("get", self.request())?;
+        Ok(value.into_bytes())
+    }
+
+    fn write(&self, data: &[u8]) -> Ev3Result<()> {
+        self.ev3.request(
+            "set",
+            Request {
+                value: Some(String::from_utf8_lossy(data).into_owned()),
+                ..self.request()
+            },
+        )
+    }
+}
@@ -0,0 +1,101 @@
+//! Remote access to the devices of a brick over TCP.
+//!
+//! The `Server` (feature `remote-server`) runs on the brick and answers requests of a client,
+//! e.g. a laptop that runs the heavy computations or visualizes sensor values live.
+//! `RemoteEv3` (feature `remote-client`) is such a client and provides the remote devices
+//! with the same types as local devices.
+//!
+//! # Protocol
+//!
+//! Requests and responses are JSON objects, one per line. The optional `id` of a request
+//! is copied to the response. Every response contains either a `result` or an `error` message.
+//!
+//! | `method`   | Parameters                         | `result`                                  |
+//! |------------|------------------------------------|-------------------------------------------|
+//! | `list`     | `class` (optional)                 | Array of `{class, name, driver_name, address}` |
+//! | `get`      | `class`, `name`, `attribute`       | Attribute value as string                 |
+//! | `set`      | `class`, `name`, `attribute`, `value` | `null`                                 |
+//! | `stop_all` | `stop_action` (default: `coast`)   | `null`                                    |
+//!
+//! Only the devices of the `REMOTE_CLASSES` are exposed, requests for other classes like `gpio` fail.
+//! Without a `class`, `list` returns all devices of the `REMOTE_CLASSES`.
+//! Motors are controlled by setting their attributes, e.g. `speed_sp` and `command`.
+//!
+//! ```text
+//! > {"id":1,"method":"list","class":"tacho-motor"}
+//! < {"id":1,"result":[{"class":"tacho-motor","name":"motor0","driver_name":"lego-ev3-l-motor","address":"ev3-ports:outA"}]}
+//! > {"id":2,"method":"set","class":"tacho-motor","name":"motor0","attribute":"command","value":"run-forever"}
+//! < {"id":2,"result":null}
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::Ev3Error;
+
+#[cfg(feature = "remote-client")]
+mod client;
+#[cfg(feature = "remote-client")]
+pub use self::client::RemoteEv3;
+
+#[cfg(feature = "remote-server")]
+mod server;
+#[cfg(feature = "remote-server")]
+pub use self::server::Server;
+
+/// Default TCP port of the remote protocol.
+pub const DEFAULT_PORT: u16 = 6061;
+
+/// Device classes in `/sys/class/` that the server exposes to clients.
+pub const REMOTE_CLASSES: [&str; 6] = [
+    "lego-sensor",
+    "tacho-motor",
+    "dc-motor",
+    "servo-motor",
+    "lego-port",
+    "leds",
+];
+
+/// A request of the remote protocol. Missing parameters are `None`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Request {
+    #[serde(skip_serializing_if = "Value::is_null")]
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    method: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    class: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    attribute: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stop_action: Option<String>,
+}
+
+/// A response of the remote protocol with the `id` of the request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Response<T> {
+    id: Value,
+    #[serde(flatten)]
+    outcome: Outcome<T>,
+}
+
+/// Either the `result` or the `error` message of a response.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Outcome<T> {
+    Result(T),
+    Error(String),
+}
+
+/// Converts an invalid or unexpected message to an `Ev3Error`.
+fn protocol_error(e: serde_json::Error) -> Ev3Error {
+    Ev3Error::InternalError {
+        msg: format!("Invalid message of the remote protocol: {e}"),
+        path: None,
+    }
+}
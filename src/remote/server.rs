@@ -0,0 +1,188 @@
+//! TCP server that exposes the local devices with the remote protocol.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::thread;
+
+use serde_json::Value;
+
+use super::{protocol_error, Outcome, Request, Response, REMOTE_CLASSES};
+use crate::motors::emergency_stop_all;
+use crate::{scan_class, Attribute, Ev3Error, Ev3Result};
+
+/// Serves the devices of this brick to remote clients. See the module documentation for the protocol.
+///
+/// Every client is handled on its own thread. The server has no authentication,
+/// so it should only be reachable from a trusted network.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::remote::{Server, DEFAULT_PORT};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let server = Server::bind(("0.0.0.0", DEFAULT_PORT))?;
+/// println!("Listening on {}", server.local_addr()?);
+/// server.run()
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Server {
+    listener: TcpListener,
+    stop_on_disconnect: bool,
+}
+
+impl Server {
+    /// Creates a server that listens on the given address.
+    pub fn bind(address: impl ToSocketAddrs) -> Ev3Result<Self> {
+        Ok(Server {
+            listener: TcpListener::bind(address)?,
+            stop_on_disconnect: true,
+        })
+    }
+
+    /// Stop all motors (`coast`) if a client disconnects, e.g. because the wifi connection was lost (default: `true`).
+    pub fn with_stop_on_disconnect(mut self, stop_on_disconnect: bool) -> Self {
+        self.stop_on_disconnect = stop_on_disconnect;
+        self
+    }
+
+    /// Returns the address the server is listening on.
+    pub fn local_addr(&self) -> Ev3Result<SocketAddr> {
+        Ok(self.listener.local_addr()?)
+    }
+
+    /// Accepts clients until an error occurs.
+    pub fn run(&self) -> Ev3Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let stop_on_disconnect = self.stop_on_disconnect;
+
+            thread::spawn(move || {
+                // Also stops the motors if serving the client panics.
+                let _stop = StopOnDisconnect(stop_on_disconnect);
+                // A failed connection only affects this client.
+                let _ = serve_client(stream);
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Stops all motors when dropped, if enabled. Runs on every exit path of a client thread.
+struct StopOnDisconnect(bool);
+
+impl Drop for StopOnDisconnect {
+    fn drop(&mut self) {
+        if self.0 {
+            let _ = emergency_stop_all("coast");
+        }
+    }
+}
+
+/// Answers the requests of a client until it disconnects.
+fn serve_client(stream: TcpStream) -> Ev3Result<()> {
+    let mut writer = stream.try_clone()?;
+    let reader = BufReader::new(stream);
+
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<Request>(&line).map_err(protocol_error) {
+            Ok(request) => Response {
+                outcome: match handle_request(&request) {
+                    Ok(result) => Outcome::Result(result),
+                    Err(e) => Outcome::Error(e.to_string()),
+                },
+                id: request.id,
+            },
+            Err(e) => Response {
+                id: Value::Null,
+                outcome: Outcome::Error(e.to_string()),
+            },
+        };
+
+        let response = serde_json::to_string(&response).map_err(protocol_error)?;
+        writeln!(writer, "{response}")?;
+    }
+    Ok(())
+}
+
+/// Returns the parameter `key` of the request or an error if it is missing.
+fn param<'a>(value: &'a Option<String>, key: &str) -> Ev3Result<&'a str> {
+    value.as_deref().ok_or_else(|| Ev3Error::InternalError {
+        msg: format!("Missing parameter `{key}`"),
+        path: None,
+    })
+}
+
+/// Returns the path parameter `key` with at most `max_components` components,
+/// e.g. a device name or a nested attribute like `hold_pid/Kp`.
+/// Components must not be empty, `.` or `..`, so the path cannot leave its directory.
+fn segment<'a>(value: &'a Option<String>, key: &str, max_components: usize) -> Ev3Result<&'a str> {
+    let value = param(value, key)?;
+    let mut components = value.split('/');
+    let valid = components.clone().count() <= max_components
+        && components.all(|component| !matches!(component, "" | "." | ".."));
+    if !valid {
+        return Err(Ev3Error::InternalError {
+            msg: format!("Invalid {key} `{value}`"),
+            path: None,
+        });
+    }
+    Ok(value)
+}
+
+/// Returns the `class` parameter. Only the `REMOTE_CLASSES` are allowed.
+fn class(request: &Request) -> Ev3Result<&str> {
+    let class = param(&request.class, "class")?;
+    if !REMOTE_CLASSES.contains(&class) {
+        return Err(Ev3Error::InternalError {
+            msg: format!("Class `{class}` is not exposed"),
+            path: None,
+        });
+    }
+    Ok(class)
+}
+
+/// Opens the attribute addressed by the `class`, `name` and `attribute` parameters.
+fn attribute(request: &Request) -> Ev3Result<Attribute> {
+    Attribute::from_sys_class(
+        class(request)?,
+        segment(&request.name, "name", 1)?,
+        segment(&request.attribute, "attribute", 2)?,
+    )
+}
+
+fn handle_request(request: &Request) -> Ev3Result<Value> {
+    match param(&request.method, "method")? {
+        "list" => {
+            let mut descriptors = Vec::new();
+            match request.class {
+                Some(_) => descriptors.extend(scan_class(class(request)?)?),
+                None => {
+                    for class in REMOTE_CLASSES {
+                        descriptors.extend(scan_class(class)?);
+                    }
+                }
+            }
+            serde_json::to_value(descriptors).map_err(protocol_error)
+        }
+        "get" => Ok(attribute(request)?.get::<String>()?.into()),
+        "set" => {
+            attribute(request)?.set_str_slice(param(&request.value, "value")?)?;
+            Ok(Value::Null)
+        }
+        "stop_all" => {
+            let stop_action = request.stop_action.as_deref().unwrap_or("coast");
+            emergency_stop_all(stop_action)?;
+            Ok(Value::Null)
+        }
+        method => Err(Ev3Error::InternalError {
+            msg: format!("Unknown method `{method}`"),
+            path: None,
+        }),
+    }
+}
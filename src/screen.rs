@@ -56,7 +56,7 @@ impl Screen {
     }
 
     fn update_1bpp(&mut self) {
-        let mut buffer = vec![0u8; ((self.xres() * self.yres() + 7) / 8) as usize];
+        let mut buffer = vec![0u8; (self.xres() * self.yres()).div_ceil(8) as usize];
 
         let mut byte: usize = 0;
         let mut bit: u8 = 0x80;
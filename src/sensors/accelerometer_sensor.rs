@@ -0,0 +1,73 @@
+//! HiTechnic NXT Acceleration / Tilt Sensor. (<https://www.generationrobots.com/en/401177-accelerometer-tilt-sensor-for-lego-mindstorms-nxt-and-ev3.html>)
+
+use super::{Sensor, SensorPort};
+use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
+
+/// HiTechnic NXT Acceleration / Tilt Sensor.
+#[derive(Debug, Clone, Device, Sensor)]
+pub struct AccelerometerSensor {
+    driver: Driver,
+    auto_mode_switch: bool,
+}
+
+impl AccelerometerSensor {
+    fn new(driver: Driver) -> Self {
+        Self {
+            driver,
+            auto_mode_switch: false,
+        }
+    }
+
+    findable!(
+        "lego-sensor",
+        ["ht-nxt-accel"],
+        SensorPort,
+        "Accelerometer",
+        "in"
+    );
+
+    sensor_mode!(
+        "ACCEL",
+        MODE_ACCEL,
+        "Acceleration of the x-axis (coarse value, upper 8 bits)",
+        set_mode_accel,
+        is_mode_accel
+    );
+    sensor_mode!(
+        "ALL",
+        MODE_ALL,
+        "Acceleration of all three axes (upper 8 bits in value0-2, lower 2 bits in value3-5)",
+        set_mode_all,
+        is_mode_all
+    );
+
+    /// Number of counts per g of the full 10-bit value.
+    pub const COUNTS_PER_G: f32 = 200.0;
+
+    /// Combines the upper 8 bits and the lower 2 bits of one axis to the signed 10-bit value.
+    pub fn decode_axis(high: i32, low: i32) -> i32 {
+        // The upper byte is a two's complement value.
+        let high = if high > 127 { high - 256 } else { high };
+        (high << 2) | (low & 0x03)
+    }
+
+    /// Gets the acceleration of the x, y and z axis as signed 10-bit values. Requires the mode `ALL`.
+    pub fn get_acceleration(&self) -> Ev3Result<(i32, i32, i32)> {
+        self.ensure_mode(&[Self::MODE_ALL])?;
+
+        let x = Self::decode_axis(self.get_value0()?, self.get_value3()?);
+        let y = Self::decode_axis(self.get_value1()?, self.get_value4()?);
+        let z = Self::decode_axis(self.get_value2()?, self.get_value5()?);
+        Ok((x, y, z))
+    }
+
+    /// Gets the acceleration of the x, y and z axis in g. Requires the mode `ALL`.
+    pub fn get_acceleration_g(&self) -> Ev3Result<(f32, f32, f32)> {
+        let (x, y, z) = self.get_acceleration()?;
+        Ok((
+            x as f32 / Self::COUNTS_PER_G,
+            y as f32 / Self::COUNTS_PER_G,
+            z as f32 / Self::COUNTS_PER_G,
+        ))
+    }
+}
@@ -0,0 +1,167 @@
+//! Sensor wrapper that caches the readings for a short time.
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::Sensor;
+use crate::{Attribute, Device, Ev3Result};
+
+/// Time of the reading and the value.
+type Reading = Option<(Instant, i32)>;
+
+/// Sensor wrapper that reuses a reading of `value0` to `value7` as long as it is younger than the maximal age.
+///
+/// Clones share the cache, so several parts of a program that read the same sensor
+/// within one control tick trigger only one read. The cache is cleared when the mode is set
+/// or the sensor is reconnected through the wrapper. Mode changes through other handles of the sensor
+/// are not detected, call `invalidate()` in this case.
+///
+/// Created by `Sensor::cached()`. The typed getters of the wrapped sensor are not cached,
+/// use `inner()` to call them.
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use ev3dev_lang_rust::sensors::{GyroSensor, Sensor};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let gyro = GyroSensor::find()?.cached(Duration::from_millis(10));
+///
+/// // The second read returns the cached angle.
+/// let steering = gyro.get_value0()?;
+/// let logging = gyro.get_value0()?;
+/// assert_eq!(steering, logging);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CachedSensor<S: Sensor> {
+    sensor: S,
+    max_age: Duration,
+    values: Arc<Mutex<[Reading; 8]>>,
+}
+
+impl<S: Sensor> CachedSensor<S> {
+    /// Wraps `sensor` and caches its readings for `max_age`.
+    pub fn new(sensor: S, max_age: Duration) -> Self {
+        Self {
+            sensor,
+            max_age,
+            values: Arc::new(Mutex::new([None; 8])),
+        }
+    }
+
+    /// Returns the wrapped sensor.
+    pub fn inner(&self) -> &S {
+        &self.sensor
+    }
+
+    /// Returns the wrapped sensor and drops the cache.
+    pub fn into_inner(self) -> S {
+        self.sensor
+    }
+
+    /// Returns the maximal age of a cached reading.
+    pub fn max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    /// Clears all cached readings, so the next read of each value reads the sensor.
+    pub fn invalidate(&self) {
+        *self.values.lock().unwrap() = [None; 8];
+    }
+
+    fn cached_value(
+        &self,
+        index: usize,
+        read: impl FnOnce(&S) -> Ev3Result<i32>,
+    ) -> Ev3Result<i32> {
+        let mut values = self.values.lock().unwrap();
+        if let Some((read_at, value)) = values[index] {
+            if read_at.elapsed() <= self.max_age {
+                return Ok(value);
+            }
+        }
+
+        // The lock is held while reading, so concurrent readers wait for this reading instead of reading again.
+        let value = read(&self.sensor)?;
+        values[index] = Some((Instant::now(), value));
+        Ok(value)
+    }
+}
+
+impl<S: Sensor> Device for CachedSensor<S> {
+    fn get_attribute(&self, name: &str) -> Ev3Result<Attribute> {
+        self.sensor.get_attribute(name)
+    }
+
+    fn is_connected(&self) -> bool {
+        self.sensor.is_connected()
+    }
+
+    fn reconnect(&self) -> Ev3Result<()> {
+        self.invalidate();
+        self.sensor.reconnect()
+    }
+
+    fn clear_cache(&self) {
+        self.sensor.clear_cache()
+    }
+}
+
+impl<S: Sensor> Sensor for CachedSensor<S> {
+    fn set_mode(&self, mode: &str) -> Ev3Result<()> {
+        self.invalidate();
+        self.sensor.set_mode(mode)
+    }
+
+    fn get_auto_mode_switch(&self) -> bool {
+        self.sensor.get_auto_mode_switch()
+    }
+
+    fn set_auto_mode_switch(&mut self, enabled: bool) {
+        self.sensor.set_auto_mode_switch(enabled)
+    }
+
+    fn get_value0(&self) -> Ev3Result<i32> {
+        self.cached_value(0, S::get_value0)
+    }
+
+    fn get_value1(&self) -> Ev3Result<i32> {
+        self.cached_value(1, S::get_value1)
+    }
+
+    fn get_value2(&self) -> Ev3Result<i32> {
+        self.cached_value(2, S::get_value2)
+    }
+
+    fn get_value3(&self) -> Ev3Result<i32> {
+        self.cached_value(3, S::get_value3)
+    }
+
+    fn get_value4(&self) -> Ev3Result<i32> {
+        self.cached_value(4, S::get_value4)
+    }
+
+    fn get_value5(&self) -> Ev3Result<i32> {
+        self.cached_value(5, S::get_value5)
+    }
+
+    fn get_value6(&self) -> Ev3Result<i32> {
+        self.cached_value(6, S::get_value6)
+    }
+
+    fn get_value7(&self) -> Ev3Result<i32> {
+        self.cached_value(7, S::get_value7)
+    }
+}
+
+impl<S: Sensor + fmt::Debug> fmt::Debug for CachedSensor<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachedSensor")
+            .field("sensor", &self.sensor)
+            .field("max_age", &self.max_age)
+            .finish()
+    }
+}
@@ -6,11 +6,15 @@ use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
 #[derive(Debug, Clone, Device, Sensor)]
 pub struct ColorSensor {
     driver: Driver,
+    auto_mode_switch: bool,
 }
 
 impl ColorSensor {
     fn new(driver: Driver) -> Self {
-        Self { driver }
+        Self {
+            driver,
+            auto_mode_switch: false,
+        }
     }
 
     findable!(
@@ -66,45 +70,79 @@ impl ColorSensor {
 
     /// Get the color value for the modes COL-REFLECT, COL-AMBIENT, COL-COLOR and REF-RAW.
     pub fn get_color(&self) -> Ev3Result<i32> {
+        self.ensure_mode(&[
+            Self::MODE_COL_COLOR,
+            Self::MODE_COL_REFLECT,
+            Self::MODE_COL_AMBIENT,
+            Self::MODE_REF_RAW,
+        ])?;
         self.get_value0()
     }
 
     /// Red component of the detected color, in the range 0-1020.
+    /// Requires the mode RGB-RAW.
     pub fn get_red(&self) -> Ev3Result<i32> {
+        self.ensure_mode(&[Self::MODE_RGB_RAW])?;
         self.get_value0()
     }
 
     /// Green component of the detected color, in the range 0-1020.
+    /// Requires the mode RGB-RAW.
     pub fn get_green(&self) -> Ev3Result<i32> {
+        self.ensure_mode(&[Self::MODE_RGB_RAW])?;
         self.get_value1()
     }
 
     /// Blue component of the detected color, in the range 0-1020.
+    /// Requires the mode RGB-RAW.
     pub fn get_blue(&self) -> Ev3Result<i32> {
+        self.ensure_mode(&[Self::MODE_RGB_RAW])?;
         self.get_value2()
     }
 
     /// Red, green and blue components of the detected color, each in the range 0-1020
+    /// Requires the mode RGB-RAW.
     pub fn get_rgb(&self) -> Ev3Result<(i32, i32, i32)> {
-        let red = self.get_red()?;
-        let green = self.get_green()?;
-        let blue = self.get_blue()?;
+        self.ensure_mode(&[Self::MODE_RGB_RAW])?;
+
+        let red = self.get_value0()?;
+        let green = self.get_value1()?;
+        let blue = self.get_value2()?;
 
         Ok((red, green, blue))
     }
 
-
-    /// Returns the unscaled raw values in the `value<N>` attributes as raw byte
-    /// array. Use `bin_data_format`, `num_values` and the individual sensor
-    /// documentation to determine how to interpret the data.
+    /// Returns the first three unscaled raw values of `bin_data`, e.g. the red, green and blue values
+    /// in the mode `RGB-RAW`, with a single read.
+    ///
+    /// Returns `Ev3Error::UnsupportedFormat` if the `bin_data_format` of the current mode is not `s16`,
+    /// use the `value<N>` attributes in this case.
     pub fn get_bin_data(&self) -> Ev3Result<(i16, i16, i16)> {
-        let data = self.get_attribute("bin_data").get_raw_data()?;
+        let format = self.get_bin_data_format()?;
+        if format != "s16" {
+            return Err(Ev3Error::UnsupportedFormat {
+                format,
+                expected: vec!["s16".to_owned()],
+                path: self
+                    .get_attribute("bin_data_format")
+                    .ok()
+                    .map(|a| a.get_file_path()),
+            });
+        }
+
+        let mut data = [0u8; 8];
+        let len = self.get_bin_data_into(&mut data)?;
+        if len < 6 {
+            return Err(Ev3Error::InternalError {
+                msg: format!("bin_data contains {len} bytes, expected 6"),
+                path: None,
+            });
+        }
 
-        let colors: Vec<i16> = data[0..8]
-            .chunks_exact(2)
-            .map(|a| i16::from_ne_bytes([a[0], a[1]]))
-            .collect();
-   
-        Ok((colors[0], colors[1], colors[2]))
+        Ok((
+            i16::from_ne_bytes([data[0], data[1]]),
+            i16::from_ne_bytes([data[2], data[3]]),
+            i16::from_ne_bytes([data[4], data[5]]),
+        ))
     }
 }
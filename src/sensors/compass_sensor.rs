@@ -1,6 +1,7 @@
 //! HiTechnic EV3 / NXT Compass Sensor. (<https://www.generationrobots.com/en/401186-hitechnic-compass-sensor-for-lego-mindstorms-nxt-and-ev3.html>)
 
-use super::{Sensor, SensorPort};
+use super::{HeadingSensor, Sensor, SensorPort};
+use crate::device::device_path;
 use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result};
 
 /// HiTechnic EV3 / NXT Compass Sensor.
@@ -36,6 +37,22 @@ impl CompassSensor {
         self.get_value0()
     }
 
+    /// Gets the absolute heading (in degree, clockwise, 0 = north) in the range `0..360`.
+    ///
+    /// Returns `Ev3Error::UnsupportedOperation` while the sensor is not calibrated or calibrating,
+    /// because it reports -258 instead of a heading.
+    pub fn get_heading(&self) -> Ev3Result<i32> {
+        let rotation = self.get_rotation()?;
+        if !(0..360).contains(&rotation) {
+            return Err(Ev3Error::UnsupportedOperation {
+                device: "Compass".to_owned(),
+                operation: "get_heading() during calibration".to_owned(),
+                path: device_path(self),
+            });
+        }
+        Ok(rotation)
+    }
+
     /// sets the origin
     pub fn set_zero(&mut self) -> Ev3Result<()> {
         self.origin = self.get_rotation()?;
@@ -52,19 +69,32 @@ impl CompassSensor {
         Ok(rel_rot)
     }
 
-    /// calibration:
-    /// start the calibration by start_calibration()
-    /// turn the robot 360 degrees
-    /// end the calibration by stop_calibration()
-    /// attention: if calibration has not finished, the get_rotation method always returns -258
+    /// Starts the calibration.
+    ///
+    /// Turn the robot slowly by at least 360 degrees and end the calibration with `end_calibration()`.
+    /// Attention: If the calibration has not finished, `get_rotation()` always returns -258.
+    pub fn begin_calibration(&self) -> Ev3Result<()> {
+        self.set_command(Self::COMMAND_START_CALIBRATION)
+    }
 
-    /// starts the calibration
+    /// Ends the calibration.
+    pub fn end_calibration(&self) -> Ev3Result<()> {
+        self.set_command(Self::COMMAND_STOP_CALIBRATION)
+    }
+
+    /// starts the calibration, same as `begin_calibration()`
     pub fn start_calibration(&self) -> Ev3Result<()> {
-        self.set_command(Self::COMMAND_START_CALIBRATION)
+        self.begin_calibration()
     }
 
-    /// stops the calibration
+    /// stops the calibration, same as `end_calibration()`
     pub fn stop_calibration(&self) -> Ev3Result<()> {
-        self.set_command(Self::COMMAND_STOP_CALIBRATION)
+        self.end_calibration()
+    }
+}
+
+impl HeadingSensor for CompassSensor {
+    fn get_heading(&self) -> Ev3Result<i32> {
+        CompassSensor::get_heading(self)
     }
 }
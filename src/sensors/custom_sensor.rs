@@ -0,0 +1,110 @@
+//! Sensor types for drivers that are not supported by this crate.
+
+/// Defines a sensor type for a `lego-sensor` driver that is not supported by this crate,
+/// e.g. in a downstream crate for third-party hardware.
+///
+/// The generated struct implements `Device` and `Sensor` and has the same constructors as the
+/// sensors of this crate (`get()`, `get_by_address()`, `find()`, `list()`, `get_from()`, ...).
+/// For every mode a constant and a setter and checker are generated, for every value a getter
+/// that returns the raw `value<N>` attribute.
+///
+/// # Example
+/// ```
+/// use ev3dev_lang_rust::custom_sensor;
+/// use ev3dev_lang_rust::sensors::{Sensor, SensorPort};
+/// use ev3dev_lang_rust::MockEv3;
+///
+/// custom_sensor! {
+///     /// mindsensors.com EV3 light sensor array.
+///     pub struct LightSensorArray {
+///         drivers: ["ms-light-array"],
+///         port: SensorPort,
+///         modes: [
+///             (MODE_CAL, "CAL", "Calibrated reflection values", set_mode_cal, is_mode_cal),
+///             (MODE_RAW, "RAW", "Raw reflection values", set_mode_raw, is_mode_raw),
+///         ],
+///         values: [
+///             (get_first, 0, "Reflection of the first sensor"),
+///             (get_last, 7, "Reflection of the last sensor"),
+///         ],
+///     }
+/// }
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// # let ev3 = MockEv3::new();
+/// # ev3.add_sensor("ms-light-array", SensorPort::In1).set("value0", 42);
+/// let sensor = LightSensorArray::get_from(&ev3, SensorPort::In1)?;
+/// sensor.set_mode_cal()?;
+/// assert_eq!(sensor.get_first()?, 42);
+/// # Ok(())
+/// # }
+/// ```
+#[macro_export]
+macro_rules! custom_sensor {
+    (
+        $(#[$meta:meta])*
+        $vis:vis struct $name:ident {
+            drivers: [$( $driver_name:expr ),* $(,)?],
+            port: $port:ty,
+            modes: [$( ($const_name:ident, $mode:expr, $mode_doc:expr, $setter:ident, $checker:ident) ),* $(,)?],
+            values: [$( ($getter:ident, $index:expr, $value_doc:expr) ),* $(,)?] $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        $vis struct $name {
+            driver: $crate::Driver,
+        }
+
+        const _: () = {
+            // The internal macros resolve these names at the call site.
+            #[allow(unused_imports)]
+            use $crate::{sensors::Sensor, Attribute, Device, Driver, Ev3Error, Ev3Result};
+
+            impl $name {
+                fn new(driver: Driver) -> Self {
+                    Self { driver }
+                }
+
+                $crate::findable!(
+                    "lego-sensor",
+                    [$( $driver_name ),*],
+                    $port,
+                    stringify!($name),
+                    "in"
+                );
+
+                $(
+                    $crate::sensor_mode!($mode, $const_name, $mode_doc, $setter, $checker);
+                )*
+
+                $(
+                    #[doc = $value_doc]
+                    pub fn $getter(&self) -> Ev3Result<i32> {
+                        self.get_value($index)
+                    }
+                )*
+            }
+
+            impl Device for $name {
+                fn get_attribute(&self, name: &str) -> Ev3Result<Attribute> {
+                    self.driver.get_attribute(name)
+                }
+
+                fn is_connected(&self) -> bool {
+                    self.driver.is_connected()
+                }
+
+                fn reconnect(&self) -> Ev3Result<()> {
+                    self.driver.reconnect()
+                }
+
+                fn clear_cache(&self) {
+                    self.driver.clear_cache()
+                }
+            }
+
+            impl Sensor for $name {}
+        };
+    };
+}
@@ -0,0 +1,133 @@
+//! Sensor for arbitrary `lego-sensor` drivers.
+
+use std::sync::Arc;
+
+use super::{Sensor, SensorPort};
+use crate::{
+    Attribute, Backend, Device, DeviceDescriptor, Driver, Ev3Error, Ev3Result, Port, Sysfs,
+};
+
+/// Sensor for any device of the `lego-sensor` class, regardless of its driver name.
+///
+/// Gives third-party or experimental drivers access to the common `Sensor` api.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::Device;
+/// use ev3dev_lang_rust::sensors::{GenericSensor, Sensor, SensorPort};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let sensor = GenericSensor::get(SensorPort::In1)?;
+/// println!("{} reports {}", sensor.get_driver_name()?, sensor.get_value0()?);
+///
+/// let sensor = GenericSensor::find_by_driver("my-custom-driver")?;
+/// println!("Modes: {:?}", sensor.get_modes()?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Device, Sensor)]
+pub struct GenericSensor {
+    driver: Driver,
+}
+
+impl GenericSensor {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    fn map_error(e: Ev3Error) -> Ev3Error {
+        match e {
+            Ev3Error::NotConnected {
+                device: _,
+                port,
+                path,
+            } => Ev3Error::NotConnected {
+                device: "GenericSensor".to_owned(),
+                port,
+                path,
+            },
+            Ev3Error::MultipleMatches {
+                device,
+                ports,
+                path,
+            } => Ev3Error::MultipleMatches {
+                device,
+                ports: ports
+                    .iter()
+                    .map(|item| SensorPort::format_name(item))
+                    .collect(),
+                path,
+            },
+            e => e,
+        }
+    }
+
+    /// Try to get a sensor on the given port, regardless of its driver.
+    pub fn get(port: SensorPort) -> Ev3Result<Self> {
+        Self::get_by_port(&port)
+    }
+
+    /// Try to get a sensor on any kind of port, e.g. a sensor multiplexer sub-port, regardless of its driver.
+    pub fn get_by_port(port: &dyn Port) -> Ev3Result<Self> {
+        let name =
+            Driver::find_name_by_port("lego-sensor", port).map_err(GenericSensor::map_error)?;
+
+        Ok(Self::new(Driver::reconnectable("lego-sensor", &name, &[])))
+    }
+
+    /// Try to find a sensor with the given driver name.
+    /// Only returns a sensor if there is exactly one connected, `Error::NotFound` otherwise.
+    pub fn find_by_driver(driver_name: &str) -> Ev3Result<Self> {
+        let name = Driver::find_name_by_driver("lego-sensor", &[driver_name])
+            .map_err(GenericSensor::map_error)?;
+
+        Ok(Self::new(Driver::reconnectable(
+            "lego-sensor",
+            &name,
+            &[driver_name],
+        )))
+    }
+
+    /// Extract list of all connected sensors with the given driver name.
+    pub fn list_by_driver(driver_name: &str) -> Ev3Result<Vec<Self>> {
+        Ok(Driver::find_names_by_driver("lego-sensor", &[driver_name])?
+            .iter()
+            .map(|name| Self::new(Driver::reconnectable("lego-sensor", name, &[driver_name])))
+            .collect())
+    }
+
+    /// Create a sensor from a descriptor returned by `scan()`.
+    /// Returns an error if the descriptor does not belong to the `lego-sensor` class.
+    pub fn from_descriptor(descriptor: &DeviceDescriptor) -> Ev3Result<Self> {
+        Self::from_descriptor_from(&Sysfs, descriptor)
+    }
+
+    /// Create a sensor from a descriptor returned by `scan_from()` for the same `backend`.
+    /// Returns an error if the descriptor does not belong to the `lego-sensor` class.
+    pub fn from_descriptor_from<B: Backend + Clone + 'static>(
+        backend: &B,
+        descriptor: &DeviceDescriptor,
+    ) -> Ev3Result<Self> {
+        if descriptor.class != "lego-sensor" {
+            return Err(Ev3Error::InternalError {
+                msg: format!("{descriptor:?} is not a GenericSensor"),
+                path: None,
+            });
+        }
+
+        Ok(Self::new(Driver::reconnectable_from(
+            Arc::new(backend.clone()),
+            "lego-sensor",
+            &descriptor.name,
+            &[],
+        )))
+    }
+
+    /// Extract list of all connected sensors.
+    pub fn list() -> Ev3Result<Vec<Self>> {
+        Ok(Driver::find_names("lego-sensor")?
+            .iter()
+            .map(|name| Self::new(Driver::reconnectable("lego-sensor", name, &[])))
+            .collect())
+    }
+}
@@ -1,17 +1,21 @@
 //! LEGO EV3 gyro sensor.
 
-use super::{Sensor, SensorPort};
+use super::{HeadingSensor, Sensor, SensorPort};
 use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
 
 /// LEGO EV3 gyro sensor.
 #[derive(Debug, Clone, Device, Sensor)]
 pub struct GyroSensor {
     driver: Driver,
+    auto_mode_switch: bool,
 }
 
 impl GyroSensor {
     fn new(driver: Driver) -> Self {
-        Self { driver }
+        Self {
+            driver,
+            auto_mode_switch: false,
+        }
     }
 
     findable!(
@@ -72,29 +76,35 @@ impl GyroSensor {
     );
 
     /// Gets the angle, ranging from -32768 to 32767
-    /// Fails if it has been set in the wrong mode
+    /// Fails with `Ev3Error::WrongMode` if it has been set in the wrong mode
     pub fn get_angle(&self) -> Ev3Result<i32> {
         match self.get_mode()?.as_ref() {
             GyroSensor::MODE_GYRO_G_AND_A => self.get_value0(),
             GyroSensor::MODE_GYRO_ANG => self.get_value0(),
-            mode => Ev3Result::Err(Ev3Error::InternalError {
-                msg: format!("Cannot get angle while in {mode} mode"),
-                // Returns error
-            }),
+            _ => {
+                self.ensure_mode(&[Self::MODE_GYRO_ANG, Self::MODE_GYRO_G_AND_A])?;
+                self.get_value0()
+            }
         }
     }
 
     /// Gets the rotational speed value, ranging from -440 to 440
-    /// Fails is it has been set in the wrong mode:
+    /// Fails with `Ev3Error::WrongMode` if it has been set in the wrong mode:
     /// for example, fails if we ask for rotational speed while in MODE_GYRO_ANG mode
     pub fn get_rotational_speed(&self) -> Ev3Result<i32> {
         match self.get_mode()?.as_ref() {
             GyroSensor::MODE_GYRO_RATE => self.get_value0(),
             GyroSensor::MODE_GYRO_G_AND_A => self.get_value1(),
-            mode => Ev3Result::Err(Ev3Error::InternalError {
-                msg: format!("Cannot get rotational speed while in {mode} mode"),
-                // Returns error
-            }),
+            _ => {
+                self.ensure_mode(&[Self::MODE_GYRO_RATE, Self::MODE_GYRO_G_AND_A])?;
+                self.get_value0()
+            }
         }
     }
 }
+
+impl HeadingSensor for GyroSensor {
+    fn get_heading(&self) -> Ev3Result<i32> {
+        self.get_angle()
+    }
+}
@@ -0,0 +1,44 @@
+//! Common interface for sensors that measure the heading of the robot.
+
+use crate::Ev3Result;
+
+/// A sensor that measures the heading of the robot, like a compass or a gyro sensor.
+///
+/// Headings are measured in degree and increase clockwise. Depending on the sensor
+/// the value is bounded to `0..360` (compass) or unbounded (gyro). Use `heading_difference()`
+/// to compare two headings.
+///
+/// # Example
+///
+/// ```no_run
+/// use ev3dev_lang_rust::sensors::{heading_difference, CompassSensor, HeadingSensor};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// fn turn_error(sensor: &dyn HeadingSensor, target: i32) -> ev3dev_lang_rust::Ev3Result<i32> {
+///     Ok(heading_difference(target, sensor.get_heading()?))
+/// }
+///
+/// let compass = CompassSensor::find()?;
+/// println!("Turn by {} degree", turn_error(&compass, 90)?);
+/// # Ok(())
+/// # }
+/// ```
+pub trait HeadingSensor {
+    /// Returns the current heading in degree.
+    fn get_heading(&self) -> Ev3Result<i32>;
+
+    /// Returns the current heading as angle.
+    #[cfg(feature = "uom")]
+    fn get_heading_typed(&self) -> Ev3Result<crate::units::Angle> {
+        use crate::units::{angle::degree, Angle};
+
+        Ok(Angle::new::<degree>(self.get_heading()? as f32))
+    }
+}
+
+/// Returns the shortest signed rotation (in degree, `-180..180`) from `actual` to `target`.
+///
+/// A positive value means a clockwise rotation.
+pub fn heading_difference(target: i32, actual: i32) -> i32 {
+    (target - actual + 180).rem_euclid(360) - 180
+}
@@ -8,11 +8,15 @@ use super::{Sensor, SensorPort};
 #[derive(Debug, Clone, Device, Sensor)]
 pub struct HiTechnicColorSensor {
     driver: Driver,
+    auto_mode_switch: bool,
 }
 
 impl HiTechnicColorSensor {
     fn new(driver: Driver) -> Self {
-        Self { driver }
+        Self {
+            driver,
+            auto_mode_switch: false,
+        }
     }
 
     findable!(
@@ -68,26 +72,41 @@ impl HiTechnicColorSensor {
 
     /// Get the color value for the modes `COL-REFLECT`, `COL-AMBIENT`, `COL-COLOR` and `REF-RAW`.
     pub fn get_color(&self) -> Ev3Result<i32> {
+        self.ensure_mode(&[
+            Self::MODE_COL_COLOR,
+            Self::MODE_COL_REFLECT,
+            Self::MODE_COL_AMBIENT,
+            Self::MODE_REF_RAW,
+        ])?;
         self.get_value0()
     }
 
     /// Red component of the detected color, in the range 0-1020.
+    /// Requires the mode `RAW`.
     pub fn get_red(&self) -> Ev3Result<i32> {
+        self.ensure_mode(&[Self::MODE_RGB_RAW])?;
         self.get_value0()
     }
 
     /// Green component of the detected color, in the range 0-1020.
+    /// Requires the mode `RAW`.
     pub fn get_green(&self) -> Ev3Result<i32> {
+        self.ensure_mode(&[Self::MODE_RGB_RAW])?;
         self.get_value1()
     }
 
     /// Blue component of the detected color, in the range 0-1020.
+    /// Requires the mode `RAW`.
     pub fn get_blue(&self) -> Ev3Result<i32> {
+        self.ensure_mode(&[Self::MODE_RGB_RAW])?;
         self.get_value2()
     }
 
     /// Red, green, blue and intensity components of the detected color, each in the range 0-1020
+    /// Requires the mode `RAW`.
     pub fn get_rgb_i(&self) -> Ev3Result<(i32, i32, i32, i32)> {
+        self.ensure_mode(&[Self::MODE_RGB_RAW])?;
+
         let red = self.value(0)?;
         let green = self.value(1)?;
         let blue = self.value(2)?;
@@ -0,0 +1,111 @@
+//! Generic NXT I2C sensor.
+
+use super::{Sensor, SensorPort};
+use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result, LegoPort, Port};
+
+/// I2C address on one of the sensor ports, e.g. `in1:i2c1`.
+struct I2cAddress {
+    port: SensorPort,
+    address: u8,
+}
+
+impl Port for I2cAddress {
+    fn address(&self) -> String {
+        format!("{}:i2c{}", self.port.address(), self.address)
+    }
+}
+
+/// Generic NXT I2C sensor.
+///
+/// ev3dev loads the `nxt-i2c-sensor` driver for I2C devices that are not known to the kernel.
+/// The registers of the device can be accessed directly, so third-party sensors can be used without
+/// writing a kernel driver.
+///
+/// # Example
+///
+/// ```no_run
+/// use ev3dev_lang_rust::sensors::{I2cSensor, Sensor, SensorPort};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// // Let the port detect I2C devices.
+/// I2cSensor::set_port_mode(SensorPort::In1)?;
+///
+/// let sensor = I2cSensor::get_with_address(SensorPort::In1, 0x01)?;
+/// println!("Firmware: {}", sensor.get_fw_version()?);
+///
+/// let mut data = [0u8; 4];
+/// sensor.read_registers(0x42, &mut data)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Device, Sensor)]
+pub struct I2cSensor {
+    driver: Driver,
+}
+
+impl I2cSensor {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    findable!(
+        "lego-sensor",
+        ["nxt-i2c-sensor"],
+        SensorPort,
+        "I2cSensor",
+        "in"
+    );
+
+    /// Sets the mode of the given input port to `nxt-i2c`, so connected I2C devices get detected.
+    pub fn set_port_mode(port: SensorPort) -> Ev3Result<()> {
+        LegoPort::get(&port)?.set_mode(LegoPort::MODE_NXT_I2C)
+    }
+
+    /// Try to get the I2C sensor with the given (7-bit) I2C `address` on the given port.
+    pub fn get_with_address(port: SensorPort, address: u8) -> Ev3Result<Self> {
+        Self::get_by_port(&I2cAddress { port, address })
+    }
+
+    /// Returns the (7-bit) I2C address of this sensor.
+    pub fn get_i2c_address(&self) -> Ev3Result<u8> {
+        let address = self.get_address()?;
+
+        address
+            .rsplit(':')
+            .find_map(|segment| segment.strip_prefix("i2c"))
+            .and_then(|value| value.parse().ok())
+            .ok_or_else(|| Ev3Error::InternalError {
+                msg: format!("Address `{address}` does not contain an I2C address"),
+                path: None,
+            })
+    }
+
+    /// Reads `buf.len()` registers starting at `register`. Returns the number of bytes read.
+    pub fn read_registers(&self, register: u8, buf: &mut [u8]) -> Ev3Result<usize> {
+        self.get_attribute("direct")?
+            .read_at(u64::from(register), buf)
+    }
+
+    /// Reads the value of a single register.
+    pub fn read_register(&self, register: u8) -> Ev3Result<u8> {
+        let mut buf = [0u8];
+        if self.read_registers(register, &mut buf)? != 1 {
+            return Err(Ev3Error::InternalError {
+                msg: format!("Register {register:#04x} could not be read"),
+                path: None,
+            });
+        }
+        Ok(buf[0])
+    }
+
+    /// Writes `data` to the registers starting at `register`.
+    pub fn write_registers(&self, register: u8, data: &[u8]) -> Ev3Result<()> {
+        self.get_attribute("direct")?
+            .write_at(u64::from(register), data)
+    }
+
+    /// Writes the value of a single register.
+    pub fn write_register(&self, register: u8, value: u8) -> Ev3Result<()> {
+        self.write_registers(register, &[value])
+    }
+}
@@ -11,11 +11,15 @@ use std::rc::Rc;
 #[derive(Debug, Clone, Device, Sensor)]
 pub struct InfraredSensor {
     driver: Driver,
+    auto_mode_switch: bool,
 }
 
 impl InfraredSensor {
     fn new(driver: Driver) -> Self {
-        Self { driver }
+        Self {
+            driver,
+            auto_mode_switch: false,
+        }
     }
 
     findable!(
@@ -70,7 +74,9 @@ impl InfraredSensor {
     );
 
     /// Get the proximity distance, in the range 0-100 (pct).
+    /// Requires the mode `IR-PROX`.
     pub fn get_distance(&self) -> Ev3Result<i32> {
+        self.ensure_mode(&[Self::MODE_IR_PROX])?;
         self.get_value0()
     }
 }
@@ -7,11 +7,15 @@ use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
 #[derive(Debug, Clone, Device, Sensor)]
 pub struct IrSeekerSensor {
     driver: Driver,
+    auto_mode_switch: bool,
 }
 
 impl IrSeekerSensor {
     fn new(driver: Driver) -> Self {
-        Self { driver }
+        Self {
+            driver,
+            auto_mode_switch: false,
+        }
     }
 
     findable!(
@@ -65,4 +69,13 @@ impl IrSeekerSensor {
         let val5 = self.get_value5()?;
         Ok([val1, val2, val3, val4, val5])
     }
+
+    /// Gets the direction of the incoming ir light (`0` = no signal, `1..=9` from left to right)
+    /// together with the signal strength of each of the five segments.
+    /// Requires the mode `AC-ALL` or `DC-ALL`.
+    pub fn get_direction_and_strengths(&self) -> Ev3Result<(i32, [i32; 5])> {
+        self.ensure_mode(&[Self::MODE_AC_ALL, Self::MODE_DC_ALL])?;
+
+        Ok((self.get_value0()?, self.get_raw_values()?))
+    }
 }
@@ -1,13 +1,14 @@
-//! LEGO EV3 light sensor.
+//! LEGO NXT light sensor.
 
 use super::{Sensor, SensorPort};
 use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
 use std::cell::Cell;
 
-/// LEGO EV3 light sensor.
+/// LEGO NXT light sensor.
 #[derive(Debug, Clone, Device, Sensor)]
 pub struct LightSensor {
     driver: Driver,
+    auto_mode_switch: bool,
     reflect_scale: Cell<Option<f32>>,
     ambient_scale: Cell<Option<f32>>,
 }
@@ -16,6 +17,7 @@ impl LightSensor {
     fn new(driver: Driver) -> Self {
         Self {
             driver,
+            auto_mode_switch: false,
             reflect_scale: Cell::new(None),
             ambient_scale: Cell::new(None),
         }
@@ -50,7 +52,10 @@ impl LightSensor {
     }
 
     /// A measurement of the reflected light intensity, as a percentage.
+    /// Requires the mode `REFLECT`.
     pub fn get_reflected_light_intensity(&self) -> Ev3Result<f32> {
+        self.ensure_mode(&[Self::MODE_REFLECT])?;
+
         let scale_field = self.reflect_scale.get();
         let scale = match scale_field {
             Some(s) => s,
@@ -66,7 +71,10 @@ impl LightSensor {
     }
 
     /// A measurement of the ambient light intensity, as a percentage.
+    /// Requires the mode `AMBIENT`.
     pub fn get_ambient_light_intensity(&self) -> Ev3Result<f32> {
+        self.ensure_mode(&[Self::MODE_AMBIENT])?;
+
         let scale_field = self.ambient_scale.get();
         let scale = match scale_field {
             Some(s) => s,
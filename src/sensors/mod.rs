@@ -3,12 +3,32 @@
 mod sensor;
 pub use self::sensor::Sensor;
 
+mod mode_guard;
+pub use self::mode_guard::ModeGuard;
+
+mod cached_sensor;
+pub use self::cached_sensor::CachedSensor;
+
+mod custom_sensor;
+
+mod sensor_info;
+pub use self::sensor_info::SensorInfo;
+
+mod snapshot;
+pub use self::snapshot::{read_snapshot, read_snapshot_parallel, Snapshot};
+
+mod accelerometer_sensor;
+pub use self::accelerometer_sensor::AccelerometerSensor;
+
 mod color_sensor;
 pub use self::color_sensor::ColorSensor;
 
 mod hi_technic_color_sensor;
 pub use self::hi_technic_color_sensor::HiTechnicColorSensor;
 
+mod i2c_sensor;
+pub use self::i2c_sensor::I2cSensor;
+
 mod ir_seeker_sensor;
 pub use self::ir_seeker_sensor::IrSeekerSensor;
 
@@ -18,6 +38,12 @@ pub use self::compass_sensor::CompassSensor;
 mod light_sensor;
 pub use self::light_sensor::LightSensor;
 
+mod generic_sensor;
+pub use self::generic_sensor::GenericSensor;
+
+mod heading_sensor;
+pub use self::heading_sensor::{heading_difference, HeadingSensor};
+
 mod gyro_sensor;
 pub use self::gyro_sensor::GyroSensor;
 
@@ -26,16 +52,35 @@ pub use self::infrared_sensor::BeaconSeeker;
 pub use self::infrared_sensor::InfraredSensor;
 pub use self::infrared_sensor::RemoteControl;
 
+mod pixy_camera;
+pub use self::pixy_camera::{PixyBlock, PixyCamera};
+
+mod sound_sensor;
+pub use self::sound_sensor::SoundSensor;
+
+mod temperature_sensor;
+pub use self::temperature_sensor::TemperatureSensor;
+
 mod touch_sensor;
 pub use self::touch_sensor::TouchSensor;
 
 mod ultrasonic_sensor;
 pub use self::ultrasonic_sensor::UltrasonicSensor;
 
-use crate::{port_constants, Port};
+mod wedo_motion_sensor;
+pub use self::wedo_motion_sensor::WedoMotionSensor;
+
+mod wedo_tilt_sensor;
+pub use self::wedo_tilt_sensor::{WedoTilt, WedoTiltSensor};
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{port_constants, Ev3Error, Port};
 
 /// EV3 ports `in1` to `in4`
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SensorPort {
     /// EV3 `in1` port
     In1,
@@ -48,6 +93,24 @@ pub enum SensorPort {
 }
 
 impl SensorPort {
+    /// All sensor ports.
+    pub const ALL: [SensorPort; 4] = [
+        SensorPort::In1,
+        SensorPort::In2,
+        SensorPort::In3,
+        SensorPort::In4,
+    ];
+
+    /// Returns the EV3 name of the port, e.g. `in1`, on every platform.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SensorPort::In1 => "in1",
+            SensorPort::In2 => "in2",
+            SensorPort::In3 => "in3",
+            SensorPort::In4 => "in4",
+        }
+    }
+
     /// Try to format a device name path to a  port name.
     pub fn format_name(name: &str) -> String {
         match name {
@@ -71,6 +134,134 @@ impl Port for SensorPort {
     }
 }
 
+impl fmt::Display for SensorPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for SensorPort {
+    type Err = Ev3Error;
+
+    /// Parses `in1`, `1` or the address of the port (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let number = s
+            .get(..2)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("in"))
+            .map_or(s, |_| &s[2..]);
+
+        SensorPort::ALL
+            .into_iter()
+            .find(|port| port.name()[2..] == *number || port.address().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Ev3Error::InvalidPort {
+                value: s.to_owned(),
+                expected: "in1 to in4".to_owned(),
+                path: None,
+            })
+    }
+}
+
+/// Channels of a sensor multiplexer like the mindsensors.com EV3 Sensor Multiplexer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MuxChannel {
+    /// First channel (`i2c80:mux1`)
+    Mux1,
+    /// Second channel (`i2c81:mux2`)
+    Mux2,
+    /// Third channel (`i2c82:mux3`)
+    Mux3,
+}
+
+/// Sub-port of a sensor multiplexer that is connected to one of the sensor ports.
+///
+/// # Example
+///
+/// ```no_run
+/// use ev3dev_lang_rust::sensors::{MuxChannel, MuxPort, SensorPort, TouchSensor};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let port = MuxPort::new(SensorPort::In1, MuxChannel::Mux2);
+/// let touch_sensor = TouchSensor::get_by_port(&port)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MuxPort {
+    port: SensorPort,
+    channel: MuxChannel,
+}
+
+impl MuxPort {
+    /// Create a new sub-port for the multiplexer connected to `port`.
+    pub fn new(port: SensorPort, channel: MuxChannel) -> Self {
+        Self { port, channel }
+    }
+
+    /// Returns the sensor port the multiplexer is connected to.
+    pub fn get_port(&self) -> SensorPort {
+        self.port
+    }
+
+    /// Returns the channel of the multiplexer.
+    pub fn get_channel(&self) -> MuxChannel {
+        self.channel
+    }
+}
+
+impl Port for MuxPort {
+    fn address(&self) -> String {
+        let channel = match self.channel {
+            MuxChannel::Mux1 => "i2c80:mux1",
+            MuxChannel::Mux2 => "i2c81:mux2",
+            MuxChannel::Mux3 => "i2c82:mux3",
+        };
+        format!("{}:{}", self.port.address(), channel)
+    }
+}
+
+impl fmt::Display for MuxPort {
+    /// Writes the port as `in1:mux2`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let channel = match self.channel {
+            MuxChannel::Mux1 => "mux1",
+            MuxChannel::Mux2 => "mux2",
+            MuxChannel::Mux3 => "mux3",
+        };
+        write!(f, "{}:{channel}", self.port)
+    }
+}
+
+impl FromStr for MuxPort {
+    type Err = Ev3Error;
+
+    /// Parses `in1:mux2` or the address of the port, e.g. `in1:i2c81:mux2`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || Ev3Error::InvalidPort {
+            value: s.to_owned(),
+            expected: "e.g. in1:mux2".to_owned(),
+            path: None,
+        };
+
+        let (rest, channel) = s.rsplit_once(':').ok_or_else(invalid)?;
+        let (channel, i2c) = match channel.to_ascii_lowercase().as_str() {
+            "mux1" => (MuxChannel::Mux1, "i2c80"),
+            "mux2" => (MuxChannel::Mux2, "i2c81"),
+            "mux3" => (MuxChannel::Mux3, "i2c82"),
+            _ => return Err(invalid()),
+        };
+        let port = rest
+            .strip_suffix(i2c)
+            .and_then(|rest| rest.strip_suffix(':'))
+            .unwrap_or(rest);
+
+        Ok(MuxPort::new(port.parse().map_err(|_| invalid())?, channel))
+    }
+}
+
 #[macro_export]
 /// Add a sensor mode constant with getter and setter
 macro_rules! sensor_mode {
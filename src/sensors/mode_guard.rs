@@ -0,0 +1,67 @@
+//! RAII guard that temporarily switches the mode of a sensor.
+
+use std::fmt;
+use std::ops::Deref;
+
+use super::Sensor;
+use crate::Ev3Result;
+
+/// Guard returned by `Sensor::with_mode()`.
+///
+/// The sensor stays in the requested mode as long as the guard is alive.
+/// When the guard is dropped the previous mode is restored, even on early returns.
+/// The guard dereferences to the wrapped sensor.
+pub struct ModeGuard<'a, S: Sensor> {
+    sensor: &'a S,
+    previous_mode: String,
+    switched: bool,
+}
+
+impl<'a, S: Sensor> ModeGuard<'a, S> {
+    /// Switch `sensor` to `mode` and remember the current mode.
+    pub(crate) fn new(sensor: &'a S, mode: &str) -> Ev3Result<Self> {
+        let previous_mode = sensor.get_mode()?;
+        let switched = previous_mode != mode;
+
+        if switched {
+            sensor.set_mode(mode)?;
+        }
+
+        Ok(ModeGuard {
+            sensor,
+            previous_mode,
+            switched,
+        })
+    }
+
+    /// Returns the mode that will be restored when this guard is dropped.
+    pub fn get_previous_mode(&self) -> &str {
+        &self.previous_mode
+    }
+}
+
+impl<S: Sensor> Deref for ModeGuard<'_, S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        self.sensor
+    }
+}
+
+impl<S: Sensor> Drop for ModeGuard<'_, S> {
+    fn drop(&mut self) {
+        if self.switched {
+            // Errors cannot be propagated out of `drop`.
+            let _ = self.sensor.set_mode(&self.previous_mode);
+        }
+    }
+}
+
+impl<S: Sensor + fmt::Debug> fmt::Debug for ModeGuard<'_, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModeGuard")
+            .field("sensor", self.sensor)
+            .field("previous_mode", &self.previous_mode)
+            .finish()
+    }
+}
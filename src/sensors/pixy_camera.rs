@@ -0,0 +1,221 @@
+//! Charmed Labs Pixy (LEGO) camera. (<https://pixycam.com/>)
+
+use super::{Sensor, SensorPort};
+use crate::device::device_path;
+use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
+
+/// Object detected by the Pixy camera.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PixyBlock {
+    /// Signature (`1..=7`) or color code of the object.
+    pub signature: i32,
+    /// X coordinate of the object center (`0..=255`).
+    pub x: i32,
+    /// Y coordinate of the object center (`0..=199`).
+    pub y: i32,
+    /// Width of the object.
+    pub width: i32,
+    /// Height of the object.
+    pub height: i32,
+    /// Angle of the object, only available for color codes.
+    pub angle: Option<i32>,
+}
+
+/// Charmed Labs Pixy (LEGO) camera.
+#[derive(Debug, Clone, Device, Sensor)]
+pub struct PixyCamera {
+    driver: Driver,
+}
+
+impl PixyCamera {
+    fn new(driver: Driver) -> Self {
+        Self { driver }
+    }
+
+    findable!("lego-sensor", ["pixy-lego"], SensorPort, "PixyCamera", "in");
+
+    sensor_mode!(
+        "ALL",
+        MODE_ALL,
+        "Largest object of any signature (signature, x, y, width, height)",
+        set_mode_all,
+        is_mode_all
+    );
+    sensor_mode!(
+        "SIG1",
+        MODE_SIG1,
+        "Largest object with signature 1 (count, x, y, width, height)",
+        set_mode_sig1,
+        is_mode_sig1
+    );
+    sensor_mode!(
+        "SIG2",
+        MODE_SIG2,
+        "Largest object with signature 2 (count, x, y, width, height)",
+        set_mode_sig2,
+        is_mode_sig2
+    );
+    sensor_mode!(
+        "SIG3",
+        MODE_SIG3,
+        "Largest object with signature 3 (count, x, y, width, height)",
+        set_mode_sig3,
+        is_mode_sig3
+    );
+    sensor_mode!(
+        "SIG4",
+        MODE_SIG4,
+        "Largest object with signature 4 (count, x, y, width, height)",
+        set_mode_sig4,
+        is_mode_sig4
+    );
+    sensor_mode!(
+        "SIG5",
+        MODE_SIG5,
+        "Largest object with signature 5 (count, x, y, width, height)",
+        set_mode_sig5,
+        is_mode_sig5
+    );
+    sensor_mode!(
+        "SIG6",
+        MODE_SIG6,
+        "Largest object with signature 6 (count, x, y, width, height)",
+        set_mode_sig6,
+        is_mode_sig6
+    );
+    sensor_mode!(
+        "SIG7",
+        MODE_SIG7,
+        "Largest object with signature 7 (count, x, y, width, height)",
+        set_mode_sig7,
+        is_mode_sig7
+    );
+    sensor_mode!(
+        "COL-CODE",
+        MODE_COL_CODE,
+        "Largest color code object (count, x, y, width, height, angle)",
+        set_mode_col_code,
+        is_mode_col_code
+    );
+
+    /// Returns the objects detected in the current mode.
+    ///
+    /// The camera only reports the largest object, so the result contains at most one block.
+    /// It is empty if no object was detected.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::PixyCamera;
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let camera = PixyCamera::find()?;
+    /// camera.set_mode_sig1()?;
+    ///
+    /// for block in camera.get_blocks()? {
+    ///     println!("Object at ({}, {})", block.x, block.y);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_blocks(&self) -> Ev3Result<Vec<PixyBlock>> {
+        let mode = self.get_mode()?;
+        let format = self.get_bin_data_format()?;
+        let num_values = self.get_num_values()? as usize;
+        let mut data = [0u8; 32];
+        let len = self.get_bin_data_into(&mut data)?;
+
+        let values = decode_values(&data[..len], &format, num_values).map_err(|e| match e {
+            Ev3Error::UnsupportedFormat {
+                format, expected, ..
+            } => Ev3Error::UnsupportedFormat {
+                format,
+                expected,
+                path: self
+                    .get_attribute("bin_data_format")
+                    .ok()
+                    .map(|a| a.get_file_path()),
+            },
+            e => e,
+        })?;
+        if values.len() < 5 {
+            return Err(Ev3Error::InternalError {
+                msg: format!("PixyCamera returned {} values, expected 5", values.len()),
+                path: None,
+            });
+        }
+
+        let (signature, count) = match mode.as_str() {
+            Self::MODE_ALL => (values[0], values[0]),
+            Self::MODE_COL_CODE => (0, values[0]),
+            sig => match sig.strip_prefix("SIG").and_then(|n| n.parse().ok()) {
+                Some(signature) => (signature, values[0]),
+                None => {
+                    return Err(Ev3Error::UnsupportedOperation {
+                        device: "PixyCamera".to_owned(),
+                        operation: format!("get_blocks() in mode {mode}"),
+                        path: device_path(self),
+                    })
+                }
+            },
+        };
+
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+
+        Ok(vec![PixyBlock {
+            signature,
+            x: values[1],
+            y: values[2],
+            width: values[3],
+            height: values[4],
+            angle: values.get(5).copied(),
+        }])
+    }
+}
+
+/// Decodes the first `count` values of `bin_data` with the given `bin_data_format`.
+fn decode_values(data: &[u8], format: &str, count: usize) -> Ev3Result<Vec<i32>> {
+    let size = match format {
+        "u8" | "s8" => 1,
+        "u16" | "s16" | "s16_be" => 2,
+        "s32" | "s32_be" | "float" => 4,
+        _ => {
+            return Err(Ev3Error::UnsupportedFormat {
+                format: format.to_owned(),
+                expected: ["u8", "s8", "u16", "s16", "s16_be", "s32", "s32_be", "float"]
+                    .map(str::to_owned)
+                    .to_vec(),
+                path: None,
+            })
+        }
+    };
+
+    if data.len() < size * count {
+        return Err(Ev3Error::InternalError {
+            msg: format!(
+                "bin_data contains {} bytes, expected {}",
+                data.len(),
+                size * count
+            ),
+            path: None,
+        });
+    }
+
+    Ok(data
+        .chunks_exact(size)
+        .take(count)
+        .map(|b| match format {
+            "u8" => b[0] as i32,
+            "s8" => b[0] as i8 as i32,
+            "u16" => u16::from_ne_bytes([b[0], b[1]]) as i32,
+            "s16" => i16::from_ne_bytes([b[0], b[1]]) as i32,
+            "s16_be" => i16::from_be_bytes([b[0], b[1]]) as i32,
+            "s32" => i32::from_ne_bytes([b[0], b[1], b[2], b[3]]),
+            "s32_be" => i32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+            _ => f32::from_ne_bytes([b[0], b[1], b[2], b[3]]) as i32,
+        })
+        .collect())
+}
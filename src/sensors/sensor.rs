@@ -1,13 +1,28 @@
 //! Common utility functions for sensors.
 
-use crate::{Device, Ev3Result};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::{CachedSensor, ModeGuard, SensorInfo};
+use crate::device::device_path;
+use crate::{wait, Device, Ev3Error, Ev3Result};
+
+/// Maximal time `Sensor::ensure_mode()` waits for the sensor to signal the first sample after a mode switch.
+const MODE_SWITCH_TIMEOUT: Duration = Duration::from_millis(100);
 
 /// Common utility functions for sensors.
 pub trait Sensor: Device {
     /// Reading the file will give the unscaled raw values in the `value<N>` attributes.
     /// Use `bin_data_format`, `num_values` and the individual sensor documentation to determine how to interpret the data.
     fn get_bin_data(&self) -> Ev3Result<String> {
-        self.get_attribute("bin_data").get()
+        self.get_read_only_attribute("bin_data")?.get()
+    }
+
+    /// Reads the raw bytes of the `bin_data` attribute into `buf` without allocating.
+    /// Returns the number of bytes read. `bin_data` is at most 32 bytes long.
+    fn get_bin_data_into(&self, buf: &mut [u8]) -> Ev3Result<usize> {
+        self.get_read_only_attribute("bin_data")?
+            .get_raw_data_into(buf)
     }
 
     /// Returns the format of the values in `bin_data` for the current mode. Possible values are:
@@ -20,61 +35,143 @@ pub trait Sensor: Device {
     // * s32_be: Signed 32-bit integer, big endian
     // * float: IEEE 754 32-bit floating point (float)
     fn get_bin_data_format(&self) -> Ev3Result<String> {
-        self.get_attribute("bin_data_format").get()
+        self.get_read_only_attribute("bin_data_format")?.get()
     }
 
     /// Returns the number of decimal places for the values in the `value<N>` attributes of the current mode.
     fn get_decimals(&self) -> Ev3Result<i32> {
-        self.get_attribute("decimals").get()
+        self.get_read_only_attribute("decimals")?.get()
     }
 
     /// Returns the firmware version of the sensor if available.
     /// Currently only NXT/I2C sensors support this.
     fn get_fw_version(&self) -> Ev3Result<String> {
-        self.get_attribute("fw_version").get()
+        self.get_read_only_attribute("fw_version")?.get()
     }
 
     /// Returns the current mode.
     /// See the individual sensor documentation for a description of the modes available for each type of sensor.
     fn get_mode(&self) -> Ev3Result<String> {
-        self.get_attribute("mode").get()
+        self.get_attribute("mode")?.get()
     }
 
     /// Sets the sensor to that mode.
     /// See the individual sensor documentation for a description of the modes available for each type of sensor.
     fn set_mode(&self, mode: &str) -> Ev3Result<()> {
-        self.get_attribute("mode").set_str_slice(mode)
+        self.get_attribute("mode")?.set_str_slice(mode)
+    }
+
+    /// Switches the sensor to `mode` and returns a guard that restores the previous mode when dropped.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::{ColorSensor, Sensor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let color_sensor = ColorSensor::find()?;
+    /// color_sensor.set_mode_col_reflect()?;
+    ///
+    /// {
+    ///     let sensor = color_sensor.with_mode(ColorSensor::MODE_RGB_RAW)?;
+    ///     println!("Current rgb color: {:?}", sensor.get_rgb()?);
+    /// } // The sensor is switched back to `COL-REFLECT` here.
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn with_mode(&self, mode: &str) -> Ev3Result<ModeGuard<'_, Self>>
+    where
+        Self: Sized,
+    {
+        ModeGuard::new(self, mode)
+    }
+
+    /// Returns `true` if the typed getters switch the sensor to their required mode automatically.
+    /// See `set_auto_mode_switch()`.
+    fn get_auto_mode_switch(&self) -> bool {
+        false
+    }
+
+    /// Enables or disables the automatic mode switch of the typed getters, e.g. `ColorSensor::get_rgb()`.
+    ///
+    /// If disabled (default), the typed getters return `Ev3Error::WrongMode` if the sensor is not in a
+    /// matching mode. If enabled, they switch to the required mode and wait for the first sample instead.
+    /// Sensors without mode dependent getters ignore this setting.
+    fn set_auto_mode_switch(&mut self, _enabled: bool) {}
+
+    /// Checks that the sensor is in one of the given `modes`.
+    ///
+    /// Returns `Ev3Error::WrongMode` if the sensor is in another mode. If the automatic mode switch is enabled,
+    /// the sensor is switched to the first of the given modes instead and this call blocks until the sensor
+    /// signals the first sample of the new mode with a change of `value0`, at most 100 milliseconds.
+    /// Attributes that are not backed by a file, e.g. of `MockEv3`, provide no change signal and are read immediately.
+    fn ensure_mode(&self, modes: &[&str]) -> Ev3Result<()> {
+        let mode = self.get_mode()?;
+        if modes.iter().any(|m| *m == mode) {
+            return Ok(());
+        }
+
+        let target = match modes.first() {
+            Some(target) if self.get_auto_mode_switch() => target,
+            _ => {
+                return Err(Ev3Error::WrongMode {
+                    expected: modes.iter().map(|m| (*m).to_owned()).collect(),
+                    actual: mode,
+                    path: self
+                        .get_attribute("mode")
+                        .ok()
+                        .map(|mode| mode.get_file_path()),
+                })
+            }
+        };
+
+        // The value attributes keep the old reading until the sensor delivers its first sample in the new mode,
+        // which is signaled as change of `value0`. Reading the attribute arms the notification.
+        let value0 = self.get_read_only_attribute("value0").ok();
+        if let Some(value0) = &value0 {
+            let _ = value0.get::<String>();
+        }
+
+        self.set_mode(target)?;
+
+        if let Some(value0) = value0 {
+            let fd = value0.get_raw_fd();
+            if fd >= 0 {
+                wait::wait_file_changes(fd, Some(MODE_SWITCH_TIMEOUT));
+            }
+        }
+
+        Ok(())
     }
 
     /// Returns a list of the valid modes for the sensor.
     fn get_modes(&self) -> Ev3Result<Vec<String>> {
-        self.get_attribute("modes").get_vec()
+        self.get_read_only_attribute("modes")?.get_vec()
     }
 
     /// Returns the number of `value<N>` attributes that will return a valid value for the current mode.
     fn get_num_values(&self) -> Ev3Result<i32> {
-        self.get_attribute("num_values").get()
+        self.get_read_only_attribute("num_values")?.get()
     }
 
     /// Returns the polling period of the sensor in milliseconds.
     /// Returns `-EOPNOTSUPP` if changing polling is not supported.
     /// Note: Setting poll_ms too high can cause the input port auto detection to fail.
-    /// If this happens, use the mode attribute of the port to force the port to `nxt-i2c mode`. Values must not be negative.
+    /// If this happens, use `LegoPort::set_mode()` to force the port to `nxt-i2c` mode. Values must not be negative.
     fn get_poll_ms(&self) -> Ev3Result<i32> {
-        self.get_attribute("poll_ms").get()
+        self.get_attribute("poll_ms")?.get()
     }
 
     /// Sets the polling period of the sensor in milliseconds.
     /// Setting to 0 disables polling.
     /// Note: Setting poll_ms too high can cause the input port auto detection to fail.
-    /// If this happens, use the mode attribute of the port to force the port to `nxt-i2c mode`. Values must not be negative.
+    /// If this happens, use `LegoPort::set_mode()` to force the port to `nxt-i2c` mode. Values must not be negative.
     fn set_poll_ms(&self, poll_ms: i32) -> Ev3Result<()> {
-        self.get_attribute("poll_ms").set(poll_ms)
+        self.get_attribute("poll_ms")?.set(poll_ms)
     }
 
     /// Returns the units of the measured value for the current mode. May return empty string if units are unknown.
     fn get_units(&self) -> Ev3Result<String> {
-        self.get_attribute("units").get()
+        self.get_read_only_attribute("units")?.get()
     }
 
     /// Returns the current `value{index}` value if available.
@@ -91,52 +188,190 @@ pub trait Sensor: Device {
             7 => self.get_value7(),
             _ => Ev3Result::Err(Ev3Error::InternalError {
                 msg: format!("Sensor value index {index} is out of bounds [0, 7]"),
+                path: None,
             }),
         }
     }
 
+    /// Reads the sensor with `read` until `cond` is met and returns the matching reading.
+    ///
+    /// The sensor is read again when it signals a new sample with a change of `value0`.
+    /// Attributes that are not backed by a file, e.g. of `MockEv3`, are read every 20 milliseconds instead.
+    ///
+    /// Returns `Ev3Error::Timeout` if no reading matches within the `timeout`
+    /// (None to wait forever) and the first read error otherwise.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use ev3dev_lang_rust::sensors::{Sensor, UltrasonicSensor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = UltrasonicSensor::find()?;
+    /// sensor.set_mode_us_dist_cm()?;
+    ///
+    /// // Wait until the wall is closer than 10 cm.
+    /// let distance = sensor.wait_for_value(
+    ///     UltrasonicSensor::get_distance_centimeters,
+    ///     |distance| *distance < 10.0,
+    ///     Some(Duration::from_secs(5)),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn wait_for_value<T>(
+        &self,
+        mut read: impl FnMut(&Self) -> Ev3Result<T>,
+        mut cond: impl FnMut(&T) -> bool,
+        timeout: Option<Duration>,
+    ) -> Ev3Result<T>
+    where
+        Self: Sized,
+    {
+        let start = Instant::now();
+        let value0 = self.get_read_only_attribute("value0")?;
+        let fd = value0.get_raw_fd();
+
+        loop {
+            // Reading `value0` arms its change notification, even if `read` only reads other values.
+            if fd >= 0 {
+                value0.get::<String>()?;
+            }
+
+            let value = read(self)?;
+            if cond(&value) {
+                return Ok(value);
+            }
+
+            let remaining = match timeout {
+                Some(timeout) => {
+                    let elapsed = start.elapsed();
+                    if elapsed >= timeout {
+                        return Err(Ev3Error::Timeout {
+                            timeout,
+                            path: device_path(self),
+                        });
+                    }
+                    Some(timeout - elapsed)
+                }
+                None => None,
+            };
+
+            if fd >= 0 {
+                wait::wait_file_changes(fd, remaining);
+            } else {
+                thread::sleep(
+                    remaining.map_or(wait::POLL_INTERVAL, |r| r.min(wait::POLL_INTERVAL)),
+                );
+            }
+        }
+    }
+
+    /// Waits until `value0` meets `cond` and returns the matching value.
+    /// Returns `Ev3Error::Timeout` if no value matches within the `timeout`.
+    ///
+    /// `value0` is unscaled, use `wait_for_value()` to wait for scaled readings.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use ev3dev_lang_rust::sensors::{ColorSensor, Sensor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let sensor = ColorSensor::find()?;
+    /// sensor.set_mode_col_reflect()?;
+    ///
+    /// // Wait for the black line.
+    /// sensor.wait_for(|reflection| reflection < 10, Duration::from_secs(5))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn wait_for(&self, mut cond: impl FnMut(i32) -> bool, timeout: Duration) -> Ev3Result<i32>
+    where
+        Self: Sized,
+    {
+        self.wait_for_value(Self::get_value0, |value| cond(*value), Some(timeout))
+    }
+
     /// Returns the current `value0` value if available.
     fn get_value0(&self) -> Ev3Result<i32> {
-        self.get_attribute("value0").get()
+        self.get_read_only_attribute("value0")?.get()
     }
 
     /// Returns the current `value1` value if available.
     fn get_value1(&self) -> Ev3Result<i32> {
-        self.get_attribute("value1").get()
+        self.get_read_only_attribute("value1")?.get()
     }
 
     /// Returns the current `value2` value if available.
     fn get_value2(&self) -> Ev3Result<i32> {
-        self.get_attribute("value2").get()
+        self.get_read_only_attribute("value2")?.get()
     }
 
     /// Returns the current `value3` value if available.
     fn get_value3(&self) -> Ev3Result<i32> {
-        self.get_attribute("value3").get()
+        self.get_read_only_attribute("value3")?.get()
     }
 
     /// Returns the current `value4` value if available.
     fn get_value4(&self) -> Ev3Result<i32> {
-        self.get_attribute("value4").get()
+        self.get_read_only_attribute("value4")?.get()
     }
 
     /// Returns the current `value5` value if available.
     fn get_value5(&self) -> Ev3Result<i32> {
-        self.get_attribute("value5").get()
+        self.get_read_only_attribute("value5")?.get()
     }
 
     /// Returns the current `value6` value if available.
     fn get_value6(&self) -> Ev3Result<i32> {
-        self.get_attribute("value6").get()
+        self.get_read_only_attribute("value6")?.get()
     }
 
     /// Returns the current `value7` value if available.
     fn get_value7(&self) -> Ev3Result<i32> {
-        self.get_attribute("value7").get()
+        self.get_read_only_attribute("value7")?.get()
+    }
+
+    /// Returns a wrapper that reuses readings of `value0` to `value7` that are younger than `max_age`.
+    /// Clones of the wrapper share the cache. See `CachedSensor`.
+    fn cached(&self, max_age: Duration) -> CachedSensor<Self>
+    where
+        Self: Sized + Clone,
+    {
+        CachedSensor::new(self.clone(), max_age)
+    }
+
+    /// Returns a snapshot of the driver name, address, modes, current mode, units, decimals,
+    /// number of values and binary data format of this sensor.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ev3dev_lang_rust::sensors::{ColorSensor, Sensor};
+    ///
+    /// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+    /// let color_sensor = ColorSensor::find()?;
+    ///
+    /// let info = color_sensor.info()?;
+    /// println!("{} at {} in mode {}", info.driver_name, info.address, info.mode);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn info(&self) -> Ev3Result<SensorInfo> {
+        Ok(SensorInfo {
+            driver_name: self.get_driver_name()?,
+            address: self.get_address()?,
+            modes: self.get_modes()?,
+            mode: self.get_mode()?,
+            units: self.get_units()?,
+            decimals: self.get_decimals()?,
+            num_values: self.get_num_values()?,
+            bin_data_format: self.get_bin_data_format()?,
+        })
     }
 
     /// Returns a space delimited string representing sensor-specific text values. Returns `-EOPNOTSUPP` if a sensor does not support text values.
     fn get_text_value(&self) -> Ev3Result<String> {
-        self.get_attribute("text_value").get()
+        self.get_read_only_attribute("text_value")?.get()
     }
 }
@@ -0,0 +1,25 @@
+//! Introspection snapshot of a sensor.
+
+/// Snapshot of the static and mode dependent attributes of a sensor.
+///
+/// Created by `Sensor::info()`. Useful for debugging tools and generic dashboards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SensorInfo {
+    /// Name of the driver that provides this sensor.
+    pub driver_name: String,
+    /// Name of the port that the sensor is connected to.
+    pub address: String,
+    /// Valid modes of the sensor.
+    pub modes: Vec<String>,
+    /// Current mode of the sensor.
+    pub mode: String,
+    /// Units of the measured value for the current mode. May be empty if units are unknown.
+    pub units: String,
+    /// Number of decimal places for the values in the `value<N>` attributes of the current mode.
+    pub decimals: i32,
+    /// Number of `value<N>` attributes that will return a valid value for the current mode.
+    pub num_values: i32,
+    /// Format of the values in `bin_data` for the current mode.
+    pub bin_data_format: String,
+}
@@ -0,0 +1,118 @@
+//! Synchronized reading of several sensors.
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::Sensor;
+use crate::{Ev3Error, Ev3Result};
+
+/// Values of several sensors that were read back-to-back, created by `read_snapshot()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    /// Time in the middle between the first and the last reading.
+    pub timestamp: Instant,
+    /// Time between the start of the first and the end of the last reading.
+    pub skew: Duration,
+    /// Values `value0` to `value<num_values - 1>` of each sensor, in the order of the sensors.
+    pub readings: Vec<Vec<i32>>,
+}
+
+impl Snapshot {
+    /// Returns the value `value_index` of the sensor at `index`.
+    pub fn value(&self, index: usize, value_index: usize) -> Option<i32> {
+        self.readings.get(index)?.get(value_index).copied()
+    }
+
+    fn new(start: Instant, end: Instant, readings: Vec<Vec<i32>>) -> Self {
+        let skew = end - start;
+        Self {
+            timestamp: start + skew / 2,
+            skew,
+            readings,
+        }
+    }
+}
+
+fn read_values(sensor: &dyn Sensor, num_values: i32) -> Ev3Result<Vec<i32>> {
+    (0..num_values.clamp(0, 8) as u8)
+        .map(|index| sensor.get_value(index))
+        .collect()
+}
+
+fn num_values<S: Sensor + ?Sized>(sensors: &[&S]) -> Ev3Result<Vec<i32>> {
+    sensors
+        .iter()
+        .map(|sensor| sensor.get_num_values())
+        .collect()
+}
+
+/// Reads all values of the `sensors` back-to-back and returns them with a shared timestamp.
+///
+/// The number of values of each sensor is read before the first value, so only the values are
+/// inside the measured time span. This keeps e.g. the gyro angle and the line sensors of a robot consistent.
+///
+/// # Example
+/// ```no_run
+/// use ev3dev_lang_rust::sensors::{read_snapshot, ColorSensor, GyroSensor, SensorPort};
+///
+/// # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+/// let gyro = GyroSensor::get(SensorPort::In1)?;
+/// let left = ColorSensor::get(SensorPort::In2)?;
+/// let right = ColorSensor::get(SensorPort::In3)?;
+///
+/// let snapshot = read_snapshot(&[&gyro, &left, &right])?;
+/// println!(
+///     "Angle {:?}, reflection {:?} / {:?} within {:?}",
+///     snapshot.value(0, 0),
+///     snapshot.value(1, 0),
+///     snapshot.value(2, 0),
+///     snapshot.skew
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn read_snapshot(sensors: &[&dyn Sensor]) -> Ev3Result<Snapshot> {
+    let num_values = num_values(sensors)?;
+
+    let start = Instant::now();
+    let readings = sensors
+        .iter()
+        .zip(num_values)
+        .map(|(sensor, num_values)| read_values(*sensor, num_values))
+        .collect::<Ev3Result<Vec<_>>>()?;
+
+    Ok(Snapshot::new(start, Instant::now(), readings))
+}
+
+/// Like `read_snapshot()`, but reads every sensor in its own thread.
+///
+/// This reduces the skew if reading a sensor blocks, e.g. for I2C sensors,
+/// but adds the overhead of starting the threads.
+pub fn read_snapshot_parallel(sensors: &[&(dyn Sensor + Sync)]) -> Ev3Result<Snapshot> {
+    let num_values = num_values(sensors)?;
+
+    let start = Instant::now();
+    let readings = thread::scope(|scope| {
+        let handles = sensors
+            .iter()
+            .zip(num_values)
+            .map(|(sensor, num_values)| {
+                scope.spawn(move || read_values(*sensor as &dyn Sensor, num_values))
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle.join().unwrap_or_else(|_| {
+                    Err(Ev3Error::InternalError {
+                        msg: "Reading a sensor panicked".to_owned(),
+                        path: None,
+                    })
+                })
+            })
+            .collect::<Ev3Result<Vec<_>>>()
+    })?;
+
+    Ok(Snapshot::new(start, Instant::now(), readings))
+}
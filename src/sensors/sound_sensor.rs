@@ -0,0 +1,91 @@
+//! LEGO NXT sound sensor.
+
+use super::{Sensor, SensorPort};
+use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
+use std::cell::Cell;
+
+/// LEGO NXT sound sensor.
+#[derive(Debug, Clone, Device, Sensor)]
+pub struct SoundSensor {
+    driver: Driver,
+    auto_mode_switch: bool,
+    db_scale: Cell<Option<f32>>,
+    dba_scale: Cell<Option<f32>>,
+}
+
+impl SoundSensor {
+    fn new(driver: Driver) -> Self {
+        Self {
+            driver,
+            auto_mode_switch: false,
+            db_scale: Cell::new(None),
+            dba_scale: Cell::new(None),
+        }
+    }
+
+    findable!(
+        "lego-sensor",
+        ["lego-nxt-sound"],
+        SensorPort,
+        "SoundSensor",
+        "in"
+    );
+
+    sensor_mode!(
+        "DB",
+        MODE_DB,
+        "Sound pressure level. Flat weighting",
+        set_mode_db,
+        is_mode_db
+    );
+    sensor_mode!(
+        "DBA",
+        MODE_DBA,
+        "Sound pressure level. A weighting",
+        set_mode_dba,
+        is_mode_dba
+    );
+
+    /// A measurement of the sound pressure level, unscaled.
+    pub fn get_sound_intensity(&self) -> Ev3Result<i32> {
+        self.get_value0()
+    }
+
+    /// A measurement of the measured sound pressure level, as a percentage.
+    /// Uses a flat weighting. Requires the mode `DB`.
+    pub fn get_sound_pressure(&self) -> Ev3Result<f32> {
+        self.ensure_mode(&[Self::MODE_DB])?;
+
+        let scale_field = self.db_scale.get();
+        let scale = match scale_field {
+            Some(s) => s,
+            None => {
+                let decimals = self.get_decimals()?;
+                let s = 10f32.powi(-decimals);
+                self.db_scale.set(Some(s));
+                s
+            }
+        };
+
+        Ok((self.get_value0()? as f32) * scale)
+    }
+
+    /// A measurement of the measured sound pressure level, as a percentage.
+    /// Uses A-weighting, which focuses on levels up to 55 dB. Requires the mode `DBA`.
+    pub fn get_sound_pressure_low(&self) -> Ev3Result<f32> {
+        self.ensure_mode(&[Self::MODE_DBA])?;
+
+        let scale_field = self.dba_scale.get();
+        let scale = match scale_field {
+            Some(s) => s,
+            None => {
+                let decimals = self.get_decimals()?;
+                let s = 10f32.powi(-decimals);
+                self.dba_scale.set(Some(s));
+                s
+            }
+        };
+
+        Ok((self.get_value0()? as f32) * scale)
+    }
+}
@@ -0,0 +1,84 @@
+//! LEGO NXT temperature sensor.
+
+use super::{Sensor, SensorPort};
+use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result};
+use std::cell::Cell;
+
+/// LEGO NXT temperature sensor.
+#[derive(Debug, Clone, Device, Sensor)]
+pub struct TemperatureSensor {
+    driver: Driver,
+    auto_mode_switch: bool,
+    celsius_scale: Cell<Option<f32>>,
+    fahrenheit_scale: Cell<Option<f32>>,
+}
+
+impl TemperatureSensor {
+    fn new(driver: Driver) -> Self {
+        Self {
+            driver,
+            auto_mode_switch: false,
+            celsius_scale: Cell::new(None),
+            fahrenheit_scale: Cell::new(None),
+        }
+    }
+
+    findable!(
+        "lego-sensor",
+        ["lego-nxt-temp"],
+        SensorPort,
+        "TemperatureSensor",
+        "in"
+    );
+
+    sensor_mode!(
+        "NXT-TEMP-C",
+        MODE_NXT_TEMP_C,
+        "Temperature in degrees Celsius",
+        set_mode_nxt_temp_c,
+        is_mode_nxt_temp_c
+    );
+    sensor_mode!(
+        "NXT-TEMP-F",
+        MODE_NXT_TEMP_F,
+        "Temperature in degrees Fahrenheit",
+        set_mode_nxt_temp_f,
+        is_mode_nxt_temp_f
+    );
+
+    /// Temperature in degrees Celsius. Requires the mode `NXT-TEMP-C`.
+    pub fn get_temperature_celsius(&self) -> Ev3Result<f32> {
+        self.ensure_mode(&[Self::MODE_NXT_TEMP_C])?;
+
+        let scale_field = self.celsius_scale.get();
+        let scale = match scale_field {
+            Some(s) => s,
+            None => {
+                let decimals = self.get_decimals()?;
+                let s = 10f32.powi(-decimals);
+                self.celsius_scale.set(Some(s));
+                s
+            }
+        };
+
+        Ok((self.get_value0()? as f32) * scale)
+    }
+
+    /// Temperature in degrees Fahrenheit. Requires the mode `NXT-TEMP-F`.
+    pub fn get_temperature_fahrenheit(&self) -> Ev3Result<f32> {
+        self.ensure_mode(&[Self::MODE_NXT_TEMP_F])?;
+
+        let scale_field = self.fahrenheit_scale.get();
+        let scale = match scale_field {
+            Some(s) => s,
+            None => {
+                let decimals = self.get_decimals()?;
+                let s = 10f32.powi(-decimals);
+                self.fahrenheit_scale.set(Some(s));
+                s
+            }
+        };
+
+        Ok((self.get_value0()? as f32) * scale)
+    }
+}
@@ -1,9 +1,9 @@
-//! Touch Sensor
+//! LEGO EV3 and NXT touch sensor.
 
 use super::{Sensor, SensorPort};
 use crate::{Attribute, Device, Driver, Ev3Error, Ev3Result};
 
-/// Touch Sensor
+/// LEGO EV3 and NXT touch sensor.
 #[derive(Debug, Clone, Device, Sensor)]
 pub struct TouchSensor {
     driver: Driver,
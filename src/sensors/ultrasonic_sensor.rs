@@ -8,6 +8,7 @@ use std::cell::Cell;
 #[derive(Debug, Clone, Device, Sensor)]
 pub struct UltrasonicSensor {
     driver: Driver,
+    auto_mode_switch: bool,
     cm_scale: Cell<Option<f32>>,
     in_scale: Cell<Option<f32>>,
 }
@@ -16,6 +17,7 @@ impl UltrasonicSensor {
     fn new(driver: Driver) -> Self {
         Self {
             driver,
+            auto_mode_switch: false,
             cm_scale: Cell::new(None),
             in_scale: Cell::new(None),
         }
@@ -85,7 +87,14 @@ impl UltrasonicSensor {
     }
 
     /// Measurement of the distance detected by the sensor, in centimeters.
+    /// Requires one of the modes `US-DIST-CM`, `US-SI-CM` or `US-DC-CM`.
     pub fn get_distance_centimeters(&self) -> Ev3Result<f32> {
+        self.ensure_mode(&[
+            Self::MODE_US_DIST_CM,
+            Self::MODE_US_SI_CM,
+            Self::MODE_US_DC_CM,
+        ])?;
+
         let scale_field = self.cm_scale.get();
         let scale = match scale_field {
             Some(s) => s,
@@ -100,8 +109,15 @@ impl UltrasonicSensor {
         Ok((self.get_value0()? as f32) * scale)
     }
 
-    /// Measurement of the distance detected by the sensor, in centimeters.
+    /// Measurement of the distance detected by the sensor, in inches.
+    /// Requires one of the modes `US-DIST-IN`, `US-SI-IN` or `US-DC-IN`.
     pub fn get_distance_inches(&self) -> Ev3Result<f32> {
+        self.ensure_mode(&[
+            Self::MODE_US_DIST_IN,
+            Self::MODE_US_SI_IN,
+            Self::MODE_US_DC_IN,
+        ])?;
+
         let scale_field = self.in_scale.get();
         let scale = match scale_field {
             Some(s) => s,
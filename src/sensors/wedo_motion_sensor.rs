@@ -0,0 +1,44 @@
+//! LEGO WeDo motion sensor.
+
+use super::Sensor;
+use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result, WedoPort};
+
+/// LEGO WeDo motion sensor (45304) on a WeDo USB hub. Measures the distance to an object with infrared light.
+#[derive(Debug, Clone, Device, Sensor)]
+pub struct WedoMotionSensor {
+    driver: Driver,
+    auto_mode_switch: bool,
+}
+
+impl WedoMotionSensor {
+    fn new(driver: Driver) -> Self {
+        Self {
+            driver,
+            auto_mode_switch: false,
+        }
+    }
+
+    findable!(
+        "lego-sensor",
+        ["wedo-motion"],
+        WedoPort,
+        "WedoMotionSensor",
+        "wedo"
+    );
+
+    sensor_mode!("PROX", MODE_PROX, "Proximity", set_mode_prox, is_mode_prox);
+    sensor_mode!(
+        "RAW",
+        MODE_RAW,
+        "Raw value of the sensor",
+        set_mode_raw,
+        is_mode_raw
+    );
+
+    /// Returns the proximity of an object in percent, 0 is far away and 100 is close.
+    /// The sensor has to be in the mode `PROX`.
+    pub fn get_proximity(&self) -> Ev3Result<i32> {
+        self.ensure_mode(&[Self::MODE_PROX])?;
+        self.get_value0()
+    }
+}
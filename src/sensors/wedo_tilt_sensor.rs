@@ -0,0 +1,88 @@
+//! LEGO WeDo tilt sensor.
+
+use super::Sensor;
+use crate::{sensor_mode, Attribute, Device, Driver, Ev3Error, Ev3Result, WedoPort};
+
+/// Direction a `WedoTiltSensor` is tilted to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WedoTilt {
+    /// The sensor lies flat.
+    Level,
+    /// Tilted to the front.
+    Front,
+    /// Tilted to the back.
+    Back,
+    /// Tilted to the left.
+    Left,
+    /// Tilted to the right.
+    Right,
+}
+
+/// LEGO WeDo tilt sensor (45305) on a WeDo USB hub.
+#[derive(Debug, Clone, Device, Sensor)]
+pub struct WedoTiltSensor {
+    driver: Driver,
+    auto_mode_switch: bool,
+}
+
+impl WedoTiltSensor {
+    fn new(driver: Driver) -> Self {
+        Self {
+            driver,
+            auto_mode_switch: false,
+        }
+    }
+
+    findable!(
+        "lego-sensor",
+        ["wedo-tilt"],
+        WedoPort,
+        "WedoTiltSensor",
+        "wedo"
+    );
+
+    sensor_mode!(
+        "TILT",
+        MODE_TILT,
+        "Tilt direction",
+        set_mode_tilt,
+        is_mode_tilt
+    );
+    sensor_mode!(
+        "TILT-AXIS",
+        MODE_TILT_AXIS,
+        "Tilt of the x and y axes (-1, 0 or 1)",
+        set_mode_tilt_axis,
+        is_mode_tilt_axis
+    );
+    sensor_mode!(
+        "RAW",
+        MODE_RAW,
+        "Raw value of the sensor",
+        set_mode_raw,
+        is_mode_raw
+    );
+
+    /// Returns the direction the sensor is tilted to. The sensor has to be in the mode `TILT`.
+    pub fn get_tilt(&self) -> Ev3Result<WedoTilt> {
+        self.ensure_mode(&[Self::MODE_TILT])?;
+        match self.get_value0()? {
+            0 => Ok(WedoTilt::Level),
+            1 => Ok(WedoTilt::Front),
+            2 => Ok(WedoTilt::Back),
+            3 => Ok(WedoTilt::Left),
+            4 => Ok(WedoTilt::Right),
+            value => Err(Ev3Error::InternalError {
+                msg: format!("Invalid WeDo tilt direction {value}"),
+                path: None,
+            }),
+        }
+    }
+
+    /// Returns the tilt of the x and y axes, each -1, 0 or 1. The sensor has to be in the mode `TILT-AXIS`.
+    pub fn get_tilt_axis(&self) -> Ev3Result<(i32, i32)> {
+        self.ensure_mode(&[Self::MODE_TILT_AXIS])?;
+        Ok((self.get_value0()?, self.get_value1()?))
+    }
+}
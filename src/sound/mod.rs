@@ -0,0 +1,572 @@
+//! Sound-related functions. It can beep, play wav files, or convert text to
+//! speech.
+//!
+//! Note that all methods of the module spawn system processes and return
+//! `std::process::Child` objects. The methods are asynchronous (they return
+//! immediately after child process was spawned, without waiting for its
+//! completion), but you can call wait() on the returned result.
+//!
+//! # Examples
+//! ```no_run
+//! # use ev3dev_lang_rust::Ev3Result;
+//! use ev3dev_lang_rust::sound;
+//!
+//! # fn main() -> Ev3Result<()> {
+//! // Play "bark.wav", return immediately:
+//! sound::play("bark.wav")?;
+//!
+//! // Introduce yourself, wait for completion:
+//! sound::speak("Hello, I am Robot")?.wait()?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Tones can also be played directly on the `Speaker` input device without
+//! spawning `beep`. The `start_*` functions return a `SoundHandle` that can be
+//! waited on or cancelled.
+//!
+//! ```no_run
+//! # use ev3dev_lang_rust::Ev3Result;
+//! use ev3dev_lang_rust::sound;
+//! use std::thread;
+//! use std::time::Duration;
+//!
+//! # fn main() -> Ev3Result<()> {
+//! let mut melody = sound::start_tone_sequence(&[(440.0, 200, 50), (660.0, 200, 0)])?;
+//! thread::sleep(Duration::from_millis(100));
+//! melody.cancel()?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod rso;
+pub use self::rso::{RmdMelody, RsoSound};
+
+use crate::task::CancelToken;
+use crate::{Ev3Error, Ev3Result};
+use std::ffi::OsStr;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::mem;
+use std::path::Path;
+use std::process::{Child, Command, Stdio};
+use std::slice;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// Input event device of the EV3 speaker.
+const SPEAKER_EVENT_FILE: &str = "/dev/input/by-path/platform-sound-event";
+/// Event type of sound events, see /include/uapi/linux/input-event-codes.h.
+const EV_SND: u16 = 0x12;
+/// Sound event code to play a tone with the event value as frequency.
+const SND_TONE: u16 = 0x02;
+
+/// Call beep command.
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound;
+///
+/// # fn main() -> Ev3Result<()> {
+/// sound::beep()?.wait()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn beep() -> Ev3Result<Child> {
+    Command::new("/usr/bin/beep")
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(Ev3Error::io(Path::new("/usr/bin/beep")))
+}
+
+/// Call beep command with the provided arguments.
+///
+/// See `beep man page`_ and google `linux beep music`_ for inspiration.
+/// * `beep man page`: <https://linux.die.net/man/1/beep>
+/// * `linux beep music`: <https://www.google.com/search?q=linux+beep+music>
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound;
+///
+/// # fn main() -> Ev3Result<()> {
+/// sound::beep_args(&[""])?.wait()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn beep_args<I, S>(args: I) -> Ev3Result<Child>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<OsStr>,
+{
+    Command::new("/usr/bin/beep")
+        .args(args)
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(Ev3Error::io(Path::new("/usr/bin/beep")))
+}
+
+/// Play tone sequence. The tone_sequence parameter is a list of tuples,
+/// where each tuple contains up to three numbers. The first number is
+/// frequency in Hz, the second is duration in milliseconds, and the third
+/// is delay in milliseconds between this and the next tone in the
+/// sequence.
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound;
+///
+/// # fn main() -> Ev3Result<()> {
+/// sound::tone(466.0, 500)?.wait()?;
+/// # Ok(())
+/// # }
+pub fn tone(frequency: f32, duration: i32) -> Ev3Result<Child> {
+    beep_args(vec![format!("-f {frequency}"), format!("-l {duration}")])
+}
+
+/// Play tone sequence. The tone_sequence parameter is a list of tuples,
+/// where each tuple contains up to three numbers. The first number is
+/// frequency in Hz, the second is duration in milliseconds, and the third
+/// is delay in milliseconds between this and the next tone in the
+/// sequence.
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound;
+///
+/// # fn main() -> Ev3Result<()> {
+/// sound::tone_sequence(
+///     &[
+///         (392.00, 350, 100), (392.00, 350, 100), (392.00, 350, 100), (311.1, 250, 100),
+///         (466.20, 025, 100), (392.00, 350, 100), (311.10, 250, 100), (466.2, 025, 100),
+///         (392.00, 700, 100), (587.32, 350, 100), (587.32, 350, 100),
+///         (587.32, 350, 100), (622.26, 250, 100), (466.20, 025, 100),
+///         (369.99, 350, 100), (311.10, 250, 100), (466.20, 025, 100), (392.00, 700, 100),
+///         (784.00, 350, 100), (392.00, 250, 100), (392.00, 025, 100), (784.00, 350, 100),
+///         (739.98, 250, 100), (698.46, 025, 100), (659.26, 025, 100),
+///         (622.26, 025, 100), (659.26, 050, 400), (415.30, 025, 200), (554.36, 350, 100),
+///         (523.25, 250, 100), (493.88, 025, 100), (466.16, 025, 100), (440.00, 025, 100),
+///         (466.16, 050, 400), (311.13, 025, 200), (369.99, 350, 100),
+///         (311.13, 250, 100), (392.00, 025, 100), (466.16, 350, 100), (392.00, 250, 100),
+///         (466.16, 025, 100), (587.32, 700, 100), (784.00, 350, 100), (392.00, 250, 100),
+///         (392.00, 025, 100), (784.00, 350, 100), (739.98, 250, 100), (698.46, 025, 100),
+///         (659.26, 025, 100), (622.26, 025, 100), (659.26, 050, 400), (415.30, 025, 200),
+///         (554.36, 350, 100), (523.25, 250, 100), (493.88, 025, 100),
+///         (466.16, 025, 100), (440.00, 025, 100), (466.16, 050, 400), (311.13, 025, 200),
+///         (392.00, 350, 100), (311.13, 250, 100), (466.16, 025, 100),
+///         (392.00, 300, 150), (311.13, 250, 100), (466.16, 025, 100), (392.00, 700, 0)
+///     ]
+/// )?.wait()?;
+/// # Ok(())
+/// # }
+pub fn tone_sequence(sequence: &[(f32, i32, i32)]) -> Ev3Result<Child> {
+    let tones: Vec<String> = sequence
+        .iter()
+        .map(|(frequency, duration, delay)| {
+            vec![
+                format!("-f {frequency}"),
+                format!("-l {duration}"),
+                format!("-D {delay}"),
+            ]
+        })
+        .collect::<Vec<Vec<String>>>()
+        .join(&["-n".to_owned()][..]);
+
+    beep_args(tones)
+}
+
+/// Play wav file
+pub fn play(wav_file: &str) -> Ev3Result<Child> {
+    Command::new("/usr/bin/aplay")
+        .arg("-q")
+        .arg(wav_file)
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(Ev3Error::io(Path::new("/usr/bin/aplay")))
+}
+
+/// Play wav file and return a handle that can be waited on or cancelled.
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound;
+///
+/// # fn main() -> Ev3Result<()> {
+/// let mut bark = sound::start_play("bark.wav")?;
+/// if !bark.is_finished()? {
+///     bark.cancel()?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn start_play(wav_file: &str) -> Ev3Result<SoundHandle> {
+    play(wav_file).map(SoundHandle::from)
+}
+
+/// Play a tone sequence on the `Speaker` in a background thread.
+///
+/// Each tuple contains the frequency in Hz, the duration in milliseconds and
+/// the delay in milliseconds between this and the next tone, like `tone_sequence()`.
+/// Cancelling the returned handle silences the speaker immediately.
+pub fn start_tone_sequence(sequence: &[(f32, i32, i32)]) -> Ev3Result<SoundHandle> {
+    let speaker = Speaker::new()?;
+    let sequence = sequence.to_vec();
+    let token = CancelToken::new();
+
+    let thread_token = token.clone();
+    let handle = thread::spawn(move || {
+        let result = speaker.play_sequence(&sequence, &thread_token);
+        // Never leave the speaker beeping, even if the sequence failed.
+        speaker.stop().and(result)
+    });
+
+    Ok(SoundHandle {
+        inner: HandleKind::Thread {
+            token,
+            handle: Some(handle),
+        },
+    })
+}
+
+/// The EV3 speaker as input event device.
+///
+/// Plays tones by writing `EV_SND` events, which does not require the `beep` command.
+/// The speaker is silenced when it is dropped.
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound::Speaker;
+/// use std::time::Duration;
+///
+/// # fn main() -> Ev3Result<()> {
+/// let speaker = Speaker::new()?;
+/// speaker.tone(440, Duration::from_millis(500))?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct Speaker {
+    file: File,
+}
+
+impl Speaker {
+    /// Opens the speaker input device `/dev/input/by-path/platform-sound-event`.
+    pub fn new() -> Ev3Result<Self> {
+        let path = Path::new(SPEAKER_EVENT_FILE);
+        let file = OpenOptions::new()
+            .write(true)
+            .open(path)
+            .map_err(Ev3Error::io(path))?;
+
+        Ok(Self { file })
+    }
+
+    /// Starts a tone with the given frequency in Hz. The tone plays until `stop()` is called.
+    pub fn start_tone(&self, frequency: u32) -> Ev3Result<()> {
+        self.write_event(frequency.min(i32::MAX as u32) as i32)
+    }
+
+    /// Silences the speaker.
+    pub fn stop(&self) -> Ev3Result<()> {
+        self.write_event(0)
+    }
+
+    /// Plays a tone with the given frequency in Hz and blocks for its `duration`.
+    pub fn tone(&self, frequency: u32, duration: Duration) -> Ev3Result<()> {
+        self.start_tone(frequency)?;
+        thread::sleep(duration);
+        self.stop()
+    }
+
+    /// Plays a short beep like the `beep` command (440 Hz for 200 ms).
+    pub fn beep(&self) -> Ev3Result<()> {
+        self.tone(440, Duration::from_millis(200))
+    }
+
+    /// Plays the tones of `sequence` until it is finished or the `token` is cancelled.
+    fn play_sequence(&self, sequence: &[(f32, i32, i32)], token: &CancelToken) -> Ev3Result<()> {
+        for &(frequency, duration, delay) in sequence {
+            self.start_tone(frequency.round().max(0.0) as u32)?;
+            if !token.sleep(millis(duration)) {
+                return Ok(());
+            }
+            self.stop()?;
+            if !token.sleep(millis(delay)) {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a single `SND_TONE` event.
+    fn write_event(&self, value: i32) -> Ev3Result<()> {
+        let mut event: libc::input_event = unsafe { mem::zeroed() };
+        event.type_ = EV_SND;
+        event.code = SND_TONE;
+        event.value = value;
+
+        let buffer = unsafe {
+            slice::from_raw_parts(
+                &event as *const libc::input_event as *const u8,
+                mem::size_of::<libc::input_event>(),
+            )
+        };
+        (&self.file)
+            .write_all(buffer)
+            .map_err(Ev3Error::io(Path::new(SPEAKER_EVENT_FILE)))
+    }
+}
+
+impl Drop for Speaker {
+    fn drop(&mut self) {
+        let _ = self.stop();
+    }
+}
+
+/// Converts a possibly negative number of milliseconds to a `Duration`.
+fn millis(ms: i32) -> Duration {
+    Duration::from_millis(ms.max(0) as u64)
+}
+
+/// Handle to a sound that is played in the background.
+///
+/// Returned by the `start_*` functions. Dropping the handle does not stop the sound.
+pub struct SoundHandle {
+    inner: HandleKind,
+}
+
+enum HandleKind {
+    Process(Child),
+    Thread {
+        token: CancelToken,
+        handle: Option<JoinHandle<Ev3Result<()>>>,
+    },
+}
+
+impl SoundHandle {
+    /// Blocks until the sound is finished.
+    pub fn wait(&mut self) -> Ev3Result<()> {
+        match &mut self.inner {
+            HandleKind::Process(child) => {
+                child.wait()?;
+                Ok(())
+            }
+            HandleKind::Thread { handle, .. } => match handle.take() {
+                Some(handle) => handle.join().unwrap_or_else(|_| {
+                    Err(Ev3Error::InternalError {
+                        msg: "Sound thread panicked!".to_owned(),
+                        path: None,
+                    })
+                }),
+                None => Ok(()),
+            },
+        }
+    }
+
+    /// Stops the sound and waits until it is silenced.
+    pub fn cancel(&mut self) -> Ev3Result<()> {
+        match &mut self.inner {
+            HandleKind::Process(child) => {
+                // The process may have exited on its own in the meantime.
+                if child.try_wait()?.is_none() {
+                    child.kill()?;
+                }
+            }
+            HandleKind::Thread { token, .. } => token.cancel(),
+        }
+        self.wait()
+    }
+
+    /// Checks if the sound is finished without blocking.
+    pub fn is_finished(&mut self) -> Ev3Result<bool> {
+        match &mut self.inner {
+            HandleKind::Process(child) => Ok(child.try_wait()?.is_some()),
+            HandleKind::Thread { handle, .. } => {
+                Ok(handle.as_ref().is_none_or(JoinHandle::is_finished))
+            }
+        }
+    }
+}
+
+impl From<Child> for SoundHandle {
+    fn from(child: Child) -> Self {
+        Self {
+            inner: HandleKind::Process(child),
+        }
+    }
+}
+
+impl fmt::Debug for SoundHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.inner {
+            HandleKind::Process(child) => f
+                .debug_struct("SoundHandle")
+                .field("pid", &child.id())
+                .finish(),
+            HandleKind::Thread { token, .. } => {
+                f.debug_struct("SoundHandle").field("token", token).finish()
+            }
+        }
+    }
+}
+
+/// Options for the `espeak` text to speech conversion of `speak_with()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpeakOptions {
+    /// Voice name, e.g. `en`, `de` or `en+f3`. `None` uses the espeak default voice.
+    pub voice: Option<String>,
+    /// Speed in words per minute (espeak range: 80 to 450).
+    pub speed: u32,
+    /// Amplitude (espeak range: 0 to 200).
+    pub amplitude: u32,
+}
+
+impl Default for SpeakOptions {
+    /// The options used by `speak()`: default voice, 130 words per minute and amplitude 200.
+    fn default() -> Self {
+        Self {
+            voice: None,
+            speed: 130,
+            amplitude: 200,
+        }
+    }
+}
+
+/// Speak the given text aloud.
+pub fn speak(text: &str) -> Ev3Result<Child> {
+    speak_with(text, &SpeakOptions::default())
+}
+
+/// Speak the given text aloud with the given voice, speed and amplitude.
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound::{self, SpeakOptions};
+///
+/// # fn main() -> Ev3Result<()> {
+/// let options = SpeakOptions {
+///     voice: Some("de".to_owned()),
+///     speed: 100,
+///     ..SpeakOptions::default()
+/// };
+/// sound::speak_with("Hallo, ich bin ein Roboter", &options)?.wait()?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn speak_with(text: &str, options: &SpeakOptions) -> Ev3Result<Child> {
+    let mut espeak = Command::new("/usr/bin/espeak");
+    espeak
+        .arg("--stdout")
+        .args(["-a", &options.amplitude.to_string()])
+        .args(["-s", &options.speed.to_string()]);
+    if let Some(voice) = &options.voice {
+        espeak.args(["-v", voice]);
+    }
+    // Separate the text from the options, it may start with a dash.
+    let espeak = espeak
+        .args(["--", text])
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(Ev3Error::io(Path::new("/usr/bin/espeak")))?;
+
+    Command::new("/usr/bin/aplay")
+        .arg("-q")
+        .stdin(espeak.stdout.ok_or(Ev3Error::InternalError {
+            msg: "`espeak` pipe to `aplay` could not be created!".to_owned(),
+            path: None,
+        })?)
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(Ev3Error::io(Path::new("/usr/bin/aplay")))
+}
+
+/// Get the main channel name or 'Playback' if not available.
+fn get_channels() -> Ev3Result<Vec<String>> {
+    let out = String::from_utf8(
+        Command::new("/usr/bin/amixer")
+            .arg("scontrols")
+            .output()
+            .map_err(Ev3Error::io(Path::new("/usr/bin/amixer")))?
+            .stdout,
+    )?;
+
+    let mut channels: Vec<String> = out
+        .split('\n')
+        .filter_map(|line| {
+            let vol_start = line.find('\'').unwrap_or(0) + 1;
+            let vol_end = line.rfind('\'').unwrap_or(1);
+
+            if vol_start >= vol_end {
+                None
+            } else {
+                Some(line[vol_start..vol_end].to_owned())
+            }
+        })
+        .collect();
+
+    if channels.is_empty() {
+        channels.push("Playback".to_owned());
+    }
+
+    Ok(channels)
+}
+
+/// Sets the sound volume to the given percentage [0-100] by calling
+/// `amixer -q set <channel> <pct>%`.
+pub fn set_volume_channel(volume: i32, channel: &str) -> Ev3Result<()> {
+    Command::new("/usr/bin/amixer")
+        .args(["-q", "set", channel, &format!("{volume}%")])
+        .stdout(Stdio::null())
+        .spawn()
+        .map_err(Ev3Error::io(Path::new("/usr/bin/amixer")))?
+        .wait()?;
+
+    Ok(())
+}
+
+/// Sets the sound volume to the given percentage [0-100] by calling
+/// `amixer -q set <channel> <pct>%`.
+/// It tries to determine the default channel
+/// by running `amixer scontrols`. If that fails as well, it uses the
+/// `Playback` channel, as that is the only channel on the EV3.
+pub fn set_volume(volume: i32) -> Ev3Result<()> {
+    for channel in get_channels()? {
+        set_volume_channel(volume, &channel)?;
+    }
+    Ok(())
+}
+
+/// Gets the current sound volume by parsing the output of
+/// `amixer get <channel>`.
+pub fn get_volume_channel(channel: &str) -> Ev3Result<i32> {
+    let out = String::from_utf8(
+        Command::new("/usr/bin/amixer")
+            .args(["get", channel])
+            .output()
+            .map_err(Ev3Error::io(Path::new("/usr/bin/amixer")))?
+            .stdout,
+    )?;
+
+    let vol_start = out.find('[').unwrap_or(0) + 1;
+    let vol_end = out.find("%]").unwrap_or(1);
+    let vol = &out[vol_start..vol_end].parse::<i32>()?;
+
+    Ok(*vol)
+}
+
+/// Gets the current sound volume by parsing the output of
+/// `amixer get <channel>`.
+/// It tries to determine the default channel
+/// by running `amixer scontrols`. If that fails as well, it uses the
+/// `Playback` channel, as that is the only channel on the EV3.
+pub fn get_volume() -> Ev3Result<i32> {
+    get_volume_channel(&get_channels()?[0])
+}
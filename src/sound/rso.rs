@@ -0,0 +1,228 @@
+//! Decoder for the sound files of the LEGO Mindstorms software.
+//!
+//! * `.rso` files contain 8 bit unsigned mono PCM samples.
+//! * `.rmd` files contain a melody as a list of tones.
+//!
+//! Both formats start with an 8 byte big-endian header:
+//! format, number of data bytes, sample rate and play mode.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use super::{start_tone_sequence, SoundHandle};
+use crate::{Ev3Error, Ev3Result};
+
+/// Format id of uncompressed `.rso` files.
+const FORMAT_RSO: u16 = 0x0100;
+/// Format id of ADPCM compressed `.rso` files.
+const FORMAT_RSO_COMPRESSED: u16 = 0x0101;
+/// Format id of `.rmd` melody files.
+const FORMAT_RMD: u16 = 0x0500;
+/// Length of the common file header.
+const HEADER_LEN: usize = 8;
+
+/// Header of a `.rso` or `.rmd` file.
+struct Header {
+    format: u16,
+    data_len: usize,
+    sample_rate: u16,
+}
+
+impl Header {
+    fn decode(data: &[u8]) -> Ev3Result<Self> {
+        if data.len() < HEADER_LEN {
+            return Err(Ev3Error::InternalError {
+                msg: format!("Sound file is too short for a header: {} bytes", data.len()),
+                path: None,
+            });
+        }
+
+        let word = |i: usize| u16::from_be_bytes([data[i], data[i + 1]]);
+        let header = Header {
+            format: word(0),
+            data_len: word(2) as usize,
+            sample_rate: word(4),
+        };
+
+        if data.len() < HEADER_LEN + header.data_len {
+            return Err(Ev3Error::InternalError {
+                msg: format!(
+                    "Sound file is truncated: expected {} data bytes, found {}",
+                    header.data_len,
+                    data.len() - HEADER_LEN
+                ),
+                path: None,
+            });
+        }
+
+        Ok(header)
+    }
+
+    fn data<'a>(&self, data: &'a [u8]) -> &'a [u8] {
+        &data[HEADER_LEN..HEADER_LEN + self.data_len]
+    }
+}
+
+/// Reads a sound file and attaches the path to io errors.
+fn read_file(path: &Path) -> Ev3Result<Vec<u8>> {
+    fs::read(path).map_err(Ev3Error::io(path))
+}
+
+/// Sound samples of a `.rso` file.
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound::RsoSound;
+///
+/// # fn main() -> Ev3Result<()> {
+/// let sound = RsoSound::read("Hello.rso")?;
+/// sound.play()?.wait()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RsoSound {
+    /// Sample rate in Hz.
+    pub sample_rate: u16,
+    /// Unsigned 8 bit mono samples, `128` is silence.
+    pub samples: Vec<u8>,
+}
+
+impl RsoSound {
+    /// Decodes the content of a `.rso` file.
+    ///
+    /// Only uncompressed files are supported.
+    pub fn decode(data: &[u8]) -> Ev3Result<Self> {
+        let header = Header::decode(data)?;
+
+        match header.format {
+            FORMAT_RSO => Ok(RsoSound {
+                sample_rate: header.sample_rate,
+                samples: header.data(data).to_vec(),
+            }),
+            FORMAT_RSO_COMPRESSED => Err(Ev3Error::InternalError {
+                msg: "Compressed rso files are not supported".to_owned(),
+                path: None,
+            }),
+            format => Err(Ev3Error::InternalError {
+                msg: format!("Not a rso file, format is {format:#06x}"),
+                path: None,
+            }),
+        }
+    }
+
+    /// Reads and decodes a `.rso` file.
+    pub fn read<P: AsRef<Path>>(path: P) -> Ev3Result<Self> {
+        RsoSound::decode(&read_file(path.as_ref())?)
+    }
+
+    /// Returns the playback duration of the sound.
+    pub fn duration(&self) -> Duration {
+        if self.sample_rate == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(self.samples.len() as f64 / f64::from(self.sample_rate))
+    }
+
+    /// Plays the sound with `aplay` and returns immediately.
+    pub fn play(&self) -> Ev3Result<SoundHandle> {
+        let mut child = Command::new("/usr/bin/aplay")
+            .args(["-q", "-t", "raw", "-f", "U8", "-c", "1"])
+            .args(["-r", &self.sample_rate.to_string()])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .map_err(Ev3Error::io(Path::new("/usr/bin/aplay")))?;
+
+        let mut stdin = child.stdin.take().ok_or(Ev3Error::InternalError {
+            msg: "`aplay` pipe could not be created!".to_owned(),
+            path: None,
+        })?;
+        let samples = self.samples.clone();
+        // Feed the pipe in the background, `aplay` only consumes it at the sample rate.
+        // Cancelling the sound kills `aplay`, which ends this thread with a broken pipe.
+        thread::spawn(move || {
+            let _ = stdin.write_all(&samples);
+        });
+
+        Ok(SoundHandle::from(child))
+    }
+}
+
+/// Melody of a `.rmd` file.
+///
+/// # Example
+/// ```no_run
+/// # use ev3dev_lang_rust::Ev3Result;
+/// use ev3dev_lang_rust::sound::RmdMelody;
+///
+/// # fn main() -> Ev3Result<()> {
+/// let melody = RmdMelody::read("Startup.rmd")?;
+/// melody.play()?.wait()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RmdMelody {
+    /// Tones as tuples of frequency in Hz and duration in milliseconds.
+    /// A frequency of `0` is a rest.
+    pub tones: Vec<(u16, u16)>,
+}
+
+impl RmdMelody {
+    /// Decodes the content of a `.rmd` file.
+    pub fn decode(data: &[u8]) -> Ev3Result<Self> {
+        let header = Header::decode(data)?;
+
+        if header.format != FORMAT_RMD {
+            return Err(Ev3Error::InternalError {
+                msg: format!("Not a rmd file, format is {:#06x}", header.format),
+                path: None,
+            });
+        }
+
+        let tones = header
+            .data(data)
+            .chunks_exact(4)
+            .map(|tone| {
+                (
+                    u16::from_be_bytes([tone[0], tone[1]]),
+                    u16::from_be_bytes([tone[2], tone[3]]),
+                )
+            })
+            .collect();
+
+        Ok(RmdMelody { tones })
+    }
+
+    /// Reads and decodes a `.rmd` file.
+    pub fn read<P: AsRef<Path>>(path: P) -> Ev3Result<Self> {
+        RmdMelody::decode(&read_file(path.as_ref())?)
+    }
+
+    /// Returns the melody in the format of `sound::tone_sequence()`.
+    /// Rests are converted to delays after the previous tone.
+    pub fn to_tone_sequence(&self) -> Vec<(f32, i32, i32)> {
+        let mut sequence: Vec<(f32, i32, i32)> = Vec::new();
+
+        for &(frequency, duration) in &self.tones {
+            match sequence.last_mut() {
+                Some(last) if frequency == 0 => last.2 += i32::from(duration),
+                None if frequency == 0 => sequence.push((0.0, 0, i32::from(duration))),
+                _ => sequence.push((f32::from(frequency), i32::from(duration), 0)),
+            }
+        }
+
+        sequence
+    }
+
+    /// Plays the melody on the `Speaker` and returns immediately.
+    pub fn play(&self) -> Ev3Result<SoundHandle> {
+        start_tone_sequence(&self.to_tone_sequence())
+    }
+}
@@ -0,0 +1,193 @@
+//! Structured concurrency for robot tasks.
+//!
+//! Tasks like line followers, sensor watchers or led animations often run on their own thread.
+//! If such a thread outlives the mission step that started it, it keeps fighting the next step
+//! for the same motors. Tasks spawned with `scope()` are tied to the scope: when the scope closure
+//! returns, every task is cancelled and joined before `scope()` itself returns.
+//!
+//! Cancellation is cooperative. Each task gets a `CancelToken` that it should check regularly.
+//!
+//! # Example
+//! ```no_run
+//! use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+//! use ev3dev_lang_rust::task;
+//! use std::time::Duration;
+//!
+//! # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+//! let motor = LargeMotor::get(MotorPort::OutA)?;
+//!
+//! task::scope(|s| {
+//!     s.spawn(|token| {
+//!         motor.run_direct()?;
+//!         while !token.is_cancelled() {
+//!             motor.set_duty_cycle_sp(30)?;
+//!             token.sleep(Duration::from_millis(10));
+//!         }
+//!         motor.stop()
+//!     });
+//!
+//!     std::thread::sleep(Duration::from_secs(5));
+//! }); // The task is cancelled and joined here.
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, ScopedJoinHandle};
+use std::time::{Duration, Instant};
+
+/// Cooperative cancellation flag shared between a task and its owner.
+#[derive(Clone, Default)]
+pub struct CancelToken {
+    inner: Arc<(Mutex<bool>, Condvar)>,
+}
+
+impl CancelToken {
+    /// Create a new token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation. Wakes up all threads sleeping on this token.
+    pub fn cancel(&self) {
+        let (lock, condvar) = &*self.inner;
+        *lock.lock().unwrap() = true;
+        condvar.notify_all();
+    }
+
+    /// Checks if cancellation was requested.
+    pub fn is_cancelled(&self) -> bool {
+        *self.inner.0.lock().unwrap()
+    }
+
+    /// Sleep for `duration` or until the token is cancelled.
+    /// Returns `true` if the full duration elapsed and `false` if the token was cancelled.
+    pub fn sleep(&self, duration: Duration) -> bool {
+        let (lock, condvar) = &*self.inner;
+        let deadline = Instant::now() + duration;
+
+        let mut cancelled = lock.lock().unwrap();
+        while !*cancelled {
+            let now = Instant::now();
+            if now >= deadline {
+                return true;
+            }
+            cancelled = condvar.wait_timeout(cancelled, deadline - now).unwrap().0;
+        }
+        false
+    }
+
+    /// Block until the token is cancelled.
+    pub fn wait(&self) {
+        let (lock, condvar) = &*self.inner;
+        let mut cancelled = lock.lock().unwrap();
+        while !*cancelled {
+            cancelled = condvar.wait(cancelled).unwrap();
+        }
+    }
+}
+
+impl fmt::Debug for CancelToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancelToken")
+            .field("cancelled", &self.is_cancelled())
+            .finish()
+    }
+}
+
+/// Handle to a task spawned with `Scope::spawn()`.
+#[derive(Debug)]
+pub struct TaskHandle<'scope, T> {
+    handle: ScopedJoinHandle<'scope, T>,
+    token: CancelToken,
+}
+
+impl<T> TaskHandle<'_, T> {
+    /// Request cancellation of this task.
+    pub fn cancel(&self) {
+        self.token.cancel()
+    }
+
+    /// Checks if the task has finished.
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+
+    /// Wait for the task to finish and return its result.
+    /// Returns `Err` if the task panicked.
+    pub fn join(self) -> thread::Result<T> {
+        self.handle.join()
+    }
+
+    /// Cancel the task and wait for it to finish.
+    pub fn cancel_and_join(self) -> thread::Result<T> {
+        self.token.cancel();
+        self.handle.join()
+    }
+}
+
+/// A scope to spawn robot tasks in. Created by `scope()`.
+pub struct Scope<'scope, 'env: 'scope> {
+    inner: &'scope thread::Scope<'scope, 'env>,
+    tokens: Mutex<Vec<CancelToken>>,
+}
+
+impl<'scope, 'env> Scope<'scope, 'env> {
+    /// Spawn a new task in this scope.
+    ///
+    /// The task gets its own `CancelToken` that is cancelled by `TaskHandle::cancel()`,
+    /// by `Scope::cancel_all()` or when the scope ends.
+    pub fn spawn<F, T>(&self, f: F) -> TaskHandle<'scope, T>
+    where
+        F: FnOnce(&CancelToken) -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        let token = CancelToken::new();
+        self.tokens.lock().unwrap().push(token.clone());
+
+        let task_token = token.clone();
+        let handle = self.inner.spawn(move || f(&task_token));
+
+        TaskHandle { handle, token }
+    }
+
+    /// Request cancellation of all tasks spawned in this scope so far.
+    pub fn cancel_all(&self) {
+        for token in self.tokens.lock().unwrap().iter() {
+            token.cancel();
+        }
+    }
+}
+
+impl Drop for Scope<'_, '_> {
+    fn drop(&mut self) {
+        // Also runs when the scope closure panics, so the join in `thread::scope` cannot hang.
+        self.cancel_all();
+    }
+}
+
+impl fmt::Debug for Scope<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("tasks", &self.tokens.lock().unwrap().len())
+            .finish()
+    }
+}
+
+/// Create a scope for spawning robot tasks.
+///
+/// When `f` returns, all tasks spawned in the scope are cancelled and joined.
+/// If any task panicked, this function panics after all tasks have been joined.
+pub fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&Scope<'scope, 'env>) -> T,
+{
+    thread::scope(|s| {
+        let scope = Scope {
+            inner: s,
+            tokens: Mutex::new(Vec::new()),
+        };
+        f(&scope)
+    })
+}
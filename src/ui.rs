@@ -0,0 +1,264 @@
+//! Simple widgets for the EV3 brick display, controlled with the brick buttons.
+//!
+//! * `Menu`: Scrollable list to select e.g. a mission with up, down and enter.
+//! * `StatusPanel`: List of labeled values like the battery voltage or sensor readings.
+//!
+//! # Example
+//! ```no_run
+//! use ev3dev_lang_rust::ui::{Menu, StatusPanel};
+//! use ev3dev_lang_rust::{Button, Display, PowerSupply};
+//!
+//! # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+//! let mut display = Display::new()?;
+//! let button = Button::new()?;
+//!
+//! let mut menu = Menu::new(["Mission 1", "Mission 2", "Mission 3"]).with_title("Missions");
+//! if let Some(mission) = menu.run(&mut display, &button)? {
+//!     let mut status = StatusPanel::new().with_title(menu.get_item(mission).unwrap());
+//!     status.set_battery(&PowerSupply::new()?)?;
+//!     status.show(&mut display);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use crate::{BrickButton, Button, Display, Ev3Result, Font, PowerSupply};
+
+/// Horizontal padding between the screen border and the text.
+const PADDING: i32 = 2;
+
+/// Draws the title with a separator line and returns the y coordinate below it.
+fn draw_title(display: &mut Display, title: Option<&str>, font: &Font) -> i32 {
+    match title {
+        Some(title) => {
+            display.draw_text(PADDING, 1, title, font, true);
+            let y = font.line_height() as i32;
+            display.draw_line(0, y, display.width() as i32 - 1, y, true);
+            y + 2
+        }
+        None => 0,
+    }
+}
+
+/// Scrollable menu to select one of multiple items with the brick buttons.
+///
+/// Up and down move the selection, enter confirms it and backspace cancels the menu.
+#[derive(Debug, Clone)]
+pub struct Menu {
+    title: Option<String>,
+    items: Vec<String>,
+    selected: usize,
+    offset: usize,
+    font: Font,
+}
+
+impl Menu {
+    /// Creates a menu with the given items. The first item is selected.
+    pub fn new<I, S>(items: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Menu {
+            title: None,
+            items: items.into_iter().map(Into::into).collect(),
+            selected: 0,
+            offset: 0,
+            font: Font::LARGE,
+        }
+    }
+
+    /// Shows a title above the items.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the font of the title and items (default: `Font::LARGE`).
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Returns the index of the selected item.
+    pub fn get_selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Selects the item with the given index. Out of range indices select the last item.
+    pub fn set_selected(&mut self, index: usize) {
+        self.selected = index.min(self.items.len().saturating_sub(1));
+    }
+
+    /// Returns the item with the given index.
+    pub fn get_item(&self, index: usize) -> Option<&str> {
+        self.items.get(index).map(String::as_str)
+    }
+
+    /// Selects the next item, wraps around at the end of the list.
+    pub fn select_next(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+    }
+
+    /// Selects the previous item, wraps around at the start of the list.
+    pub fn select_previous(&mut self) {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+        }
+    }
+
+    /// Draws the menu. The selected item is highlighted and scrolled into view.
+    ///
+    /// The display is not cleared or updated.
+    pub fn draw(&mut self, display: &mut Display) {
+        let line_height = self.font.line_height() as i32;
+        let top = draw_title(display, self.title.as_deref(), &self.font);
+        let visible = ((display.height() as i32 - top) / line_height).max(1) as usize;
+
+        // Only scroll if the selection leaves the visible area.
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + visible {
+            self.offset = self.selected + 1 - visible;
+        }
+
+        let width = display.width();
+        for (row, (index, item)) in self
+            .items
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(visible)
+            .enumerate()
+        {
+            let y = top + row as i32 * line_height;
+            display.draw_text(PADDING, y + 1, item, &self.font, true);
+            if index == self.selected {
+                display.invert_rectangle(0, y, width, line_height as u32);
+            }
+        }
+
+        // Scroll bar at the right border if not all items fit on the screen.
+        if self.items.len() > visible {
+            let height = display.height() as i32 - top;
+            let bar_height = (height * visible as i32 / self.items.len() as i32).max(2);
+            let bar_top = top + height * self.offset as i32 / self.items.len() as i32;
+            display.fill_rectangle(width as i32 - 2, bar_top, 2, bar_height as u32, true);
+        }
+    }
+
+    /// Clears the display, draws the menu and updates the display.
+    pub fn show(&mut self, display: &mut Display) {
+        display.clear();
+        self.draw(display);
+        display.update();
+    }
+
+    /// Shows the menu and handles button presses until an item is chosen.
+    ///
+    /// Returns the index of the chosen item or `None` if the menu was cancelled with backspace.
+    pub fn run(&mut self, display: &mut Display, button: &Button) -> Ev3Result<Option<usize>> {
+        loop {
+            self.show(display);
+
+            match button.wait_for_press(None)? {
+                Some(BrickButton::Up) => self.select_previous(),
+                Some(BrickButton::Down) => self.select_next(),
+                Some(BrickButton::Enter) if !self.items.is_empty() => {
+                    return Ok(Some(self.selected))
+                }
+                Some(BrickButton::Backspace) => return Ok(None),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// List of labeled values, e.g. the battery voltage and sensor readings.
+///
+/// Labels are drawn left aligned and values right aligned.
+/// Updating the value of an existing label keeps its position.
+#[derive(Debug, Clone)]
+pub struct StatusPanel {
+    title: Option<String>,
+    entries: Vec<(String, String)>,
+    font: Font,
+}
+
+impl Default for StatusPanel {
+    fn default() -> Self {
+        StatusPanel::new()
+    }
+}
+
+impl StatusPanel {
+    /// Creates an empty status panel.
+    pub fn new() -> Self {
+        StatusPanel {
+            title: None,
+            entries: Vec::new(),
+            font: Font::SMALL,
+        }
+    }
+
+    /// Shows a title above the entries.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Sets the font of the title and entries (default: `Font::SMALL`).
+    pub fn with_font(mut self, font: Font) -> Self {
+        self.font = font;
+        self
+    }
+
+    /// Sets the value of the entry with the given label. New labels are appended.
+    pub fn set(&mut self, label: &str, value: impl fmt::Display) {
+        let value = value.to_string();
+        match self.entries.iter_mut().find(|(l, _)| l == label) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((label.to_owned(), value)),
+        }
+    }
+
+    /// Removes the entry with the given label.
+    pub fn remove(&mut self, label: &str) {
+        self.entries.retain(|(l, _)| l != label);
+    }
+
+    /// Sets the entry `Battery` to the current voltage of the power supply.
+    pub fn set_battery(&mut self, power_supply: &PowerSupply) -> Ev3Result<()> {
+        let volts = power_supply.voltage_volts()?;
+        self.set("Battery", format!("{volts:.2} V"));
+        Ok(())
+    }
+
+    /// Draws the status panel. Entries that do not fit on the display are omitted.
+    ///
+    /// The display is not cleared or updated.
+    pub fn draw(&self, display: &mut Display) {
+        let line_height = self.font.line_height() as i32;
+        let top = draw_title(display, self.title.as_deref(), &self.font);
+
+        for (row, (label, value)) in self.entries.iter().enumerate() {
+            let y = top + row as i32 * line_height + 1;
+            display.draw_text(PADDING, y, label, &self.font, true);
+
+            let (value_width, _) = self.font.text_size(value);
+            let x = display.width() as i32 - PADDING - value_width as i32;
+            display.draw_text(x, y, value, &self.font, true);
+        }
+    }
+
+    /// Clears the display, draws the status panel and updates the display.
+    pub fn show(&self, display: &mut Display) {
+        display.clear();
+        self.draw(display);
+        display.update();
+    }
+}
@@ -0,0 +1,205 @@
+//! Unit-typed accessors based on the [`uom`](https://docs.rs/uom) crate.
+//!
+//! The `_typed()` variants of the getters and setters convert tacho counts, microvolts and
+//! sensor specific scales to quantities, so a distance in millimeters can not be mixed up
+//! with tacho counts or volts with microvolts.
+//!
+//! # Example
+//! ```no_run
+//! use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+//! use ev3dev_lang_rust::units::{angle::degree, angular_velocity::revolution_per_minute};
+//! use ev3dev_lang_rust::units::{Angle, AngularVelocity};
+//! use ev3dev_lang_rust::PowerSupply;
+//!
+//! # fn main() -> ev3dev_lang_rust::Ev3Result<()> {
+//! let motor = LargeMotor::get(MotorPort::OutA)?;
+//! motor.set_speed_sp_typed(AngularVelocity::new::<revolution_per_minute>(60.0))?;
+//! motor.run_to_rel_pos(None)?;
+//! motor.set_position_sp_typed(Angle::new::<degree>(90.0))?;
+//!
+//! let battery = PowerSupply::new()?;
+//! println!("{:?}", battery.get_voltage_now_typed()?);
+//! # Ok(())
+//! # }
+//! ```
+
+use uom::si::angle::{degree, revolution};
+use uom::si::angular_velocity::{degree_per_second, revolution_per_second};
+use uom::si::electric_current::microampere;
+use uom::si::electric_potential::microvolt;
+use uom::si::length::{centimeter, inch, meter};
+use uom::si::velocity::meter_per_second;
+
+pub use uom::si::f32::{
+    Angle, AngularVelocity, ElectricCurrent, ElectricPotential, Length, Velocity,
+};
+pub use uom::si::{
+    angle, angular_velocity, electric_current, electric_potential, length, velocity,
+};
+
+use crate::motors::{LargeMotor, LinearActuator, MediumMotor, TachoMotor};
+use crate::sensors::{GyroSensor, Sensor, UltrasonicSensor};
+use crate::{Ev3Result, PowerSupply};
+
+/// Generates the unit-typed functions of a rotary tacho motor, based on `count_per_rot`.
+macro_rules! rotary_units {
+    ($motor:ty) => {
+        impl $motor {
+            fn counts_to_revolutions(&self, counts: i32) -> Ev3Result<f32> {
+                Ok(counts as f32 / self.get_count_per_rot()? as f32)
+            }
+
+            fn revolutions_to_counts(&self, revolutions: f32) -> Ev3Result<i32> {
+                Ok((revolutions * self.get_count_per_rot()? as f32).round() as i32)
+            }
+
+            /// Returns the current position of the motor as angle. See `get_position()`.
+            pub fn get_position_typed(&self) -> Ev3Result<Angle> {
+                Ok(Angle::new::<revolution>(
+                    self.counts_to_revolutions(self.get_position()?)?,
+                ))
+            }
+
+            /// Returns the target position of the motor as angle. See `get_position_sp()`.
+            pub fn get_position_sp_typed(&self) -> Ev3Result<Angle> {
+                Ok(Angle::new::<revolution>(
+                    self.counts_to_revolutions(self.get_position_sp()?)?,
+                ))
+            }
+
+            /// Sets the target position of the motor as angle. See `set_position_sp()`.
+            pub fn set_position_sp_typed(&self, position_sp: Angle) -> Ev3Result<()> {
+                self.set_position_sp(self.revolutions_to_counts(position_sp.get::<revolution>())?)
+            }
+
+            /// Returns the current speed of the motor. See `get_speed()`.
+            pub fn get_speed_typed(&self) -> Ev3Result<AngularVelocity> {
+                Ok(AngularVelocity::new::<revolution_per_second>(
+                    self.counts_to_revolutions(self.get_speed()?)?,
+                ))
+            }
+
+            /// Returns the target speed of the motor. See `get_speed_sp()`.
+            pub fn get_speed_sp_typed(&self) -> Ev3Result<AngularVelocity> {
+                Ok(AngularVelocity::new::<revolution_per_second>(
+                    self.counts_to_revolutions(self.get_speed_sp()?)?,
+                ))
+            }
+
+            /// Sets the target speed of the motor. See `set_speed_sp()`.
+            pub fn set_speed_sp_typed(&self, speed_sp: AngularVelocity) -> Ev3Result<()> {
+                self.set_speed_sp(
+                    self.revolutions_to_counts(speed_sp.get::<revolution_per_second>())?,
+                )
+            }
+
+            /// Returns the maximum speed of the motor. See `get_max_speed()`.
+            pub fn get_max_speed_typed(&self) -> Ev3Result<AngularVelocity> {
+                Ok(AngularVelocity::new::<revolution_per_second>(
+                    self.counts_to_revolutions(self.get_max_speed()?)?,
+                ))
+            }
+        }
+    };
+}
+
+rotary_units!(LargeMotor);
+rotary_units!(MediumMotor);
+rotary_units!(TachoMotor);
+
+impl LinearActuator {
+    fn counts_to_meters(&self, counts: i32) -> Ev3Result<f32> {
+        Ok(counts as f32 / self.get_count_per_m()? as f32)
+    }
+
+    fn meters_to_counts(&self, meters: f32) -> Ev3Result<i32> {
+        Ok((meters * self.get_count_per_m()? as f32).round() as i32)
+    }
+
+    /// Returns the current extension, relative to the zero point set by `home()`.
+    pub fn get_position_typed(&self) -> Ev3Result<Length> {
+        Ok(Length::new::<meter>(
+            self.counts_to_meters(self.get_position()?)?,
+        ))
+    }
+
+    /// Sets the target position of the actuator. See `set_position_sp()`.
+    pub fn set_position_sp_typed(&self, position_sp: Length) -> Ev3Result<()> {
+        self.set_position_sp(self.meters_to_counts(position_sp.get::<meter>())?)
+    }
+
+    /// Returns the maximal travel distance.
+    pub fn get_full_travel_typed(&self) -> Ev3Result<Length> {
+        Ok(Length::new::<meter>(
+            self.counts_to_meters(self.get_full_travel_count()?)?,
+        ))
+    }
+
+    /// Returns the current speed of the actuator. See `get_speed()`.
+    pub fn get_speed_typed(&self) -> Ev3Result<Velocity> {
+        Ok(Velocity::new::<meter_per_second>(
+            self.counts_to_meters(self.get_speed()?)?,
+        ))
+    }
+
+    /// Sets the target speed of the actuator. See `set_speed_sp()`.
+    pub fn set_speed_sp_typed(&self, speed_sp: Velocity) -> Ev3Result<()> {
+        self.set_speed_sp(self.meters_to_counts(speed_sp.get::<meter_per_second>())?)
+    }
+}
+
+impl GyroSensor {
+    /// Gets the angle. See `get_angle()`.
+    pub fn get_angle_typed(&self) -> Ev3Result<Angle> {
+        Ok(Angle::new::<degree>(self.get_angle()? as f32))
+    }
+
+    /// Gets the rotational speed. See `get_rotational_speed()`.
+    pub fn get_rotational_speed_typed(&self) -> Ev3Result<AngularVelocity> {
+        Ok(AngularVelocity::new::<degree_per_second>(
+            self.get_rotational_speed()? as f32,
+        ))
+    }
+}
+
+impl UltrasonicSensor {
+    /// Measurement of the distance detected by the sensor.
+    /// Uses the inch modes if one of them is set and the centimeter modes otherwise.
+    pub fn get_distance_typed(&self) -> Ev3Result<Length> {
+        if self.get_mode()?.ends_with("-IN") {
+            Ok(Length::new::<inch>(self.get_distance_inches()?))
+        } else {
+            Ok(Length::new::<centimeter>(self.get_distance_centimeters()?))
+        }
+    }
+}
+
+impl PowerSupply {
+    /// Returns the battery voltage.
+    pub fn get_voltage_now_typed(&self) -> Ev3Result<ElectricPotential> {
+        Ok(ElectricPotential::new::<microvolt>(
+            self.get_voltage_now()? as f32
+        ))
+    }
+
+    /// Returns the nominal “full” battery voltage.
+    pub fn get_voltage_max_design_typed(&self) -> Ev3Result<ElectricPotential> {
+        Ok(ElectricPotential::new::<microvolt>(
+            self.get_voltage_max_design()? as f32,
+        ))
+    }
+
+    /// Returns the nominal “empty” battery voltage.
+    pub fn get_voltage_min_design_typed(&self) -> Ev3Result<ElectricPotential> {
+        Ok(ElectricPotential::new::<microvolt>(
+            self.get_voltage_min_design()? as f32,
+        ))
+    }
+
+    /// Returns the battery current.
+    pub fn get_current_now_typed(&self) -> Ev3Result<ElectricCurrent> {
+        Ok(ElectricCurrent::new::<microampere>(
+            self.get_current_now()? as f32
+        ))
+    }
+}
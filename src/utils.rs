@@ -1,6 +1,8 @@
 //! Utility things.
 
-use std::{error::Error, fmt};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{error::Error, fmt, io};
 
 /// Helper `Result` type for easy access.
 pub type Ev3Result<T> = Result<T, Ev3Error>;
@@ -12,6 +14,8 @@ pub enum Ev3Error {
     InternalError {
         /// Original error message.
         msg: String,
+        /// Path of the file or directory that caused the error (None if no file is involved)
+        path: Option<PathBuf>,
     },
     /// No matching device found.
     NotConnected {
@@ -19,6 +23,8 @@ pub enum Ev3Error {
         device: String,
         /// Device was expected to be on this port (None if no port was specified)
         port: Option<String>,
+        /// Path of the directory or attribute that was searched (None if unknown)
+        path: Option<PathBuf>,
     },
     /// More than one matching device found.
     MultipleMatches {
@@ -26,29 +32,253 @@ pub enum Ev3Error {
         device: String,
         /// Devices of the requested type were found on this ports.
         ports: Vec<String>,
+        /// Path of the class directory that was searched (None if unknown)
+        path: Option<PathBuf>,
     },
+    /// The sensor is not in a mode that supports the requested reading.
+    WrongMode {
+        /// Modes that support the requested reading.
+        expected: Vec<String>,
+        /// Current mode of the sensor.
+        actual: String,
+        /// Path of the `mode` attribute (None if unknown)
+        path: Option<PathBuf>,
+    },
+    /// The device is already claimed by another owner.
+    AlreadyClaimed {
+        /// Corresponding device
+        device: String,
+        /// Owner of the conflicting claim
+        owner: String,
+        /// Path of the device directory (None if unknown)
+        path: Option<PathBuf>,
+    },
+    /// Reading or writing a file failed.
+    Io {
+        /// Path of the file (None if the path is unknown)
+        path: Option<PathBuf>,
+        /// Underlying io error
+        source: io::Error,
+    },
+    /// The value of an attribute could not be parsed.
+    Parse {
+        /// Path of the attribute
+        path: PathBuf,
+        /// Value that could not be parsed
+        value: String,
+        /// Original error message
+        msg: String,
+    },
+    /// A condition was not met within the timeout.
+    Timeout {
+        /// The timeout that passed
+        timeout: Duration,
+        /// Path of the device directory that was waited on (None if no device is involved)
+        path: Option<PathBuf>,
+    },
+    /// The `bin_data_format` of the sensor cannot be decoded by the requested reading.
+    /// The values can still be read from the `value<N>` attributes.
+    UnsupportedFormat {
+        /// Format reported by the sensor
+        format: String,
+        /// Formats that are supported by the reading
+        expected: Vec<String>,
+        /// Path of the `bin_data_format` attribute (None if unknown)
+        path: Option<PathBuf>,
+    },
+    /// The device does not support the requested operation, e.g. in its current mode.
+    UnsupportedOperation {
+        /// Corresponding device
+        device: String,
+        /// Description of the operation
+        operation: String,
+        /// Path of the device directory (None if unknown)
+        path: Option<PathBuf>,
+    },
+    /// A value is outside of its valid range, e.g. a target position beyond the travel limits.
+    OutOfRange {
+        /// Name of the value
+        name: String,
+        /// The rejected value
+        value: i32,
+        /// Smallest valid value
+        min: i32,
+        /// Largest valid value
+        max: i32,
+        /// Path of the device directory (None if the value does not belong to a device)
+        path: Option<PathBuf>,
+    },
+    /// A port name could not be parsed.
+    InvalidPort {
+        /// The rejected port name
+        value: String,
+        /// Description of the valid port names
+        expected: String,
+        /// Path of the attribute the name was read from (None if it was not read from a file)
+        path: Option<PathBuf>,
+    },
+}
+
+impl Ev3Error {
+    /// Returns a function that wraps an `io::Error` that occurred while accessing `path`.
+    pub(crate) fn io(path: &Path) -> impl FnOnce(io::Error) -> Ev3Error + '_ {
+        move |source| Ev3Error::Io {
+            path: Some(path.to_path_buf()),
+            source,
+        }
+    }
 }
 
 impl fmt::Display for Ev3Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Ev3Error::InternalError { msg } => write!(f, "InternalError: {msg}!"),
-            Ev3Error::NotConnected { device, port } => {
-                write!(f, "'{device}' not connected at port {port:?}!")
+            Ev3Error::InternalError { msg, path } => {
+                write!(f, "InternalError: {msg}{}!", PathSuffix(path))
             }
-            Ev3Error::MultipleMatches { device, ports } => {
-                write!(f, "Multiple '{device}' connected at ports {ports:?}!")
+            Ev3Error::NotConnected { device, port, path } => write!(
+                f,
+                "'{device}' not connected at port {port:?}{}!",
+                PathSuffix(path)
+            ),
+            Ev3Error::MultipleMatches {
+                device,
+                ports,
+                path,
+            } => write!(
+                f,
+                "Multiple '{device}' connected at ports {ports:?}{}!",
+                PathSuffix(path)
+            ),
+            Ev3Error::WrongMode {
+                expected,
+                actual,
+                path,
+            } => write!(
+                f,
+                "Sensor is in mode '{actual}', expected one of {expected:?}{}!",
+                PathSuffix(path)
+            ),
+            Ev3Error::AlreadyClaimed {
+                device,
+                owner,
+                path,
+            } => write!(
+                f,
+                "'{device}' is already claimed by '{owner}'{}!",
+                PathSuffix(path)
+            ),
+            Ev3Error::Io {
+                path: Some(path),
+                source,
+            } => write!(f, "Could not access '{}': {source}!", path.display()),
+            Ev3Error::Io { path: None, source } => write!(f, "IoError: {source}!"),
+            Ev3Error::Parse { path, value, msg } => write!(
+                f,
+                "Could not parse '{value}' read from '{}': {msg}!",
+                path.display()
+            ),
+            Ev3Error::Timeout { timeout, path } => {
+                write!(f, "Timeout after {timeout:?}{}!", PathSuffix(path))
             }
+            Ev3Error::UnsupportedFormat {
+                format,
+                expected,
+                path,
+            } => write!(
+                f,
+                "Unsupported bin_data_format '{format}', expected one of {expected:?}{}!",
+                PathSuffix(path)
+            ),
+            Ev3Error::UnsupportedOperation {
+                device,
+                operation,
+                path,
+            } => write!(
+                f,
+                "'{device}' does not support {operation}{}!",
+                PathSuffix(path)
+            ),
+            Ev3Error::OutOfRange {
+                name,
+                value,
+                min,
+                max,
+                path,
+            } => write!(
+                f,
+                "{name} {value} is outside of the range [{min}, {max}]{}!",
+                PathSuffix(path)
+            ),
+            Ev3Error::InvalidPort {
+                value,
+                expected,
+                path,
+            } => write!(
+                f,
+                "Invalid port '{value}', expected {expected}{}!",
+                PathSuffix(path)
+            ),
+        }
+    }
+}
+
+/// Formats ` ('{path}')` if the path is known.
+struct PathSuffix<'a>(&'a Option<PathBuf>);
+
+impl fmt::Display for PathSuffix<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(path) => write!(f, " ('{}')", path.display()),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Error for Ev3Error {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Ev3Error::Io { source, .. } => Some(source),
+            _ => None,
         }
     }
 }
 
-impl Error for Ev3Error {}
+/// Serializes the error as `{ "kind": "NotConnected", "message": "..." }`.
+///
+/// The underlying `io::Error` of `Ev3Error::Io` cannot be serialized,
+/// so the error is reduced to its variant name and display message.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Ev3Error {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let kind = match self {
+            Ev3Error::InternalError { .. } => "InternalError",
+            Ev3Error::NotConnected { .. } => "NotConnected",
+            Ev3Error::MultipleMatches { .. } => "MultipleMatches",
+            Ev3Error::WrongMode { .. } => "WrongMode",
+            Ev3Error::AlreadyClaimed { .. } => "AlreadyClaimed",
+            Ev3Error::Io { .. } => "Io",
+            Ev3Error::Parse { .. } => "Parse",
+            Ev3Error::Timeout { .. } => "Timeout",
+            Ev3Error::UnsupportedFormat { .. } => "UnsupportedFormat",
+            Ev3Error::UnsupportedOperation { .. } => "UnsupportedOperation",
+            Ev3Error::OutOfRange { .. } => "OutOfRange",
+            Ev3Error::InvalidPort { .. } => "InvalidPort",
+        };
+
+        let mut state = serializer.serialize_struct("Ev3Error", 2)?;
+        state.serialize_field("kind", kind)?;
+        state.serialize_field("message", &self.to_string())?;
+        state.end()
+    }
+}
 
 impl From<std::io::Error> for Ev3Error {
     fn from(err: std::io::Error) -> Self {
-        Ev3Error::InternalError {
-            msg: format!("{err}"),
+        Ev3Error::Io {
+            path: None,
+            source: err,
         }
     }
 }
@@ -57,6 +287,7 @@ impl From<std::string::FromUtf8Error> for Ev3Error {
     fn from(err: std::string::FromUtf8Error) -> Self {
         Ev3Error::InternalError {
             msg: format!("{err}"),
+            path: None,
         }
     }
 }
@@ -65,6 +296,7 @@ impl From<std::num::ParseIntError> for Ev3Error {
     fn from(err: std::num::ParseIntError) -> Self {
         Ev3Error::InternalError {
             msg: format!("{err}"),
+            path: None,
         }
     }
 }
@@ -74,6 +306,7 @@ impl From<framebuffer::FramebufferError> for Ev3Error {
     fn from(err: framebuffer::FramebufferError) -> Self {
         Ev3Error::InternalError {
             msg: format!("{:?}", err),
+            path: None,
         }
     }
 }
@@ -82,6 +315,37 @@ impl From<framebuffer::FramebufferError> for Ev3Error {
 pub trait Port {
     /// Returns the name of the port.
     fn address(&self) -> String;
+
+    /// Checks if a device with the given `address` is connected to this port.
+    ///
+    /// The port address has to match complete `:`-separated segments of the device address,
+    /// so `in1` matches `ev3-ports:in1` and `ev3-ports:in1:i2c1` but not `ev3-ports:in10`.
+    /// Devices behind a sensor multiplexer (`ev3-ports:in1:i2c80:mux1`) only match the mux port itself.
+    fn matches_address(&self, address: &str) -> bool {
+        let port_address = self.address();
+        let port_segments = port_address.split(':').collect::<Vec<_>>();
+        let segments = address.split(':').collect::<Vec<_>>();
+
+        (0..segments.len())
+            .filter(|&i| segments[i..].starts_with(&port_segments))
+            .any(|i| {
+                !segments[i + port_segments.len()..]
+                    .iter()
+                    .any(|segment| segment.starts_with("mux"))
+            })
+    }
+
+    /// Parses a port from a string, e.g. a config file entry, command line argument or environment variable.
+    ///
+    /// Motor ports accept `outA` or `A`, sensor ports `in1` or `1` (case-insensitive)
+    /// and both accept the address of the platform, e.g. `spi0.1:MA`.
+    /// Multiplexer ports are written as `in1:mux2`.
+    fn parse(name: &str) -> Ev3Result<Self>
+    where
+        Self: Sized + std::str::FromStr<Err = Ev3Error>,
+    {
+        name.parse()
+    }
 }
 
 /// Helper trait to convert an option to an error.
@@ -95,6 +359,7 @@ impl<T> OrErr<T> for Option<T> {
     fn or_err(self) -> Ev3Result<T> {
         self.ok_or(Ev3Error::InternalError {
             msg: "Cannot unwrap option".to_owned(),
+            path: None,
         })
     }
 }
@@ -1,17 +1,24 @@
 //! Utility functions for cpu efficient `wait` commands.
-//! Uses the `libc::epoll_wait` that only works on linux systems.
+//! Uses `libc::poll` to wait for `sysfs_notify` events that only works on linux systems.
 
 #[cfg(target_os = "linux")]
 use libc;
 use std::os::unix::io::RawFd;
+use std::thread;
 use std::time::{Duration, Instant};
 
+/// Interval to check the condition if there is no file to wait for changes,
+/// e.g. for attributes of the mock, remote or replay backend.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
 /// Wait for until a condition `cond` is `true` or the `timeout` is reached.
 /// If the `timeout` is `None` it will wait an infinite time.
 /// The condition is checked when the `file` has changed.
+/// If `fd` is negative, e.g. for attributes that are not backed by a file,
+/// the condition is checked every 20 milliseconds instead.
 ///
 /// # Arguments
-/// * `file` - Listen to changes in this file
+/// * `fd` - Listen to changes in this file
 /// * `cond` - Condition that should become true
 /// * `timeout` - Maximal timeout to wait for the condition or file changes
 ///
@@ -46,11 +53,11 @@ where
     let mut t = timeout;
 
     loop {
-        let wait_timeout = match t {
-            Some(duration) => duration.as_millis() as i32,
-            None => -1,
-        };
-        wait_file_changes(fd, wait_timeout);
+        if fd < 0 {
+            thread::sleep(t.map_or(POLL_INTERVAL, |t| t.min(POLL_INTERVAL)));
+        } else {
+            wait_file_changes(fd, t);
+        }
 
         if let Some(duration) = timeout {
             let elapsed = start.elapsed();
@@ -66,26 +73,32 @@ where
     }
 }
 
-/// Wrapper for `libc::epoll_wait`
+/// Wait until the sysfs attribute `fd` signals a change or the `timeout` is reached.
+/// Returns `true` if the attribute has changed.
+///
+/// sysfs signals changes with `POLLPRI | POLLERR`. The attribute has to be read
+/// after each change, otherwise the next call returns immediately.
 #[cfg(target_os = "linux")]
-fn wait_file_changes(fd: RawFd, timeout: i32) -> bool {
-    let mut buf: [libc::epoll_event; 10] = [libc::epoll_event { events: 0, u64: 0 }; 10];
+pub(crate) fn wait_file_changes(fd: RawFd, timeout: Option<Duration>) -> bool {
+    let timeout = match timeout {
+        Some(duration) => duration.as_millis().min(i32::MAX as u128) as i32,
+        None => -1,
+    };
 
-    let result = unsafe {
-        libc::epoll_wait(
-            fd,
-            buf.as_mut_ptr() as *mut libc::epoll_event,
-            buf.len() as i32,
-            timeout,
-        ) as i32
+    let mut poll_fd = libc::pollfd {
+        fd,
+        events: libc::POLLPRI | libc::POLLERR,
+        revents: 0,
     };
 
-    result > 0
+    let result = unsafe { libc::poll(&mut poll_fd, 1, timeout) };
+
+    result > 0 && poll_fd.revents & (libc::POLLPRI | libc::POLLERR) != 0
 }
 
 /// Stub method for non linux os's
 #[cfg(not(target_os = "linux"))]
-fn wait_file_changes(_fd: RawFd, _timeout: i32) -> bool {
+pub(crate) fn wait_file_changes(_fd: RawFd, _timeout: Option<Duration>) -> bool {
     std::thread::sleep(Duration::from_millis(100));
     false
 }
@@ -0,0 +1,74 @@
+//! Ports of the LEGO WeDo USB hub.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Ev3Error, Port};
+
+/// Port `A` or `B` of a LEGO WeDo USB hub (9581) that is connected to the brick.
+///
+/// ev3dev names the hub ports `<usb device>:wedo1` and `<usb device>:wedo2`. The port matches the
+/// first hub with a device on it, use `get_by_port()` with the complete address to select one of several hubs.
+///
+/// LEGO Powered Up devices connect over Bluetooth LE and are not exposed by the ev3dev device classes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WedoPort {
+    /// Hub port `A` (`wedo1`)
+    A,
+    /// Hub port `B` (`wedo2`)
+    B,
+}
+
+impl WedoPort {
+    /// All ports of the hub.
+    pub const ALL: [WedoPort; 2] = [WedoPort::A, WedoPort::B];
+
+    /// Returns the label of the port on the hub, `A` or `B`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            WedoPort::A => "A",
+            WedoPort::B => "B",
+        }
+    }
+
+    /// Try to format a device name path to a port name.
+    pub fn format_name(name: &str) -> String {
+        name.to_owned()
+    }
+}
+
+impl Port for WedoPort {
+    fn address(&self) -> String {
+        match self {
+            WedoPort::A => "wedo1".to_owned(),
+            WedoPort::B => "wedo2".to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for WedoPort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for WedoPort {
+    type Err = Ev3Error;
+
+    /// Parses `A`, `B` or the address of the port, e.g. `wedo1` (case-insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        WedoPort::ALL
+            .into_iter()
+            .find(|port| {
+                port.name().eq_ignore_ascii_case(s) || port.address().eq_ignore_ascii_case(s)
+            })
+            .ok_or_else(|| Ev3Error::InvalidPort {
+                value: s.to_owned(),
+                expected: "A or B".to_owned(),
+                path: None,
+            })
+    }
+}
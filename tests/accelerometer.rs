@@ -0,0 +1,14 @@
+use ev3dev_lang_rust::sensors::AccelerometerSensor;
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_decode_axis() {
+    assert_eq!(AccelerometerSensor::decode_axis(0, 0), 0);
+    assert_eq!(AccelerometerSensor::decode_axis(50, 0), 200);
+    assert_eq!(AccelerometerSensor::decode_axis(50, 3), 203);
+    assert_eq!(AccelerometerSensor::decode_axis(127, 3), 511);
+    assert_eq!(AccelerometerSensor::decode_axis(255, 3), -1);
+    assert_eq!(AccelerometerSensor::decode_axis(128, 0), -512);
+    assert_eq!(AccelerometerSensor::decode_axis(206, 0), -200);
+}
@@ -0,0 +1,146 @@
+use std::fs::{self, Permissions};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ev3dev_lang_rust::{wait, Attribute, AttributeSource, Ev3Result};
+
+extern crate ev3dev_lang_rust;
+
+fn attribute_file(name: &str, content: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("ev3dev-lang-rust-{}-{}", std::process::id(), name));
+    fs::write(&path, content).unwrap();
+    fs::set_permissions(&path, Permissions::from_mode(0o660)).unwrap();
+    path
+}
+
+#[test]
+fn test_read_values() {
+    let path = attribute_file("read", "1234\n");
+    let attribute = Attribute::from_path(&path).unwrap();
+
+    assert_eq!(attribute.get::<i32>().unwrap(), 1234);
+    assert_eq!(attribute.get::<String>().unwrap(), "1234");
+    // Repeated reads reuse the buffer.
+    assert_eq!(attribute.get::<i32>().unwrap(), 1234);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_read_long_value() {
+    let modes = (0..40).map(|i| format!("MODE-{i}")).collect::<Vec<_>>();
+    let path = attribute_file("long", &format!("{}\n", modes.join(" ")));
+    let attribute = Attribute::from_path(&path).unwrap();
+
+    assert_eq!(attribute.get_vec().unwrap(), modes);
+    assert_eq!(
+        attribute.get_raw_data().unwrap().len(),
+        modes.join(" ").len() + 1
+    );
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_write_value() {
+    let path = attribute_file("write", "");
+    let attribute = Attribute::from_path(&path).unwrap();
+
+    attribute.set(42).unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "42");
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_read_raw_data_into() {
+    let path = attribute_file("raw", "\x01\x02\x03\x04");
+    let attribute = Attribute::from_path(&path).unwrap();
+
+    let mut buf = [0u8; 8];
+    assert_eq!(attribute.get_raw_data_into(&mut buf).unwrap(), 4);
+    assert_eq!(buf[..4], [1, 2, 3, 4]);
+
+    let mut small = [0u8; 2];
+    assert_eq!(attribute.get_raw_data_into(&mut small).unwrap(), 2);
+    assert_eq!(small, [1, 2]);
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_watch_without_change() {
+    let path = attribute_file("watch", "running\n");
+    let attribute = Attribute::from_path(&path).unwrap();
+
+    let mut watch = attribute.watch(Some(Duration::from_millis(20)));
+    assert!(watch.next().is_none());
+    assert!(watch.next().is_none());
+
+    fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_wait_timeout() {
+    let path = attribute_file("wait", "running\n");
+    let attribute = Attribute::from_path(&path).unwrap();
+
+    let start = Instant::now();
+    let result = wait::wait(
+        attribute.get_raw_fd(),
+        || false,
+        Some(Duration::from_millis(50)),
+    );
+
+    assert!(!result);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    fs::remove_file(path).unwrap();
+}
+
+#[derive(Debug, Default)]
+struct MemorySource(Mutex<Vec<u8>>);
+
+impl AttributeSource for MemorySource {
+    fn read(&self) -> Ev3Result<Vec<u8>> {
+        Ok(self.0.lock().unwrap().clone())
+    }
+
+    fn write(&self, data: &[u8]) -> Ev3Result<()> {
+        *self.0.lock().unwrap() = data.to_vec();
+        Ok(())
+    }
+}
+
+#[test]
+fn test_source_attribute() {
+    let source = Arc::new(MemorySource::default());
+    let attribute = Attribute::from_source(Path::new("memory/position"), source.clone());
+
+    attribute.set(42).unwrap();
+    assert_eq!(attribute.get::<i32>().unwrap(), 42);
+    assert_eq!(*source.0.lock().unwrap(), b"42");
+    assert_eq!(attribute.get_file_path(), Path::new("memory/position"));
+    assert!(attribute.write_at(1, b"1").is_err());
+
+    let mut buf = [0; 8];
+    assert_eq!(attribute.read_at(1, &mut buf).unwrap(), 1);
+    assert_eq!(&buf[..1], b"2");
+
+    // Attributes without file are polled for changes.
+    let writer = attribute.clone();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        writer.set(43).unwrap();
+    });
+    let mut watch = attribute.watch(Some(Duration::from_secs(2)));
+    assert_eq!(watch.next().unwrap().unwrap(), "43");
+    assert!(attribute
+        .watch(Some(Duration::from_millis(50)))
+        .next()
+        .is_none());
+}
@@ -0,0 +1,42 @@
+use ev3dev_lang_rust::brick::{LoadAverage, MemoryInfo, OsRelease};
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_os_release() {
+    let release = OsRelease::parse(
+        "PRETTY_NAME=\"ev3dev-stretch\"\nNAME=\"Debian GNU/Linux\"\nVERSION_ID=\"9\"\nID=debian\n",
+    );
+
+    assert_eq!(release.pretty_name, "ev3dev-stretch");
+    assert_eq!(release.name, "Debian GNU/Linux");
+    assert_eq!(release.version_id, "9");
+    assert_eq!(release.id, "debian");
+}
+
+#[test]
+fn test_load_average() {
+    let load = LoadAverage::parse("0.42 0.30 0.25 1/80 1234\n").unwrap();
+    assert_eq!(load.one, 0.42);
+    assert_eq!(load.five, 0.30);
+    assert_eq!(load.fifteen, 0.25);
+
+    assert!(LoadAverage::parse("0.42").is_err());
+    assert!(LoadAverage::parse("a b c").is_err());
+}
+
+#[test]
+fn test_memory_info() {
+    let memory = MemoryInfo::parse(
+        "MemTotal:          57412 kB\nMemFree:            5104 kB\nMemAvailable:      30200 kB\n",
+    )
+    .unwrap();
+    assert_eq!(memory.total_kb, 57412);
+    assert_eq!(memory.free_kb, 5104);
+    assert_eq!(memory.available_kb, 30200);
+
+    let memory = MemoryInfo::parse("MemTotal: 57412 kB\nMemFree: 5104 kB\n").unwrap();
+    assert_eq!(memory.available_kb, 5104);
+
+    assert!(MemoryInfo::parse("MemTotal: 57412 kB\n").is_err());
+}
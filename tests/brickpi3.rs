@@ -17,3 +17,20 @@ fn test_output_port_mapping() {
     assert_eq!(MotorPort::OutC.address(), "spi0.1:MC".to_string());
     assert_eq!(MotorPort::OutD.address(), "spi0.1:MD".to_string());
 }
+
+#[test]
+fn test_port_address_matching() {
+    assert!(SensorPort::In3.matches_address("spi0.1:S3"));
+    assert!(!SensorPort::In3.matches_address("spi0.1:S4"));
+    assert!(MotorPort::OutA.matches_address("spi0.1:MA"));
+}
+
+#[test]
+fn test_port_parsing() {
+    assert_eq!(
+        MotorPort::parse("spi0.1:MB").unwrap().address(),
+        "spi0.1:MB"
+    );
+    assert_eq!(MotorPort::parse("outB").unwrap().address(), "spi0.1:MB");
+    assert_eq!(SensorPort::parse("spi0.1:S2").unwrap().to_string(), "in2");
+}
@@ -0,0 +1,76 @@
+use std::fs;
+
+use ev3dev_lang_rust::calibration::{Calibration, DeviceCalibration};
+use ev3dev_lang_rust::sensors::{GyroSensor, SensorPort};
+use ev3dev_lang_rust::{Ev3Error, MockEv3, Port};
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_device_key() {
+    let ev3 = MockEv3::new();
+    ev3.add_sensor("lego-ev3-gyro", SensorPort::In2);
+    let gyro = GyroSensor::get_from(&ev3, SensorPort::In2).unwrap();
+
+    let mut calibration = Calibration::new();
+    assert!(calibration.device(&gyro).unwrap().is_empty());
+
+    calibration.device_mut(&gyro).unwrap().gyro_bias = Some(-0.25);
+    assert_eq!(
+        calibration.keys().collect::<Vec<_>>(),
+        [format!("lego-ev3-gyro@mock:{}", SensorPort::In2.address())]
+    );
+    assert_eq!(calibration.device(&gyro).unwrap().gyro_bias, Some(-0.25));
+}
+
+#[test]
+fn test_json_round_trip() {
+    let mut calibration = Calibration::new();
+    calibration.insert(
+        "lego-ev3-color@ev3-ports:in3",
+        DeviceCalibration {
+            white_balance: Some([1.0, 0.92, 1.31]),
+            ..Default::default()
+        },
+    );
+    let mut motor = DeviceCalibration {
+        wheel_diameter_factor: Some(1.02),
+        ..Default::default()
+    };
+    motor.values.insert("backlash".to_owned(), 3.0);
+    calibration.insert("lego-ev3-l-motor@ev3-ports:outB", motor);
+
+    let path = std::env::temp_dir().join(format!(
+        "ev3dev-lang-rust-{}-calibration.json",
+        std::process::id()
+    ));
+    calibration.save(&path).unwrap();
+    assert_eq!(Calibration::load(&path).unwrap(), calibration);
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        Calibration::load_or_default(&path).unwrap(),
+        Calibration::new()
+    );
+}
+
+#[test]
+fn test_invalid_json() {
+    for (content, expected) in [
+        (r#"{"version":2,"devices":{}}"#, "unsupported version 2"),
+        (r#"{"devices":{}}"#, "missing field `version`"),
+        (
+            r#"{"version":1,"devices":{"a":{"white_balance":[1,2]}}}"#,
+            "invalid length 2, expected an array of length 3",
+        ),
+        (
+            r#"{"version":1,"devices":{"a":{"backlash":"3"}}}"#,
+            "invalid type: string",
+        ),
+    ] {
+        match Calibration::from_json_str(content) {
+            Err(Ev3Error::InternalError { msg, .. }) => assert!(msg.contains(expected), "{msg}"),
+            result => panic!("Unexpected result {result:?}"),
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use ev3dev_lang_rust::calibration::Calibration;
+use ev3dev_lang_rust::config::{DeviceConfig, RobotConfig};
+use ev3dev_lang_rust::Ev3Error;
+
+extern crate ev3dev_lang_rust;
+
+fn error_message(result: Result<impl std::fmt::Debug, Ev3Error>) -> String {
+    match result.unwrap_err() {
+        Ev3Error::InternalError { msg, .. } => msg,
+        e => panic!("Unexpected error {e:?}"),
+    }
+}
+
+#[test]
+fn test_parse() {
+    let declarations = RobotConfig::parse(
+        r#"
+        drive_left = { type = "LargeMotor", port = "outB" }
+        line = { type = "ColorSensor", port = "IN3" }
+        gyro = { type = "GyroSensor" }
+        bumper = { type = "TouchSensor", port = "in1:mux2" }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        declarations.keys().collect::<Vec<_>>(),
+        vec!["bumper", "drive_left", "gyro", "line"]
+    );
+    assert_eq!(
+        declarations["drive_left"],
+        DeviceConfig {
+            device_type: "LargeMotor".to_owned(),
+            port: Some("outB".to_owned()),
+        }
+    );
+    assert_eq!(declarations["gyro"].port, None);
+}
+
+#[test]
+fn test_invalid_declarations() {
+    let msg = error_message(RobotConfig::parse(
+        r#"drive_left = { type = "HugeMotor", port = "outB" }"#,
+    ));
+    assert!(msg.contains("Unknown device type 'HugeMotor' of 'drive_left'"));
+
+    let msg = error_message(RobotConfig::parse(
+        r#"line = { type = "ColorSensor", port = "outB" }"#,
+    ));
+    assert_eq!(
+        msg,
+        "Invalid port 'outB' of 'line', a ColorSensor expects in1 to in4"
+    );
+
+    let msg = error_message(RobotConfig::parse(
+        "gyro = { type = \"GyroSensor\" }\nline = { type = \"ColorSensor\", pin = \"in3\" }",
+    ));
+    assert!(msg.starts_with("line 2: unknown field `pin`"));
+}
+
+#[test]
+fn test_missing_hardware() {
+    let msg = error_message(RobotConfig::from_toml_str(
+        r#"
+        drive_left = { type = "LargeMotor", port = "outB" }
+        line = { type = "ColorSensor", port = "in3" }
+        "#,
+    ));
+
+    assert!(msg.starts_with("Missing devices of the robot config: drive_left: "));
+    assert!(msg.contains("; line: "));
+}
+
+#[test]
+fn test_toml_calibration() {
+    let calibration = Calibration::from_toml_str(
+        r#"
+        version = 1
+
+        [devices."lego-ev3-gyro@ev3-ports:in2"]
+        gyro_bias = -0.25
+
+        [devices."lego-ev3-us@ev3-ports:in4"]
+        distance_offset = 2
+        "#,
+    )
+    .unwrap();
+
+    let us = calibration.get("lego-ev3-us@ev3-ports:in4").unwrap();
+    assert_eq!(us.distance_offset, Some(2.0));
+
+    let content = calibration.to_toml_string().unwrap();
+    assert_eq!(Calibration::from_toml_str(&content).unwrap(), calibration);
+    #[cfg(feature = "json")]
+    assert_eq!(
+        Calibration::from_json_str(&calibration.to_json_string().unwrap()).unwrap(),
+        calibration
+    );
+}
@@ -0,0 +1,226 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ev3dev_lang_rust::control::{Loop, Navigator, NavigatorOptions, PathExecutor, Pid, Segment};
+use ev3dev_lang_rust::motors::{LargeMotor, MotorPort, TachoMotor};
+use ev3dev_lang_rust::sensors::{GyroSensor, SensorPort};
+use ev3dev_lang_rust::task::CancelToken;
+use ev3dev_lang_rust::{Ev3Error, MockDevice, MockEv3};
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_loop_rate() {
+    let start = Instant::now();
+    let mut dts = Vec::new();
+
+    let stats = Loop::run_at(100.0, |ctx| {
+        assert_eq!(ctx.iteration(), dts.len() as u64);
+        dts.push(ctx.dt());
+        // Busy time is compensated by a shorter sleep.
+        thread::sleep(Duration::from_millis(4));
+        if ctx.iteration() == 9 {
+            ctx.stop();
+        }
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(stats.iterations, 10);
+    assert_eq!(dts[0], Duration::ZERO);
+    // A late iteration shortens the next dt, but the schedule does not drift.
+    assert!(dts[1..].iter().sum::<Duration>() >= Duration::from_millis(85));
+    // Each dt spans at least the busy time of the previous iteration and stays within a few periods.
+    for dt in &dts[1..] {
+        assert!(*dt >= Duration::from_millis(4), "{dts:?}");
+        assert!(*dt < Duration::from_millis(100), "{dts:?}");
+    }
+    assert!(stats.max_busy >= Duration::from_millis(4));
+
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(90));
+    assert!(elapsed < Duration::from_millis(500), "{elapsed:?}");
+}
+
+#[test]
+fn test_loop_overrun() {
+    let stats = Loop::new(Duration::from_millis(5))
+        .run(|ctx| {
+            thread::sleep(Duration::from_millis(12));
+            if ctx.iteration() == 2 {
+                ctx.stop();
+            }
+            Ok(())
+        })
+        .unwrap();
+
+    assert_eq!(stats.iterations, 3);
+    assert_eq!(stats.overruns, 2);
+}
+
+#[test]
+fn test_loop_end() {
+    let result = Loop::run_at(1000.0, |ctx| {
+        if ctx.iteration() == 3 {
+            return Err(Ev3Error::InternalError {
+                msg: "sensor failed".to_owned(),
+                path: None,
+            });
+        }
+        Ok(())
+    });
+    assert!(matches!(result, Err(Ev3Error::InternalError { .. })));
+
+    let token = CancelToken::new();
+    let stats = Loop::new(Duration::from_millis(10))
+        .cancel_token(token.clone())
+        .run(|ctx| {
+            if ctx.iteration() == 2 {
+                token.cancel();
+            }
+            Ok(())
+        })
+        .unwrap();
+    assert_eq!(stats.iterations, 3);
+
+    assert!(Loop::with_frequency(0.0).is_err());
+    assert!(Loop::run_at(f64::NAN, |_| Ok(())).is_err());
+}
+
+#[test]
+fn test_pid() {
+    let mut pid = Pid::new(2.0, 1.0, 0.5).with_limit(10.0);
+    assert_eq!(pid.update(1.0, 0.0), 2.0);
+    // 2 * 2 + 1 * (2 * 0.5) + 0.5 * (1 / 0.5)
+    assert_eq!(pid.update(2.0, 0.5), 6.0);
+    assert_eq!(pid.update(100.0, 0.5), 10.0);
+
+    pid.reset();
+    assert_eq!(pid.update(-1.0, 0.0), -2.0);
+}
+
+fn navigator(ev3: &MockEv3) -> (Navigator<GyroSensor>, MockDevice, MockDevice, MockDevice) {
+    let left = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutB);
+    let right = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutC);
+    let gyro = ev3.add_sensor("lego-ev3-gyro", SensorPort::In2);
+    gyro.set("mode", GyroSensor::MODE_GYRO_ANG);
+
+    let navigator = Navigator::new(
+        TachoMotor::from(LargeMotor::get_from(ev3, MotorPort::OutB).unwrap()),
+        TachoMotor::from(LargeMotor::get_from(ev3, MotorPort::OutC).unwrap()),
+        GyroSensor::get_from(ev3, SensorPort::In2).unwrap(),
+        NavigatorOptions {
+            rate: 500.0,
+            ..Default::default()
+        },
+    );
+    (navigator, left, right, gyro)
+}
+
+#[test]
+fn test_turn_to_heading() {
+    let ev3 = MockEv3::new();
+    let (navigator, left, right, gyro) = navigator(&ev3);
+    gyro.script("value0", [0, 30, 60, 89]);
+
+    navigator.turn_to_heading(90).unwrap();
+
+    let left_speeds = left.writes("speed_sp");
+    let right_speeds = right.writes("speed_sp");
+    assert_eq!(left_speeds.len(), 3);
+    assert_eq!(left_speeds[0], "400");
+    assert_eq!(right_speeds[0], "-400");
+    assert_eq!(left.last_write("command").as_deref(), Some("stop"));
+    assert_eq!(right.last_write("command").as_deref(), Some("stop"));
+}
+
+#[test]
+fn test_drive_heading() {
+    let ev3 = MockEv3::new();
+    let (mut navigator, left, right, gyro) = navigator(&ev3);
+    gyro.set("value0", 2);
+    left.script("position", [0, 0, 100, 100, 250]);
+    right.script("position", [0, 0, 100, 100, 250]);
+
+    navigator.reset_distance().unwrap();
+    navigator.drive_heading(0, 100.0, 300).unwrap();
+
+    // The robot points too far clockwise, so the right wheel is faster.
+    assert_eq!(left.writes("speed_sp"), ["280", "280"]);
+    assert_eq!(right.writes("speed_sp"), ["320", "320"]);
+    assert!((navigator.distance().unwrap() - 122.17).abs() < 0.01);
+
+    gyro.set("value0", 0);
+    left.set("position", 0);
+    let options = NavigatorOptions {
+        timeout: Some(Duration::from_millis(50)),
+        ..Default::default()
+    };
+    let navigator = Navigator::new(
+        TachoMotor::from(LargeMotor::get_from(&ev3, MotorPort::OutB).unwrap()),
+        TachoMotor::from(LargeMotor::get_from(&ev3, MotorPort::OutC).unwrap()),
+        GyroSensor::get_from(&ev3, SensorPort::In2).unwrap(),
+        options,
+    );
+    assert!(matches!(
+        navigator.drive_heading(0, -100.0, 300),
+        Err(Ev3Error::Timeout { .. })
+    ));
+    assert_eq!(left.last_write("speed_sp").as_deref(), Some("-300"));
+    assert_eq!(left.last_write("command").as_deref(), Some("stop"));
+}
+
+#[test]
+fn test_path_from_waypoints() {
+    let path = PathExecutor::from_waypoints(&[(100.0, 0.0), (100.0, 100.0), (0.0, 0.0)], 300);
+    assert_eq!(
+        path.segments(),
+        [
+            Segment::Straight { distance: 100.0 },
+            Segment::Turn { angle: 90 },
+            Segment::Straight { distance: 100.0 },
+            Segment::Turn { angle: 135 },
+            Segment::Straight {
+                distance: 100.0 * 2f32.sqrt()
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_path_execution() {
+    let ev3 = MockEv3::new();
+    let (navigator, left, right, gyro) = navigator(&ev3);
+    // Start heading, then the turn.
+    gyro.script("value0", [0, 45, 90]);
+    left.script("position", [0, 0, 300]);
+    right.script("position", [0, 0, 300]);
+
+    let path = PathExecutor::new(
+        vec![
+            Segment::Turn { angle: 90 },
+            Segment::Straight { distance: 100.0 },
+            Segment::Turn { angle: -90 },
+        ],
+        300,
+    );
+
+    let mut started = Vec::new();
+    let token = path.cancel_token();
+    let completed = path
+        .execute(&navigator, |progress| {
+            assert_eq!(progress.total, 3);
+            started.push(progress.index);
+            if progress.index == 2 {
+                token.cancel();
+            }
+        })
+        .unwrap();
+
+    assert_eq!(completed, 2);
+    assert_eq!(started, [0, 1, 2]);
+    assert_eq!(left.writes("speed_sp").len(), 2);
+    assert_eq!(left.last_write("command").as_deref(), Some("stop"));
+
+    assert_eq!(path.execute(&navigator, |_| {}).unwrap(), 0);
+}
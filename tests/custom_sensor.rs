@@ -0,0 +1,54 @@
+use ev3dev_lang_rust::custom_sensor;
+use ev3dev_lang_rust::sensors::{Sensor, SensorPort};
+use ev3dev_lang_rust::{Device, Ev3Error, MockEv3, Port};
+
+extern crate ev3dev_lang_rust;
+
+custom_sensor! {
+    /// HiTechnic NXT EOPD sensor.
+    pub struct EopdSensor {
+        drivers: ["ht-nxt-eopd"],
+        port: SensorPort,
+        modes: [
+            (MODE_SHORT, "SHORT", "Short range", set_mode_short, is_mode_short),
+            (MODE_LONG, "LONG", "Long range", set_mode_long, is_mode_long),
+        ],
+        values: [(get_proximity, 0, "Proximity")],
+    }
+}
+
+custom_sensor! {
+    struct MinimalSensor {
+        drivers: ["minimal", "minimal-v2"],
+        port: SensorPort,
+        modes: [],
+        values: [],
+    }
+}
+
+#[test]
+fn test_custom_sensor() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_sensor("ht-nxt-eopd", SensorPort::In2);
+    mock.set("mode", "SHORT").set("value0", 512);
+    ev3.add_sensor("minimal-v2", SensorPort::In3);
+
+    let sensor = EopdSensor::get_from(&ev3, SensorPort::In2).unwrap();
+    assert_eq!(EopdSensor::MODE_LONG, "LONG");
+    assert!(sensor.is_mode_short().unwrap());
+    sensor.set_mode_long().unwrap();
+    assert_eq!(mock.last_write("mode").as_deref(), Some("LONG"));
+    assert_eq!(sensor.get_proximity().unwrap(), 512);
+    assert_eq!(sensor.get_value0().unwrap(), 512);
+    assert_eq!(
+        sensor.get_address().unwrap(),
+        format!("mock:{}", SensorPort::In2.address())
+    );
+
+    match EopdSensor::get_from(&ev3, SensorPort::In3) {
+        Err(Ev3Error::NotConnected { device, .. }) => assert_eq!(device, "EopdSensor"),
+        result => panic!("Unexpected result {result:?}"),
+    }
+    assert!(MinimalSensor::find_from(&ev3).is_ok());
+    assert_eq!(EopdSensor::list_from(&ev3).unwrap().len(), 1);
+}
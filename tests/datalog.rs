@@ -0,0 +1,273 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ev3dev_lang_rust::datalog::{
+    Channel, LogFormat, Recorder, RecorderOptions, Replay, ReplayDevice, TimeSeries,
+};
+#[cfg(feature = "json")]
+use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+use ev3dev_lang_rust::sensors::{GyroSensor, Sensor, SensorPort};
+use ev3dev_lang_rust::{Ev3Error, Ev3Result};
+
+extern crate ev3dev_lang_rust;
+
+fn log_dir(name: &str) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("ev3dev-lang-rust-{}-{}", std::process::id(), name));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+fn counter() -> Channel {
+    let count = Arc::new(AtomicI32::new(0));
+    Channel::new("count", move || Ok(count.fetch_add(1, Ordering::SeqCst)))
+}
+
+fn failing() -> Channel {
+    Channel::new("failing", || -> Ev3Result<i32> {
+        Err(Ev3Error::InternalError {
+            msg: "Not connected".to_owned(),
+            path: None,
+        })
+    })
+}
+
+fn options(format: LogFormat) -> RecorderOptions {
+    RecorderOptions {
+        interval: Duration::from_millis(5),
+        format,
+        ..RecorderOptions::default()
+    }
+}
+
+#[test]
+fn test_csv() {
+    let dir = log_dir("csv");
+    let path = dir.join("run.csv");
+
+    let recorder = Recorder::start(
+        &path,
+        vec![counter(), failing(), Channel::new("label", || Ok("a,b"))],
+        options(LogFormat::Csv),
+    )
+    .unwrap();
+    thread::sleep(Duration::from_millis(50));
+    assert!(recorder.is_running());
+    recorder.stop().unwrap();
+
+    let content = fs::read_to_string(&path).unwrap();
+    let lines = content.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "time,count,failing,label");
+    assert!(lines.len() > 3);
+    assert!(lines[1].starts_with("0.00"));
+    assert!(lines[1].ends_with(",0,,\"a,b\""));
+    assert!(lines[2].ends_with(",1,,\"a,b\""));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_json_lines() {
+    let dir = log_dir("json");
+    let path = dir.join("run.jsonl");
+
+    let recorder = Recorder::start(
+        &path,
+        vec![
+            counter(),
+            failing(),
+            Channel::new("mode", || Ok("COL-\"RGB\"")),
+        ],
+        options(LogFormat::JsonLines),
+    )
+    .unwrap();
+    thread::sleep(Duration::from_millis(30));
+    drop(recorder);
+
+    let content = fs::read_to_string(&path).unwrap();
+    let first = content.lines().next().unwrap();
+    assert!(first.starts_with("{\"time\":0.00"));
+    assert!(first.ends_with(",\"count\":0,\"failing\":null,\"mode\":\"COL-\\\"RGB\\\"\"}"));
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+#[cfg(not(feature = "json"))]
+fn test_json_lines_unsupported() {
+    let dir = log_dir("json-unsupported");
+    let path = dir.join("run.jsonl");
+    assert!(Recorder::start(&path, vec![counter()], options(LogFormat::JsonLines)).is_err());
+    assert!(!path.exists());
+    assert!(Replay::parse("{\"time\":0,\"position\":10}\n").is_err());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_rotation() {
+    let dir = log_dir("rotation");
+    let path = dir.join("run.csv");
+
+    let recorder = Recorder::start(
+        &path,
+        vec![counter()],
+        RecorderOptions {
+            max_file_size: Some(64),
+            max_files: 2,
+            ..options(LogFormat::Csv)
+        },
+    )
+    .unwrap();
+    thread::sleep(Duration::from_millis(200));
+    recorder.stop().unwrap();
+
+    let mut files = fs::read_dir(&dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect::<Vec<_>>();
+    files.sort();
+    assert_eq!(files, vec!["run.1.csv", "run.2.csv", "run.csv"]);
+
+    for file in files {
+        let content = fs::read_to_string(dir.join(file)).unwrap();
+        assert!(content.starts_with("time,count\n"));
+        assert!(content.len() <= 64);
+    }
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+fn gyro(replay: &Replay) -> GyroSensor {
+    replay
+        .add(
+            ReplayDevice::new("lego-sensor", "lego-ev3-gyro", &SensorPort::In2)
+                .attribute("mode", GyroSensor::MODE_GYRO_ANG)
+                .column("value0", "angle"),
+        )
+        .unwrap();
+    GyroSensor::get_from(replay, SensorPort::In2).unwrap()
+}
+
+#[test]
+fn test_replay_csv() {
+    let replay = Replay::parse("time,angle,label\n0.000,0,\"a,b\"\n0.010,5,\n0.020,,\n").unwrap();
+    assert_eq!(replay.columns(), ["angle", "label"]);
+
+    let gyro = gyro(&replay);
+    assert_eq!(gyro.get_angle().unwrap(), 0);
+
+    assert!(replay.step());
+    assert_eq!(replay.time(), 0.01);
+    assert_eq!(gyro.get_angle().unwrap(), 5);
+
+    // Failed readings of the recorder are errors.
+    assert!(replay.step());
+    assert!(gyro.get_angle().is_err());
+    assert!(!replay.step());
+    assert!(replay.is_finished());
+
+    replay.seek(0.015);
+    assert_eq!(gyro.get_angle().unwrap(), 5);
+
+    // Written attributes are kept.
+    gyro.set_mode_gyro_rate().unwrap();
+    assert_eq!(gyro.get_mode().unwrap(), GyroSensor::MODE_GYRO_RATE);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_replay_json_lines() {
+    let replay = Replay::parse(
+        "{\"time\":0,\"position\":10}\n{\"time\":0.5,\"position\":-20,\"state\":\"running\"}\n",
+    )
+    .unwrap();
+    replay
+        .add(
+            ReplayDevice::new("tacho-motor", "lego-ev3-l-motor", &MotorPort::OutA)
+                .column("position", "position"),
+        )
+        .unwrap();
+
+    let motor = LargeMotor::get_from(&replay, MotorPort::OutA).unwrap();
+    assert_eq!(motor.get_position().unwrap(), 10);
+
+    replay.play(10.0);
+    thread::sleep(Duration::from_millis(100));
+    assert_eq!(motor.get_position().unwrap(), -20);
+    assert!(replay.is_finished());
+
+    assert!(LargeMotor::get_from(&replay, MotorPort::OutB).is_err());
+    assert!(replay
+        .add(
+            ReplayDevice::new("tacho-motor", "lego-ev3-l-motor", &MotorPort::OutB)
+                .column("speed", "speed")
+        )
+        .is_err());
+}
+
+#[test]
+fn test_record_and_replay() {
+    let dir = log_dir("replay");
+    let path = dir.join("run.csv");
+
+    let recorder = Recorder::start(&path, vec![counter()], options(LogFormat::Csv)).unwrap();
+    thread::sleep(Duration::from_millis(30));
+    recorder.stop().unwrap();
+
+    let replay = Replay::open(&path).unwrap();
+    replay
+        .add(
+            ReplayDevice::new("lego-sensor", "lego-ev3-gyro", &SensorPort::In1)
+                .column("value0", "count"),
+        )
+        .unwrap();
+    let gyro = GyroSensor::get_from(&replay, SensorPort::In1).unwrap();
+
+    let mut values = vec![gyro.get_value0().unwrap()];
+    while replay.step() {
+        values.push(gyro.get_value0().unwrap());
+    }
+    assert!(values.len() > 2);
+    assert_eq!(values, (0..values.len() as i32).collect::<Vec<_>>());
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn test_time_series() {
+    let start = Instant::now();
+    let mut positions = TimeSeries::new(4);
+    assert_eq!(positions.rate(Duration::from_secs(1)), None);
+
+    for (ms, position) in [(0, 0), (100, 30), (200, 50), (300, 80), (400, 120)] {
+        positions.push_at(start + Duration::from_millis(ms), position);
+    }
+
+    assert_eq!(positions.len(), 4);
+    assert_eq!(positions.oldest().unwrap().1, &30);
+    assert_eq!(positions.latest().unwrap().1, &120);
+
+    let window = Duration::from_millis(200);
+    assert_eq!(positions.window(window).count(), 3);
+    assert_eq!(positions.min(window), Some(50.0));
+    assert_eq!(positions.max(window), Some(120.0));
+    assert_eq!(positions.mean(window), Some(250.0 / 3.0));
+    assert!((positions.rate(window).unwrap() - 350.0).abs() < 1e-9);
+    assert_eq!(positions.rate(Duration::ZERO), None);
+
+    positions.push_at(start + Duration::from_millis(350), 100);
+    assert_eq!(
+        positions
+            .iter()
+            .map(|(_, value)| *value)
+            .collect::<Vec<_>>(),
+        [50, 80, 100, 120]
+    );
+}
@@ -0,0 +1,37 @@
+use std::error::Error;
+use std::io;
+use std::path::Path;
+
+use ev3dev_lang_rust::{Attribute, Ev3Error};
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_io_error_contains_path() {
+    let path = Path::new("/this/path/does/not/exist/value0");
+    let error = Attribute::from_path(path).unwrap_err();
+
+    match &error {
+        Ev3Error::Io {
+            path: Some(error_path),
+            source,
+        } => {
+            assert_eq!(error_path, path);
+            assert_eq!(source.kind(), io::ErrorKind::NotFound);
+        }
+        e => panic!("Unexpected error {e:?}"),
+    }
+
+    assert!(error
+        .to_string()
+        .contains("/this/path/does/not/exist/value0"));
+    assert!(error.source().is_some());
+}
+
+#[test]
+fn test_io_error_conversion() {
+    let error = Ev3Error::from(io::Error::other("broken"));
+
+    assert!(matches!(error, Ev3Error::Io { path: None, .. }));
+    assert_eq!(error.source().unwrap().to_string(), "broken");
+}
@@ -1,4 +1,8 @@
-use ev3dev_lang_rust::{motors::MotorPort, sensors::SensorPort, Port};
+use ev3dev_lang_rust::{
+    motors::MotorPort,
+    sensors::{MuxChannel, MuxPort, SensorPort},
+    Ev3Error, Port,
+};
 
 extern crate ev3dev_lang_rust;
 
@@ -17,3 +21,95 @@ fn test_output_port_mapping() {
     assert_eq!(MotorPort::OutC.address(), "outC".to_string());
     assert_eq!(MotorPort::OutD.address(), "outD".to_string());
 }
+
+#[test]
+fn test_mux_port_mapping() {
+    let port = MuxPort::new(SensorPort::In1, MuxChannel::Mux1);
+    assert_eq!(port.address(), "in1:i2c80:mux1".to_string());
+
+    let port = MuxPort::new(SensorPort::In4, MuxChannel::Mux3);
+    assert_eq!(port.address(), "in4:i2c82:mux3".to_string());
+}
+
+#[test]
+fn test_port_address_matching() {
+    assert!(SensorPort::In1.matches_address("ev3-ports:in1"));
+    assert!(SensorPort::In1.matches_address("ev3-ports:in1:i2c1"));
+    assert!(!SensorPort::In1.matches_address("ev3-ports:in2"));
+    assert!(!SensorPort::In1.matches_address("ev3-ports:in10"));
+    assert!(!SensorPort::In1.matches_address("ev3-ports:in1:i2c80:mux1"));
+
+    let port = MuxPort::new(SensorPort::In1, MuxChannel::Mux1);
+    assert!(port.matches_address("ev3-ports:in1:i2c80:mux1"));
+    assert!(!port.matches_address("ev3-ports:in1:i2c81:mux2"));
+    assert!(!port.matches_address("ev3-ports:in1"));
+}
+
+#[test]
+fn test_port_parsing() {
+    for name in ["outB", "OUTB", "b", "B"] {
+        assert!(matches!(MotorPort::parse(name), Ok(MotorPort::OutB)));
+    }
+    for name in ["in3", "IN3", "3", " in3\n"] {
+        assert!(matches!(name.parse::<SensorPort>(), Ok(SensorPort::In3)));
+    }
+    assert!(matches!(
+        "outE".parse::<MotorPort>(),
+        Err(Ev3Error::InvalidPort { value, .. }) if value == "outE"
+    ));
+    assert!("in1".parse::<MotorPort>().is_err());
+    assert!("in0".parse::<SensorPort>().is_err());
+    assert!("".parse::<SensorPort>().is_err());
+
+    let port = MuxPort::parse("in2:mux3").unwrap();
+    assert_eq!(port.address(), "in2:i2c82:mux3");
+    assert_eq!(
+        MuxPort::parse("in2:i2c82:mux3").unwrap().address(),
+        port.address()
+    );
+    assert!(MuxPort::parse("in2").is_err());
+}
+
+#[test]
+fn test_port_display() {
+    assert_eq!(MotorPort::OutC.to_string(), "outC");
+    assert_eq!(SensorPort::In4.to_string(), "in4");
+    assert_eq!(
+        MuxPort::new(SensorPort::In1, MuxChannel::Mux2).to_string(),
+        "in1:mux2"
+    );
+
+    for port in MotorPort::ALL {
+        assert_eq!(
+            port.to_string().parse::<MotorPort>().unwrap().address(),
+            port.address()
+        );
+    }
+    for port in SensorPort::ALL {
+        assert_eq!(
+            port.to_string().parse::<SensorPort>().unwrap().address(),
+            port.address()
+        );
+    }
+}
+
+#[test]
+fn test_get_by_address_and_env() {
+    use ev3dev_lang_rust::motors::LargeMotor;
+    use ev3dev_lang_rust::sensors::ColorSensor;
+
+    match ColorSensor::get_by_address("outA").unwrap_err() {
+        Ev3Error::InvalidPort { value, .. } => assert_eq!(value, "outA"),
+        e => panic!("Unexpected error {e:?}"),
+    }
+
+    std::env::set_var("EV3_TEST_LEFT_MOTOR_PORT", "in2");
+    match LargeMotor::get_by_env("EV3_TEST_LEFT_MOTOR_PORT", MotorPort::OutB).unwrap_err() {
+        e @ Ev3Error::InvalidPort { .. } => {
+            let msg = e.to_string();
+            assert!(msg.contains("'in2'"));
+            assert!(msg.ends_with("(from environment variable EV3_TEST_LEFT_MOTOR_PORT)!"));
+        }
+        e => panic!("Unexpected error {e:?}"),
+    }
+}
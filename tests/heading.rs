@@ -0,0 +1,13 @@
+use ev3dev_lang_rust::sensors::heading_difference;
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_heading_difference() {
+    assert_eq!(heading_difference(90, 0), 90);
+    assert_eq!(heading_difference(0, 90), -90);
+    assert_eq!(heading_difference(10, 350), 20);
+    assert_eq!(heading_difference(350, 10), -20);
+    assert_eq!(heading_difference(720, 0), 0);
+    assert_eq!(heading_difference(0, -370), 10);
+}
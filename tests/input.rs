@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use ev3dev_lang_rust::input::{Gamepad, GamepadEvent};
+use ev3dev_lang_rust::Ev3Error;
+
+extern crate ev3dev_lang_rust;
+
+/// Encodes a `struct js_event`.
+fn js_event(value: i16, type_: u8, number: u8) -> Vec<u8> {
+    let mut event = 1234u32.to_ne_bytes().to_vec();
+    event.extend_from_slice(&value.to_ne_bytes());
+    event.push(type_);
+    event.push(number);
+    event
+}
+
+fn joystick_file(name: &str, events: &[Vec<u8>]) -> PathBuf {
+    let path =
+        std::env::temp_dir().join(format!("ev3dev-lang-rust-{}-{}", std::process::id(), name));
+    fs::write(&path, events.concat()).unwrap();
+    path
+}
+
+#[test]
+fn test_gamepad_state() {
+    let path = joystick_file(
+        "js-state",
+        &[
+            // Initial state
+            js_event(0, 0x81, 0),
+            js_event(0, 0x82, 1),
+            js_event(1000, 0x82, 0),
+            // Unknown event type
+            js_event(0, 0x04, 0),
+            js_event(1, 0x01, 2),
+            js_event(-32767, 0x02, 1),
+            js_event(16384, 0x02, 0),
+        ],
+    );
+
+    let mut gamepad = Gamepad::open(&path).unwrap().with_deadzone(0.1);
+    assert_eq!(gamepad.path(), path.as_path());
+    assert_eq!(gamepad.update().unwrap(), 6);
+
+    assert!(gamepad.button(2));
+    assert!(!gamepad.button(0));
+    assert!(!gamepad.button(7));
+    assert_eq!(gamepad.raw_axis(1), -32767);
+    assert_eq!(gamepad.axis(1), -1.0);
+    assert_eq!(gamepad.scaled_axis(1, 900), -900);
+    assert!((gamepad.axis(0) - (0.5 - 0.1) / 0.9).abs() < 0.01);
+    assert_eq!(gamepad.axis(5), 0.0);
+
+    // Small deflections are inside the deadzone.
+    let path = joystick_file("js-deadzone", &[js_event(3000, 0x02, 0)]);
+    let mut gamepad = Gamepad::open(&path).unwrap().with_deadzone(0.1);
+    gamepad.update().unwrap();
+    assert_eq!(gamepad.raw_axis(0), 3000);
+    assert_eq!(gamepad.axis(0), 0.0);
+}
+
+#[test]
+fn test_gamepad_events() {
+    let path = joystick_file(
+        "js-events",
+        &[
+            js_event(1, 0x01, 3),
+            js_event(-200, 0x02, 2),
+            js_event(0, 0x01, 3),
+        ],
+    );
+
+    let mut gamepad = Gamepad::open(&path).unwrap();
+    let timeout = Some(Duration::from_millis(10));
+    assert_eq!(
+        gamepad.next_event(timeout).unwrap(),
+        Some(GamepadEvent::Button {
+            number: 3,
+            pressed: true
+        })
+    );
+    assert_eq!(
+        gamepad.next_event(timeout).unwrap(),
+        Some(GamepadEvent::Axis {
+            number: 2,
+            value: -200
+        })
+    );
+    assert!(gamepad.button(3));
+    assert_eq!(
+        gamepad.next_event(timeout).unwrap(),
+        Some(GamepadEvent::Button {
+            number: 3,
+            pressed: false
+        })
+    );
+    assert_eq!(gamepad.next_event(timeout).unwrap(), None);
+
+    let (sender, receiver) = mpsc::channel();
+    let listener = Gamepad::open(&path).unwrap().listen(move |event| {
+        let _ = sender.send(event);
+    });
+    let events = (0..3)
+        .map(|_| receiver.recv_timeout(Duration::from_secs(1)).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events[1],
+        GamepadEvent::Axis {
+            number: 2,
+            value: -200
+        }
+    );
+    listener.stop();
+
+    assert!(matches!(
+        Gamepad::open("/nonexistent/js0"),
+        Err(Ev3Error::Io { .. })
+    ));
+}
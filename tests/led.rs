@@ -0,0 +1,33 @@
+use ev3dev_lang_rust::Trigger;
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_trigger_names() {
+    for trigger in [
+        Trigger::None,
+        Trigger::Timer,
+        Trigger::Heartbeat,
+        Trigger::Other("default-on".to_owned()),
+    ] {
+        assert_eq!(Trigger::from_name(trigger.name()), trigger);
+    }
+
+    assert_eq!(Trigger::Timer.to_string(), "timer");
+    assert_eq!(
+        Trigger::from_name("mmc0"),
+        Trigger::Other("mmc0".to_owned())
+    );
+}
+
+#[cfg(feature = "ev3")]
+#[test]
+fn test_interpolate_color() {
+    use ev3dev_lang_rust::interpolate_color;
+
+    assert_eq!(interpolate_color((0, 255), (255, 0), 0.0), (0, 255));
+    assert_eq!(interpolate_color((0, 255), (255, 0), 0.5), (128, 128));
+    assert_eq!(interpolate_color((0, 255), (255, 0), 1.0), (255, 0));
+    assert_eq!(interpolate_color((0, 0), (100, 200), 2.0), (100, 200));
+    assert_eq!(interpolate_color((0, 0), (100, 200), -1.0), (0, 0));
+}
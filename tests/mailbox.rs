@@ -0,0 +1,65 @@
+use std::io::Cursor;
+
+use ev3dev_lang_rust::mailbox::Message;
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_encode_text() {
+    let data = Message::text("abc", "hi").encode().unwrap();
+    assert_eq!(
+        data,
+        [
+            0x0E, 0x00, 0x01, 0x00, 0x81, 0x9E, 0x04, b'a', b'b', b'c', 0x00, 0x03, 0x00, b'h',
+            b'i', 0x00
+        ]
+    );
+}
+
+#[test]
+fn test_round_trip() {
+    for message in [
+        Message::text("status", "ready"),
+        Message::number("speed", 42.5),
+        Message::logic("done", true),
+    ] {
+        let data = message.encode().unwrap();
+        assert_eq!(Message::decode(&data).unwrap(), message);
+    }
+
+    assert_eq!(
+        Message::text("status", "ready").as_text(),
+        Some("ready".to_owned())
+    );
+    assert_eq!(Message::number("speed", 42.5).as_number(), Some(42.5));
+    assert_eq!(Message::logic("done", true).as_logic(), Some(true));
+    assert_eq!(Message::logic("done", true).as_number(), None);
+}
+
+#[test]
+fn test_read_stream() {
+    let mut data = Message::text("a", "first").encode().unwrap();
+    data.extend(Message::number("b", -1.0).encode().unwrap());
+
+    let mut stream = Cursor::new(data);
+    assert_eq!(
+        Message::read_from(&mut stream).unwrap(),
+        Message::text("a", "first")
+    );
+    assert_eq!(
+        Message::read_from(&mut stream).unwrap(),
+        Message::number("b", -1.0)
+    );
+    assert!(Message::read_from(&mut stream).is_err());
+}
+
+#[test]
+fn test_decode_invalid() {
+    let mut data = Message::text("abc", "hi").encode().unwrap();
+    assert!(Message::decode(&data[..data.len() - 1]).is_err());
+
+    data[5] = 0x00;
+    assert!(Message::decode(&data).is_err());
+
+    assert!(Message::text(&"x".repeat(300), "").encode().is_err());
+}
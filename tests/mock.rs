@@ -0,0 +1,762 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use ev3dev_lang_rust::claim::{ClaimKind, DeviceClaim};
+use ev3dev_lang_rust::motors::{
+    emergency_stop_all_from, emergency_stop_on_signal_from, DutyRamp, DutyRampOptions, EStop,
+    EStopOptions, LargeMotor, LinearActuator, MediumMotor, MotorPort, RunOutcome, WedoMotor,
+};
+use ev3dev_lang_rust::sensors::{
+    read_snapshot, read_snapshot_parallel, ColorSensor, CompassSensor, GyroSensor, HeadingSensor,
+    InfraredSensor, PixyCamera, Sensor, SensorPort, TouchSensor, WedoMotionSensor, WedoTilt,
+    WedoTiltSensor,
+};
+use ev3dev_lang_rust::{
+    CacheStrategy, Device, Driver, Ev3, Ev3Error, Ev3Result, MockEv3, Port, WedoPort,
+};
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_scripted_values() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_sensor("lego-ev3-color", SensorPort::In3);
+    mock.set("mode", ColorSensor::MODE_COL_REFLECT)
+        .script("value0", [10, 20, 30]);
+
+    let sensor = ColorSensor::get_from(&ev3, SensorPort::In3).unwrap();
+    assert_eq!(sensor.get_value0().unwrap(), 10);
+    assert_eq!(sensor.get_value0().unwrap(), 20);
+    assert_eq!(sensor.get_value0().unwrap(), 30);
+    // The last value is kept.
+    assert_eq!(sensor.get_value0().unwrap(), 30);
+    assert_eq!(mock.value("value0").as_deref(), Some("30"));
+
+    // Unset attributes behave like missing files.
+    assert!(matches!(sensor.get_value1(), Err(Ev3Error::Io { .. })));
+}
+
+#[test]
+fn test_written_attributes() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutB);
+
+    let motor = LargeMotor::get_from(&ev3, MotorPort::OutB).unwrap();
+    motor.set_speed_sp(500).unwrap();
+    motor.run_forever().unwrap();
+    motor.stop().unwrap();
+
+    assert_eq!(mock.writes("speed_sp"), ["500"]);
+    assert_eq!(mock.writes("command"), ["run-forever", "stop"]);
+    assert_eq!(mock.last_write("command").as_deref(), Some("stop"));
+
+    mock.clear_writes();
+    assert!(mock.writes("command").is_empty());
+    assert_eq!(motor.get_speed_sp().unwrap(), 500);
+}
+
+#[test]
+fn test_device_lookup() {
+    let ev3 = MockEv3::new();
+    ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    ev3.add_motor("lego-ev3-l-motor", MotorPort::OutB);
+    ev3.add_motor("lego-ev3-m-motor", MotorPort::OutC);
+
+    assert_eq!(LargeMotor::list_from(&ev3).unwrap().len(), 2);
+    assert!(matches!(
+        LargeMotor::find_from(&ev3),
+        Err(Ev3Error::MultipleMatches { .. })
+    ));
+    assert!(MediumMotor::find_from(&ev3).is_ok());
+    match MediumMotor::get_from(&ev3, MotorPort::OutD) {
+        Err(Ev3Error::NotConnected { path, .. }) => {
+            assert!(path.unwrap().ends_with("tacho-motor"))
+        }
+        result => panic!("Unexpected result {result:?}"),
+    }
+}
+
+#[test]
+fn test_disconnect_and_reconnect() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+
+    let motor = LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap();
+    assert!(motor.is_connected());
+
+    mock.disconnect();
+    assert!(!motor.is_connected());
+    assert!(motor.get_position().is_err());
+    assert!(motor.reconnect().is_err());
+
+    // Plugging the motor in again creates a new device directory.
+    let replugged = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    replugged.set("position", 90);
+    motor.reconnect().unwrap();
+    assert_eq!(motor.get_position().unwrap(), 90);
+}
+
+#[test]
+fn test_transparent_reconnect() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    mock.set("position", 10).set("state", "");
+
+    let motor = LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap();
+    let state = motor.get_read_only_attribute("state").unwrap();
+    assert_eq!(motor.get_position().unwrap(), 10);
+
+    mock.disconnect();
+    let replugged = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    replugged.set("position", 90).set("state", "running");
+
+    // Cached attributes find the re-plugged motor without calling `reconnect()`.
+    assert_eq!(motor.get_position().unwrap(), 90);
+    assert_eq!(state.get_vec().unwrap(), ["running"]);
+    // The stale attribute keeps the new handle instead of reopening it on every access.
+    assert_eq!(
+        state.get_file_path(),
+        motor.get_attribute("state").unwrap().get_file_path()
+    );
+    replugged.set("state", "holding");
+    assert_eq!(state.get_vec().unwrap(), ["holding"]);
+    motor.run_forever().unwrap();
+    assert_eq!(replugged.writes("command"), ["run-forever"]);
+    assert!(mock.writes("command").is_empty());
+}
+
+#[test]
+fn test_mode_check() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_sensor("lego-ev3-color", SensorPort::In1);
+    mock.set("mode", ColorSensor::MODE_COL_REFLECT)
+        .set("value0", 1)
+        .set("value1", 2)
+        .set("value2", 3);
+
+    let mut sensor = ColorSensor::get_from(&ev3, SensorPort::In1).unwrap();
+    assert!(!sensor.get_auto_mode_switch());
+    match sensor.get_rgb() {
+        Err(Ev3Error::WrongMode {
+            expected,
+            actual,
+            path,
+        }) => {
+            assert_eq!(expected, [ColorSensor::MODE_RGB_RAW]);
+            assert_eq!(actual, ColorSensor::MODE_COL_REFLECT);
+            assert!(path.unwrap().ends_with("mode"));
+        }
+        result => panic!("Unexpected result {result:?}"),
+    }
+    assert!(mock.writes("mode").is_empty());
+    assert_eq!(sensor.get_color().unwrap(), 1);
+
+    sensor.set_auto_mode_switch(true);
+    assert_eq!(sensor.get_rgb().unwrap(), (1, 2, 3));
+    assert_eq!(mock.writes("mode"), [ColorSensor::MODE_RGB_RAW]);
+    // The sensor stays in the new mode.
+    assert_eq!(sensor.get_red().unwrap(), 1);
+    assert_eq!(mock.writes("mode").len(), 1);
+}
+
+#[test]
+fn test_mode_guard() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_sensor("lego-ev3-color", SensorPort::In1);
+    mock.set("mode", ColorSensor::MODE_COL_REFLECT)
+        .set("value0", 1)
+        .set("value1", 2)
+        .set("value2", 3);
+
+    let sensor = ColorSensor::get_from(&ev3, SensorPort::In1).unwrap();
+    let read_rgb = || -> Ev3Result<(i32, i32, i32)> {
+        let guard = sensor.with_mode(ColorSensor::MODE_RGB_RAW)?;
+        assert_eq!(guard.get_previous_mode(), ColorSensor::MODE_COL_REFLECT);
+        assert_eq!(
+            mock.value("mode").as_deref(),
+            Some(ColorSensor::MODE_RGB_RAW)
+        );
+        guard.get_rgb()
+    };
+    assert_eq!(read_rgb().unwrap(), (1, 2, 3));
+    assert_eq!(
+        mock.writes("mode"),
+        [ColorSensor::MODE_RGB_RAW, ColorSensor::MODE_COL_REFLECT]
+    );
+    assert_eq!(sensor.get_mode().unwrap(), ColorSensor::MODE_COL_REFLECT);
+
+    // The previous mode is also restored on an early return.
+    mock.set("value1", "invalid");
+    assert!(read_rgb().is_err());
+    assert_eq!(mock.writes("mode").len(), 4);
+    assert_eq!(sensor.get_mode().unwrap(), ColorSensor::MODE_COL_REFLECT);
+
+    // No mode is written if the sensor is already in the requested mode.
+    drop(sensor.with_mode(ColorSensor::MODE_COL_REFLECT).unwrap());
+    assert_eq!(mock.writes("mode").len(), 4);
+}
+
+#[test]
+fn test_device_claims() {
+    let ev3 = MockEv3::new();
+    ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    let motor = LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap();
+    // A second handle of the same device shares its claims.
+    let other = LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap();
+
+    let drive = DeviceClaim::exclusive(&motor, "drive").unwrap();
+    assert_eq!(drive.get_kind(), ClaimKind::Exclusive);
+    for kind in [ClaimKind::Exclusive, ClaimKind::Shared] {
+        match DeviceClaim::claim(&other, "line follower", kind) {
+            Err(Ev3Error::AlreadyClaimed { device, owner, .. }) => {
+                assert_eq!(device, format!("mock:{}", MotorPort::OutA.address()));
+                assert_eq!(owner, "drive");
+            }
+            result => panic!("Unexpected result {result:?}"),
+        }
+    }
+
+    drop(drive);
+    let logger = DeviceClaim::shared(&motor, "logger").unwrap();
+    let telemetry = DeviceClaim::shared(&other, "telemetry").unwrap();
+    assert!(matches!(
+        DeviceClaim::exclusive(&motor, "drive"),
+        Err(Ev3Error::AlreadyClaimed { .. })
+    ));
+
+    drop(logger);
+    drop(telemetry);
+    assert!(DeviceClaim::exclusive(&other, "drive").is_ok());
+}
+
+#[test]
+fn test_compass_heading() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_sensor("ht-nxt-compass", SensorPort::In2);
+    mock.script("value0", [359, -258, 360, 0]);
+
+    let compass = CompassSensor::get_from(&ev3, SensorPort::In2).unwrap();
+    assert_eq!(compass.get_heading().unwrap(), 359);
+    // The sensor reports -258 while it is not calibrated.
+    assert!(matches!(
+        HeadingSensor::get_heading(&compass),
+        Err(Ev3Error::UnsupportedOperation { .. })
+    ));
+    assert!(compass.get_heading().is_err());
+    assert_eq!(compass.get_heading().unwrap(), 0);
+}
+
+#[test]
+fn test_wait_for() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_sensor("lego-ev3-color", SensorPort::In3);
+    mock.set("mode", ColorSensor::MODE_COL_REFLECT)
+        .script("value0", [40, 30, 8, 50]);
+
+    let sensor = ColorSensor::get_from(&ev3, SensorPort::In3).unwrap();
+    assert_eq!(
+        sensor
+            .wait_for(|reflection| reflection < 10, Duration::from_secs(1))
+            .unwrap(),
+        8
+    );
+
+    let timeout = Duration::from_millis(50);
+    let address = sensor.get_attribute("address").unwrap().get_file_path();
+    match sensor.wait_for(|reflection| reflection < 10, timeout) {
+        Err(Ev3Error::Timeout {
+            timeout: actual,
+            path,
+        }) => {
+            assert_eq!(actual, timeout);
+            assert_eq!(path.as_deref(), address.parent());
+        }
+        value => panic!("Unexpected result {value:?}"),
+    }
+
+    mock.disconnect();
+    assert!(matches!(
+        sensor.wait_for_value(ColorSensor::get_value0, |_| true, None),
+        Err(Ev3Error::Io { .. })
+    ));
+}
+
+#[test]
+fn test_wait_until_state() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    mock.script("state", ["running", "running", "running", "holding"]);
+
+    // Without a file to poll, the state is checked periodically instead of blocking forever.
+    let motor = LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap();
+    assert!(motor.wait_until(LargeMotor::STATE_HOLDING, None));
+
+    mock.script("state", ["running", "running", ""]);
+    assert!(motor.wait_until_not_moving(None));
+
+    mock.set("state", "running");
+    let start = std::time::Instant::now();
+    assert!(!motor.wait_until_not_moving(Some(Duration::from_millis(50))));
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn test_run_handle() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    let motor = LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap();
+
+    mock.script("state", ["running", "running", "running stalled"]);
+    let handle = motor.start_timed(Duration::from_secs(2), 500).unwrap();
+    assert_eq!(mock.writes("speed_sp"), ["500"]);
+    assert_eq!(mock.last_write("command").as_deref(), Some("run-timed"));
+    assert!(handle.is_running().unwrap());
+    assert_eq!(handle.wait(None).unwrap(), RunOutcome::Stalled);
+
+    mock.script("state", ["running", "running", ""]);
+    let handle = motor.start_timed(Duration::from_secs(2), 500).unwrap();
+    assert_eq!(handle.wait(None).unwrap(), RunOutcome::Completed);
+    assert!(!handle.is_running().unwrap());
+
+    mock.set("state", "running");
+    assert_eq!(
+        handle.wait(Some(Duration::from_millis(50))).unwrap(),
+        RunOutcome::TimedOut
+    );
+}
+
+#[test]
+fn test_linear_actuator_errors() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_motor("act-l12-ev3-50", MotorPort::OutA);
+    mock.set("count_per_m", 2000)
+        .set("full_travel_count", 100)
+        .set("position", 0)
+        .set("state", "running");
+
+    let actuator = LinearActuator::get_from(&ev3, MotorPort::OutA).unwrap();
+    match actuator.extend_to_mm(60.0, 200) {
+        Err(Ev3Error::OutOfRange {
+            value, min, max, ..
+        }) => assert_eq!((value, min, max), (120, 0, 100)),
+        result => panic!("Unexpected result {result:?}"),
+    }
+    assert!(matches!(
+        actuator.extend_by_mm(-1.0, 200),
+        Err(Ev3Error::OutOfRange { value: -2, .. })
+    ));
+    assert!(mock.writes("command").is_empty());
+
+    let timeout = Duration::from_millis(50);
+    match actuator.home(200, Some(timeout)) {
+        Err(Ev3Error::Timeout {
+            timeout: actual, ..
+        }) => assert_eq!(actual, timeout),
+        result => panic!("Unexpected result {result:?}"),
+    }
+    assert_eq!(mock.last_write("command").as_deref(), Some("stop"));
+
+    mock.script("state", ["running", "running stalled"]);
+    actuator.home(200, Some(Duration::from_secs(1))).unwrap();
+    assert_eq!(mock.last_write("position").as_deref(), Some("0"));
+}
+
+#[test]
+fn test_emergency_stop_all() {
+    let ev3 = MockEv3::new();
+    let tacho = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    let dc = ev3.add_device("dc-motor", "rcx-motor", &MotorPort::OutB);
+    let servo = ev3.add_device("servo-motor", "ms-8ch-servo", &MotorPort::OutC);
+
+    emergency_stop_all_from(&ev3, "brake").unwrap();
+    for motor in [&tacho, &dc] {
+        assert_eq!(motor.writes("stop_action"), ["brake"]);
+        assert_eq!(motor.writes("command"), ["stop"]);
+    }
+    assert!(servo.writes("stop_action").is_empty());
+    assert_eq!(servo.writes("command"), ["float"]);
+
+    // A backend without motors has nothing to stop.
+    emergency_stop_all_from(&MockEv3::new(), "coast").unwrap();
+}
+
+#[test]
+fn test_emergency_stop_on_signal() {
+    let ev3 = MockEv3::new();
+    let tacho = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+
+    let stopped =
+        emergency_stop_on_signal_from(Arc::new(ev3.clone()), libc::SIGUSR1, "brake").unwrap();
+    assert!(emergency_stop_on_signal_from(Arc::new(ev3.clone()), libc::SIGUSR1, "brake").is_err());
+    assert!(emergency_stop_on_signal_from(Arc::new(ev3.clone()), libc::SIGKILL, "brake").is_err());
+    assert!(emergency_stop_on_signal_from(Arc::new(ev3), 0, "brake").is_err());
+    assert!(!stopped.is_cancelled());
+
+    unsafe { libc::raise(libc::SIGUSR1) };
+    stopped.wait();
+    assert_eq!(tacho.writes("stop_action"), ["brake"]);
+    assert_eq!(tacho.writes("command"), ["stop"]);
+}
+
+#[test]
+fn test_estop() {
+    let ev3 = MockEv3::new();
+    let button = ev3.add_sensor("lego-ev3-touch", SensorPort::In1);
+    button.script("value0", [0, 0, 1]);
+    let left = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutB);
+    let right = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutC);
+
+    let estop = EStop::touch_sensor(
+        TouchSensor::get_from(&ev3, SensorPort::In1).unwrap(),
+        vec![
+            Box::new(LargeMotor::get_from(&ev3, MotorPort::OutB).unwrap()),
+            Box::new(LargeMotor::get_from(&ev3, MotorPort::OutC).unwrap()),
+        ],
+        EStopOptions::default(),
+    );
+
+    assert!(!estop.token().sleep(Duration::from_secs(1)));
+    assert!(estop.is_triggered());
+    for motor in [&left, &right] {
+        assert_eq!(motor.last_write("stop_action").as_deref(), Some("coast"));
+        assert_eq!(motor.last_write("command").as_deref(), Some("stop"));
+    }
+
+    // A software trigger stops motors that are added later.
+    let ev3 = MockEv3::new();
+    let late = ev3.add_motor("lego-ev3-m-motor", MotorPort::OutA);
+    let estop = EStop::start(
+        || Ok(false),
+        Vec::new(),
+        EStopOptions {
+            stop_action: "brake".to_owned(),
+            ..Default::default()
+        },
+    );
+    assert!(!estop.is_triggered());
+    estop.trigger();
+    assert!(estop.is_triggered());
+    estop.add_motor(Box::new(
+        MediumMotor::get_from(&ev3, MotorPort::OutA).unwrap(),
+    ));
+    assert_eq!(late.last_write("stop_action").as_deref(), Some("brake"));
+    assert_eq!(late.writes("command"), ["stop"]);
+    assert!(estop.take_error().is_none());
+    estop.stop();
+
+    // An unplugged sensor triggers the emergency stop instead of disabling it.
+    let ev3 = MockEv3::new();
+    let button = ev3.add_sensor("lego-ev3-touch", SensorPort::In1);
+    button.set("value0", 0);
+    let motor = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    let estop = EStop::touch_sensor(
+        TouchSensor::get_from(&ev3, SensorPort::In1).unwrap(),
+        vec![Box::new(
+            LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap(),
+        )],
+        EStopOptions::default(),
+    );
+    assert!(estop.token().sleep(Duration::from_millis(50)));
+    button.disconnect();
+    assert!(!estop.token().sleep(Duration::from_secs(1)));
+    assert_eq!(motor.last_write("command").as_deref(), Some("stop"));
+    assert!(estop.take_error().is_some());
+
+    // Invalid IR remote channels are rejected.
+    let ev3 = MockEv3::new();
+    ev3.add_sensor("lego-ev3-ir", SensorPort::In2);
+    for channel in [0, 5] {
+        assert!(matches!(
+            EStop::ir_remote(
+                InfraredSensor::get_from(&ev3, SensorPort::In2).unwrap(),
+                channel,
+                Vec::new(),
+                EStopOptions::default(),
+            ),
+            Err(Ev3Error::OutOfRange { min: 1, max: 4, .. })
+        ));
+    }
+}
+
+#[test]
+fn test_read_snapshot() {
+    let ev3 = MockEv3::new();
+    ev3.add_sensor("lego-ev3-gyro", SensorPort::In1)
+        .set("num_values", 2)
+        .set("value0", 90)
+        .set("value1", -5);
+    ev3.add_sensor("lego-ev3-color", SensorPort::In2)
+        .set("num_values", 1)
+        .script("value0", [40, 41]);
+
+    let gyro = GyroSensor::get_from(&ev3, SensorPort::In1).unwrap();
+    let color = ColorSensor::get_from(&ev3, SensorPort::In2).unwrap();
+
+    let before = std::time::Instant::now();
+    let snapshot = read_snapshot(&[&gyro, &color]).unwrap();
+    assert_eq!(snapshot.readings, [vec![90, -5], vec![40]]);
+    assert_eq!(snapshot.value(0, 1), Some(-5));
+    assert_eq!(snapshot.value(1, 1), None);
+    assert_eq!(snapshot.value(2, 0), None);
+    assert!(snapshot.timestamp >= before);
+
+    let snapshot = read_snapshot_parallel(&[&gyro, &color]).unwrap();
+    assert_eq!(snapshot.readings, [vec![90, -5], vec![41]]);
+
+    ev3.add_sensor("lego-ev3-touch", SensorPort::In3);
+    let touch = TouchSensor::get_from(&ev3, SensorPort::In3).unwrap();
+    assert!(read_snapshot(&[&gyro, &touch]).is_err());
+}
+
+#[test]
+fn test_cached_sensor() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_sensor("lego-ev3-gyro", SensorPort::In1);
+    mock.set("mode", GyroSensor::MODE_GYRO_ANG)
+        .script("value0", [10, 20, 30, 40])
+        .set("value1", 5);
+
+    let gyro = GyroSensor::get_from(&ev3, SensorPort::In1)
+        .unwrap()
+        .cached(Duration::from_millis(50));
+    let shared = gyro.clone();
+    assert_eq!(gyro.get_value0().unwrap(), 10);
+    assert_eq!(shared.get_value(0).unwrap(), 10);
+    assert_eq!(gyro.get_value1().unwrap(), 5);
+
+    std::thread::sleep(Duration::from_millis(60));
+    assert_eq!(shared.get_value0().unwrap(), 20);
+    assert_eq!(gyro.get_value0().unwrap(), 20);
+
+    gyro.set_mode(GyroSensor::MODE_GYRO_RATE).unwrap();
+    assert_eq!(shared.get_value0().unwrap(), 30);
+    assert_eq!(mock.last_write("mode").as_deref(), Some("GYRO-RATE"));
+
+    gyro.invalidate();
+    assert_eq!(gyro.get_value0().unwrap(), 40);
+    assert_eq!(gyro.inner().get_value0().unwrap(), 40);
+}
+
+#[test]
+fn test_duty_ramp() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    mock.set("duty_cycle_sp", 50);
+
+    let ramp = DutyRamp::start(
+        LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap(),
+        DutyRampOptions {
+            rate: 1000.0,
+            interval: Duration::from_millis(5),
+        },
+    );
+    assert_eq!(ramp.get_current(), 50);
+    assert!(ramp.is_settled());
+
+    ramp.set_target(-150);
+    assert_eq!(ramp.get_target(), -100);
+    let start = std::time::Instant::now();
+    while !ramp.is_settled() {
+        assert!(start.elapsed() < Duration::from_secs(2));
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    // A full reversal takes 150 ms at 1000 % per second.
+    assert!(start.elapsed() >= Duration::from_millis(140));
+    ramp.stop().unwrap();
+
+    let writes = mock
+        .writes("duty_cycle_sp")
+        .iter()
+        .map(|value| value.parse::<i32>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(writes.last(), Some(&-100));
+    assert!(writes.len() > 3);
+    assert!(writes.windows(2).all(|pair| pair[1] < pair[0]));
+    assert_eq!(mock.last_write("command"), None);
+
+    // The thread ends if the motor is unplugged.
+    let ramp = DutyRamp::start(
+        LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap(),
+        DutyRampOptions::default(),
+    );
+    mock.disconnect();
+    ramp.set_target(0);
+    let start = std::time::Instant::now();
+    while ramp.is_running() {
+        assert!(start.elapsed() < Duration::from_secs(2));
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    assert!(ramp.stop().is_err());
+}
+
+#[test]
+fn test_discover_from() {
+    let ev3 = MockEv3::new();
+    ev3.add_motor("lego-ev3-m-motor", MotorPort::OutB)
+        .set("position", 42);
+    ev3.add_sensor("lego-ev3-color", SensorPort::In1)
+        .set("value0", 17);
+    ev3.add_sensor("lego-ev3-touch", SensorPort::In2);
+
+    let brick = Ev3::discover_from(&ev3).unwrap();
+    assert_eq!(brick.descriptors().len(), 3);
+    assert_eq!(brick.color_sensors().len(), 1);
+    assert_eq!(brick.color_sensors()[0].get_value0().unwrap(), 17);
+    assert_eq!(brick.touch_sensors().len(), 1);
+    assert!(brick.large_motors().is_empty());
+    assert_eq!(brick.medium_motors().len(), 1);
+
+    assert_eq!(
+        brick
+            .motor_on(MotorPort::OutB)
+            .unwrap()
+            .get_position()
+            .unwrap(),
+        42
+    );
+    assert!(brick.motor_on(MotorPort::OutA).is_none());
+    assert_eq!(
+        brick
+            .sensor_on(&SensorPort::In2)
+            .unwrap()
+            .get_driver_name()
+            .unwrap(),
+        "lego-ev3-touch"
+    );
+    assert!(brick.led().is_none());
+}
+
+#[test]
+fn test_wedo_devices() {
+    assert_eq!(WedoPort::parse("b").unwrap(), WedoPort::B);
+    assert_eq!(WedoPort::parse("wedo1").unwrap(), WedoPort::A);
+    assert!(WedoPort::parse("C").is_err());
+    assert!(WedoPort::A.matches_address("usb-1.2:wedo1"));
+    assert!(!WedoPort::A.matches_address("usb-1.2:wedo2"));
+
+    let ev3 = MockEv3::new();
+    let motor = ev3.add_device("dc-motor", "wedo-motor", &WedoPort::A);
+    let tilt_mock = ev3.add_device("lego-sensor", "wedo-tilt", &WedoPort::B);
+    tilt_mock
+        .set("mode", WedoTiltSensor::MODE_TILT)
+        .set("value0", 3)
+        .set("value1", -1);
+    ev3.add_device("lego-sensor", "wedo-motion", &WedoPort::A)
+        .set("mode", WedoMotionSensor::MODE_RAW)
+        .set("value0", 42);
+    ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+
+    let wedo_motor = WedoMotor::get_from(&ev3, WedoPort::A).unwrap();
+    wedo_motor.set_duty_cycle_sp(60).unwrap();
+    wedo_motor.run_direct().unwrap();
+    assert_eq!(motor.writes("duty_cycle_sp"), ["60"]);
+    assert_eq!(motor.last_write("command").as_deref(), Some("run-direct"));
+    assert!(WedoMotor::get_from(&ev3, WedoPort::B).is_err());
+
+    let mut tilt = WedoTiltSensor::find_from(&ev3).unwrap();
+    assert_eq!(tilt.get_tilt().unwrap(), WedoTilt::Left);
+    assert!(matches!(
+        tilt.get_tilt_axis(),
+        Err(Ev3Error::WrongMode { .. })
+    ));
+    tilt.set_auto_mode_switch(true);
+    assert_eq!(tilt.get_tilt_axis().unwrap(), (3, -1));
+    assert_eq!(tilt_mock.writes("mode"), [WedoTiltSensor::MODE_TILT_AXIS]);
+
+    let mut motion = WedoMotionSensor::get_from(&ev3, WedoPort::A).unwrap();
+    assert!(matches!(
+        motion.get_proximity(),
+        Err(Ev3Error::WrongMode { .. })
+    ));
+    motion.set_auto_mode_switch(true);
+    assert_eq!(motion.get_proximity().unwrap(), 42);
+
+    // The tacho motor on the same backend is not a WeDo device.
+    assert_eq!(LargeMotor::list_from(&ev3).unwrap().len(), 1);
+    assert_eq!(WedoMotor::list_from(&ev3).unwrap().len(), 1);
+}
+
+#[test]
+fn test_driver_cache() {
+    let ev3 = MockEv3::new();
+    ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA)
+        .set("speed_sp", 0)
+        .set("time_sp", 0);
+    let name =
+        Driver::find_name_by_driver_from(&ev3, "tacho-motor", &["lego-ev3-l-motor"]).unwrap();
+    let driver = Driver::from_backend(std::sync::Arc::new(ev3.clone()), "tacho-motor", &name);
+    assert_eq!(driver.get_cache_strategy(), CacheStrategy::Unbounded);
+
+    for attribute in ["position", "speed", "speed_sp", "time_sp"] {
+        driver.get_attribute(attribute).unwrap();
+    }
+    assert_eq!(driver.cache_len(), 4);
+
+    assert!(driver.evict("speed"));
+    assert!(!driver.evict("speed"));
+    assert_eq!(driver.cache_len(), 3);
+
+    // The attributes that were opened first are dropped first.
+    let clone = driver.clone();
+    clone.set_cache_strategy(CacheStrategy::Capacity(2));
+    assert_eq!(driver.cache_len(), 2);
+    driver.get_attribute("speed").unwrap();
+    assert_eq!(driver.cache_len(), 2);
+    assert!(!driver.evict("speed_sp"));
+    assert!(driver.evict("time_sp"));
+
+    driver.clear_cache();
+    assert_eq!(driver.cache_len(), 0);
+
+    driver.set_cache_strategy(CacheStrategy::Disabled);
+    assert_eq!(
+        driver
+            .get_attribute("position")
+            .unwrap()
+            .get::<i32>()
+            .unwrap(),
+        0
+    );
+    assert_eq!(driver.cache_len(), 0);
+
+    // Devices drop their cached attributes, e.g. to release file handles.
+    let motor = LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap();
+    motor.get_position().unwrap();
+    motor.clear_cache();
+    assert_eq!(motor.get_position().unwrap(), 0);
+}
+
+#[test]
+fn test_unsupported_bin_data_format() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_sensor("lego-ev3-color", SensorPort::In1);
+    mock.set("mode", ColorSensor::MODE_RGB_RAW)
+        .set("bin_data_format", "s16")
+        .set("bin_data", "\u{a}\0\u{14}\0\u{1e}\0");
+
+    let sensor = ColorSensor::get_from(&ev3, SensorPort::In1).unwrap();
+    assert_eq!(sensor.get_bin_data().unwrap(), (10, 20, 30));
+
+    mock.set("mode", ColorSensor::MODE_COL_REFLECT)
+        .set("bin_data_format", "s8");
+    match sensor.get_bin_data() {
+        Err(Ev3Error::UnsupportedFormat {
+            format, expected, ..
+        }) => {
+            assert_eq!(format, "s8");
+            assert_eq!(expected, ["s16"]);
+        }
+        result => panic!("Unexpected result {result:?}"),
+    }
+
+    ev3.add_sensor("pixy-lego", SensorPort::In2)
+        .set("mode", "ALL")
+        .set("bin_data_format", "s64")
+        .set("num_values", 5)
+        .set("bin_data", "");
+    let pixy = PixyCamera::get_from(&ev3, SensorPort::In2).unwrap();
+    assert!(matches!(
+        pixy.get_blocks(),
+        Err(Ev3Error::UnsupportedFormat { format, .. }) if format == "s64"
+    ));
+}
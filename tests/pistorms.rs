@@ -0,0 +1,26 @@
+use ev3dev_lang_rust::{motors::MotorPort, sensors::SensorPort, Port};
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_input_port_mapping() {
+    assert_eq!(SensorPort::In1.address(), "pistorms:BAS1".to_string());
+    assert_eq!(SensorPort::In2.address(), "pistorms:BAS2".to_string());
+    assert_eq!(SensorPort::In3.address(), "pistorms:BBS1".to_string());
+    assert_eq!(SensorPort::In4.address(), "pistorms:BBS2".to_string());
+}
+
+#[test]
+fn test_output_port_mapping() {
+    assert_eq!(MotorPort::OutA.address(), "pistorms:BAM1".to_string());
+    assert_eq!(MotorPort::OutB.address(), "pistorms:BAM2".to_string());
+    assert_eq!(MotorPort::OutC.address(), "pistorms:BBM1".to_string());
+    assert_eq!(MotorPort::OutD.address(), "pistorms:BBM2".to_string());
+}
+
+#[test]
+fn test_port_address_matching() {
+    assert!(SensorPort::In4.matches_address("pistorms:BBS2"));
+    assert!(!SensorPort::In4.matches_address("pistorms:BBS1"));
+    assert!(MotorPort::OutA.matches_address("pistorms:BAM1"));
+}
@@ -0,0 +1,19 @@
+use ev3dev_lang_rust::Technology;
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_technology_names() {
+    assert_eq!(Technology::from_name("Li-ion"), Technology::LiIon);
+    assert_eq!(Technology::from_name("Unknown"), Technology::Unknown);
+    assert_eq!(
+        Technology::from_name("Li-sulfur"),
+        Technology::Other("Li-sulfur".to_owned())
+    );
+
+    for name in [
+        "Unknown", "NiMH", "Li-ion", "Li-poly", "LiFe", "NiCd", "LiMn",
+    ] {
+        assert_eq!(Technology::from_name(name).to_string(), name);
+    }
+}
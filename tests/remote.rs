@@ -0,0 +1,113 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::thread;
+
+use ev3dev_lang_rust::remote::{RemoteEv3, Server};
+use ev3dev_lang_rust::Backend;
+
+extern crate ev3dev_lang_rust;
+
+/// Starts a server on a free local port and returns a function that sends a request line.
+fn start_server() -> impl FnMut(&str) -> String {
+    let server = Server::bind("127.0.0.1:0")
+        .unwrap()
+        .with_stop_on_disconnect(false);
+    let address = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    let stream = TcpStream::connect(address).unwrap();
+    let mut writer = stream.try_clone().unwrap();
+    let mut reader = BufReader::new(stream);
+
+    move |request| {
+        writeln!(writer, "{request}").unwrap();
+        let mut response = String::new();
+        reader.read_line(&mut response).unwrap();
+        response.trim_end().to_owned()
+    }
+}
+
+#[test]
+fn test_list_missing_class() {
+    let mut send = start_server();
+
+    assert_eq!(
+        send(r#"{"id":1,"method":"list","class":"servo-motor"}"#),
+        r#"{"id":1,"result":[]}"#
+    );
+}
+
+#[test]
+fn test_errors() {
+    let mut send = start_server();
+
+    assert!(send("{invalid").starts_with(r#"{"id":null,"error":"#));
+    assert!(send(r#"{"id":2}"#).starts_with(r#"{"id":2,"error":"#));
+    // Deeply nested documents are rejected instead of overflowing the stack of the server.
+    assert!(send(&"[".repeat(100_000)).starts_with(r#"{"id":null,"error":"#));
+    // A high surrogate must be followed by a low surrogate.
+    assert!(send(r#"{"id":1,"method":"x\ud800\u0041"}"#).starts_with(r#"{"id":null,"error":"#));
+    assert_eq!(
+        send(r#"{"id":3,"method":"fly ä\"x"}"#),
+        r#"{"id":3,"error":"InternalError: Unknown method `fly ä\"x`!"}"#
+    );
+    assert_eq!(
+        send(
+            r#"{"id":"a","method":"get","class":"tacho-motor","name":"..","attribute":"address"}"#
+        ),
+        r#"{"id":"a","error":"InternalError: Invalid name `..`!"}"#
+    );
+    for attribute in [
+        "",
+        ".",
+        "speed_pid/..",
+        "speed_pid//Kp",
+        "a/b/c",
+        "/address",
+    ] {
+        let request = format!(
+            r#"{{"id":6,"method":"get","class":"tacho-motor","name":"motor0","attribute":"{attribute}"}}"#
+        );
+        assert_eq!(
+            send(&request),
+            format!(r#"{{"id":6,"error":"InternalError: Invalid attribute `{attribute}`!"}}"#)
+        );
+    }
+    // Attributes may be nested one level deep, e.g. the PID constants.
+    assert!(send(
+        r#"{"id":7,"method":"get","class":"tacho-motor","name":"motor0","attribute":"hold_pid/Kp"}"#
+    )
+    .starts_with(r#"{"id":7,"error":"Could not access"#));
+
+    // Only motors, sensors, ports and leds are exposed.
+    assert_eq!(
+        send(r#"{"id":4,"method":"list","class":"gpio"}"#),
+        r#"{"id":4,"error":"InternalError: Class `gpio` is not exposed!"}"#
+    );
+    assert_eq!(
+        send(
+            r#"{"id":5,"method":"set","class":"backlight","name":"lcd","attribute":"brightness","value":"0"}"#
+        ),
+        r#"{"id":5,"error":"InternalError: Class `backlight` is not exposed!"}"#
+    );
+}
+
+#[test]
+fn test_remote_client() {
+    let server = Server::bind("127.0.0.1:0")
+        .unwrap()
+        .with_stop_on_disconnect(false);
+    let address = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    let ev3 = RemoteEv3::connect(address).unwrap();
+    assert_eq!(ev3.scan(Some("servo-motor")).unwrap(), []);
+
+    let attribute = ev3
+        .open_attribute("tacho-motor", "motor-does-not-exist", "position")
+        .unwrap();
+    assert!(attribute.get::<i32>().is_err());
+
+    // The connection is still usable after an error.
+    assert!(ev3.find_names("servo-motor").unwrap().is_empty());
+}
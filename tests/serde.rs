@@ -0,0 +1,52 @@
+use ev3dev_lang_rust::motors::MotorPort;
+use ev3dev_lang_rust::sensors::{MuxChannel, MuxPort, SensorPort};
+use ev3dev_lang_rust::{DeviceDescriptor, Ev3Error};
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_descriptor_round_trip() {
+    let descriptor = DeviceDescriptor {
+        class: "tacho-motor".to_owned(),
+        driver_name: Some("lego-ev3-l-motor".to_owned()),
+        address: Some("ev3-ports:outA".to_owned()),
+        name: "motor0".to_owned(),
+    };
+
+    let json = serde_json::to_string(&descriptor).unwrap();
+    assert_eq!(
+        serde_json::from_str::<DeviceDescriptor>(&json).unwrap(),
+        descriptor
+    );
+}
+
+#[test]
+fn test_ports() {
+    assert_eq!(
+        serde_json::to_string(&MotorPort::OutB).unwrap(),
+        r#""OutB""#
+    );
+
+    let port = MuxPort::new(SensorPort::In1, MuxChannel::Mux2);
+    assert_eq!(
+        serde_json::to_string(&port).unwrap(),
+        r#"{"port":"In1","channel":"Mux2"}"#
+    );
+}
+
+#[test]
+fn test_error() {
+    let error = Ev3Error::NotConnected {
+        device: "LargeMotor".to_owned(),
+        port: None,
+        path: None,
+    };
+
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({
+            "kind": "NotConnected",
+            "message": "'LargeMotor' not connected at port None!",
+        })
+    );
+}
@@ -0,0 +1,66 @@
+use std::time::Duration;
+
+use ev3dev_lang_rust::sound::{RmdMelody, RsoSound};
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_rso_decode() {
+    let data = [
+        0x01, 0x00, 0x00, 0x04, 0x1f, 0x40, 0x00, 0x00, 128, 255, 0, 128,
+    ];
+    let sound = RsoSound::decode(&data).unwrap();
+
+    assert_eq!(sound.sample_rate, 8000);
+    assert_eq!(sound.samples, vec![128, 255, 0, 128]);
+    assert_eq!(sound.duration(), Duration::from_micros(500));
+}
+
+#[test]
+fn test_rso_ignores_trailing_bytes() {
+    let data = [0x01, 0x00, 0x00, 0x02, 0x1f, 0x40, 0x00, 0x00, 1, 2, 3, 4];
+    let sound = RsoSound::decode(&data).unwrap();
+
+    assert_eq!(sound.samples, vec![1, 2]);
+}
+
+#[test]
+fn test_rso_decode_errors() {
+    // Header too short
+    assert!(RsoSound::decode(&[0x01, 0x00, 0x00]).is_err());
+    // Truncated data
+    assert!(RsoSound::decode(&[0x01, 0x00, 0x00, 0x04, 0x1f, 0x40, 0x00, 0x00, 1]).is_err());
+    // Compressed
+    assert!(RsoSound::decode(&[0x01, 0x01, 0x00, 0x00, 0x1f, 0x40, 0x00, 0x00]).is_err());
+    // Melody
+    assert!(RsoSound::decode(&[0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]).is_err());
+}
+
+#[test]
+fn test_rmd_decode() {
+    let data = [
+        0x05, 0x00, 0x00, 0x0c, 0x00, 0x00, 0x00, 0x00, // header
+        0x01, 0xb8, 0x00, 0xc8, // 440 Hz, 200 ms
+        0x00, 0x00, 0x00, 0x64, // rest, 100 ms
+        0x03, 0x70, 0x01, 0x90, // 880 Hz, 400 ms
+    ];
+    let melody = RmdMelody::decode(&data).unwrap();
+
+    assert_eq!(melody.tones, vec![(440, 200), (0, 100), (880, 400)]);
+    assert_eq!(
+        melody.to_tone_sequence(),
+        vec![(440.0, 200, 100), (880.0, 400, 0)]
+    );
+}
+
+#[test]
+fn test_rmd_leading_rest() {
+    let melody = RmdMelody {
+        tones: vec![(0, 50), (0, 50), (440, 100)],
+    };
+
+    assert_eq!(
+        melody.to_tone_sequence(),
+        vec![(0.0, 0, 100), (440.0, 100, 0)]
+    );
+}
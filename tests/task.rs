@@ -0,0 +1,34 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use ev3dev_lang_rust::task;
+
+extern crate ev3dev_lang_rust;
+
+#[test]
+fn test_scope_cancels_and_joins_tasks() {
+    let iterations = AtomicUsize::new(0);
+
+    task::scope(|s| {
+        s.spawn(|token| {
+            while !token.is_cancelled() {
+                iterations.fetch_add(1, Ordering::SeqCst);
+                token.sleep(Duration::from_millis(1));
+            }
+        });
+        std::thread::sleep(Duration::from_millis(20));
+    });
+
+    let count = iterations.load(Ordering::SeqCst);
+    std::thread::sleep(Duration::from_millis(20));
+    assert!(count > 0);
+    assert_eq!(iterations.load(Ordering::SeqCst), count);
+}
+
+#[test]
+fn test_task_cancel_wakes_sleep() {
+    task::scope(|s| {
+        let handle = s.spawn(|token| token.sleep(Duration::from_secs(60)));
+        assert!(!handle.cancel_and_join().unwrap());
+    });
+}
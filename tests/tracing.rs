@@ -0,0 +1,50 @@
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+use ev3dev_lang_rust::MockEv3;
+
+extern crate ev3dev_lang_rust;
+
+#[derive(Clone, Default)]
+struct Output(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_attribute_events() {
+    let output = Output::default();
+    let writer = output.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::TRACE)
+        .with_writer(move || writer.clone())
+        .finish();
+
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    mock.set("position", 42);
+
+    tracing::subscriber::with_default(subscriber, || {
+        let motor = LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap();
+        motor.run_forever().unwrap();
+        motor.get_position().unwrap();
+        assert!(motor.get_duty_cycle().is_err());
+    });
+
+    let output = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+    assert!(output.contains("find_name_by_port_and_driver_from"));
+    assert!(output.contains("return=\"motor0\""));
+    assert!(output
+        .contains("attribute write operation=\"write\" path=/sys/class/tacho-motor/motor0/command value=run-forever"));
+    assert!(output.contains("path=/sys/class/tacho-motor/motor0/position value=42"));
+    assert!(output.contains("attribute read failed"));
+}
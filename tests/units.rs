@@ -0,0 +1,57 @@
+use ev3dev_lang_rust::motors::{LargeMotor, MotorPort};
+use ev3dev_lang_rust::sensors::{GyroSensor, HeadingSensor, SensorPort};
+use ev3dev_lang_rust::units::angle::degree;
+use ev3dev_lang_rust::units::angular_velocity::{degree_per_second, revolution_per_minute};
+use ev3dev_lang_rust::units::{Angle, AngularVelocity};
+use ev3dev_lang_rust::MockEv3;
+
+extern crate ev3dev_lang_rust;
+
+fn assert_close(actual: f32, expected: f32) {
+    assert!(
+        (actual - expected).abs() < 1e-3,
+        "{actual} is not {expected}"
+    );
+}
+
+#[test]
+fn test_motor_units() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_motor("lego-ev3-l-motor", MotorPort::OutA);
+    mock.set("position", 720).set("speed", -180);
+
+    let motor = LargeMotor::get_from(&ev3, MotorPort::OutA).unwrap();
+    assert_close(motor.get_position_typed().unwrap().get::<degree>(), 720.0);
+    assert_close(
+        motor.get_speed_typed().unwrap().get::<degree_per_second>(),
+        -180.0,
+    );
+
+    motor
+        .set_speed_sp_typed(AngularVelocity::new::<revolution_per_minute>(60.0))
+        .unwrap();
+    motor
+        .set_position_sp_typed(Angle::new::<degree>(-90.0))
+        .unwrap();
+    assert_eq!(mock.last_write("speed_sp").as_deref(), Some("360"));
+    assert_eq!(mock.last_write("position_sp").as_deref(), Some("-90"));
+}
+
+#[test]
+fn test_sensor_units() {
+    let ev3 = MockEv3::new();
+    let mock = ev3.add_sensor("lego-ev3-gyro", SensorPort::In2);
+    mock.set("mode", "GYRO-G&A")
+        .set("value0", -45)
+        .set("value1", 12);
+
+    let gyro = GyroSensor::get_from(&ev3, SensorPort::In2).unwrap();
+    assert_close(gyro.get_angle_typed().unwrap().get::<degree>(), -45.0);
+    assert_close(gyro.get_heading_typed().unwrap().get::<degree>(), -45.0);
+    assert_close(
+        gyro.get_rotational_speed_typed()
+            .unwrap()
+            .get::<degree_per_second>(),
+        12.0,
+    );
+}